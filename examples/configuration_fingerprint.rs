@@ -0,0 +1,28 @@
+#[iftree::include_file_tree(
+    "
+paths = '/examples/assets/credits.md'
+
+[fingerprint]
+length = 8
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+    fingerprinted_path: &'static str,
+}
+
+fn main() {
+    use base::examples::assets;
+
+    assert_eq!(
+        assets::CREDITS_MD.fingerprinted_path,
+        "examples/assets/credits.41decc43.md",
+    );
+
+    let asset = get_by_fingerprinted_path("examples/assets/credits.41decc43.md").unwrap();
+    assert_eq!(asset.relative_path, "examples/assets/credits.md");
+
+    assert_eq!(BUNDLE_HASH.len(), 64);
+    assert_eq!(get_folder_hash(""), Some(BUNDLE_HASH));
+    assert!(get_folder_hash("examples/assets").is_some());
+}
@@ -0,0 +1,19 @@
+#[iftree::include_file_tree(
+    "
+paths = '/examples/assets/**/*.json'
+
+[[transform]]
+paths = '*.json'
+minify_json = true
+"
+)]
+pub struct Asset {
+    contents_str: &'static str,
+}
+
+fn main() {
+    use base::examples::assets;
+
+    assert_eq!(assets::configuration::MENU_JSON.contents_str, r#""Start""#);
+    assert_eq!(assets::world::PHYSICAL_CONSTANTS_JSON.contents_str, "7e-3");
+}
@@ -0,0 +1,145 @@
+use crate::model;
+use std::env;
+use std::path;
+
+pub fn main(
+    name: &str,
+    get_environment_variable: &dyn Fn(&str) -> Result<String, env::VarError>,
+    is_file: &dyn Fn(&path::Path) -> bool,
+) -> model::Result<path::PathBuf> {
+    let mut folder = get_target_folder(get_environment_variable, is_file)?;
+    folder.extend([CACHE_FOLDER_NAME, name]);
+    Ok(folder)
+}
+
+const CACHE_FOLDER_NAME: &str = "iftree";
+
+fn get_target_folder(
+    get_environment_variable: &dyn Fn(&str) -> Result<String, env::VarError>,
+    is_file: &dyn Fn(&path::Path) -> bool,
+) -> model::Result<path::PathBuf> {
+    // A relative target folder is relative to the working directory like for
+    // Cargo, but included files would be resolved from the source file.
+    if let Ok(folder) = get_environment_variable(TARGET_FOLDER_VARIABLE) {
+        return path::absolute(&folder).map_err(|source| model::Error::FileRead {
+            path: folder.into(),
+            source: model::IoError(source),
+        });
+    }
+
+    let manifest_folder =
+        path::PathBuf::from(get_environment_variable(MANIFEST_FOLDER_VARIABLE).map_err(
+            |source| model::Error::EnvironmentVariable {
+                name: MANIFEST_FOLDER_VARIABLE.into(),
+                source,
+            },
+        )?);
+
+    // Cargo tags its target folder, which is shared by all workspace members.
+    Ok(manifest_folder
+        .ancestors()
+        .map(|folder| folder.join(TARGET_FOLDER_NAME))
+        .find(|folder| is_file(&folder.join(CACHE_FOLDER_TAG)))
+        .unwrap_or_else(|| manifest_folder.join(TARGET_FOLDER_NAME)))
+}
+
+const TARGET_FOLDER_VARIABLE: &str = "CARGO_TARGET_DIR";
+
+const MANIFEST_FOLDER_VARIABLE: &str = "CARGO_MANIFEST_DIR";
+
+const TARGET_FOLDER_NAME: &str = "target";
+
+const CACHE_FOLDER_TAG: &str = "CACHEDIR.TAG";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_target_folder_variable_it_handles() {
+        let actual = main(
+            "commands",
+            &|name| {
+                Ok((if name == "CARGO_TARGET_DIR" {
+                    "/a/b"
+                } else {
+                    unreachable!()
+                })
+                .into())
+            },
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap();
+        let expected = path::PathBuf::from("/a/b/iftree/commands");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_relative_target_folder_variable_it_resolves_from_working_directory() {
+        let actual = main(
+            "commands",
+            &|name| {
+                Ok((if name == "CARGO_TARGET_DIR" {
+                    "a/b"
+                } else {
+                    unreachable!()
+                })
+                .into())
+            },
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap();
+        let expected = env::current_dir().unwrap().join("a/b/iftree/commands");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_tagged_target_folder_in_ancestor_it_handles() {
+        let actual = main(
+            "commands",
+            &|name| match name {
+                "CARGO_MANIFEST_DIR" => Ok("/a/b".into()),
+                _ => Err(env::VarError::NotPresent),
+            },
+            &|path| path == path::Path::new("/a/target/CACHEDIR.TAG"),
+        );
+
+        let actual = actual.unwrap();
+        let expected = path::PathBuf::from("/a/target/iftree/commands");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_no_tagged_target_folder_it_defaults_to_manifest_folder() {
+        let actual = main(
+            "commands",
+            &|name| match name {
+                "CARGO_MANIFEST_DIR" => Ok("/a/b".into()),
+                _ => Err(env::VarError::NotPresent),
+            },
+            &|_| false,
+        );
+
+        let actual = actual.unwrap();
+        let expected = path::PathBuf::from("/a/b/target/iftree/commands");
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_no_manifest_folder_variable_it_errs() {
+        let actual = main(
+            "commands",
+            &|_| Err(env::VarError::NotPresent),
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::EnvironmentVariable {
+            name: "CARGO_MANIFEST_DIR".into(),
+            source: env::VarError::NotPresent,
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,5 @@
+mod get_folder;
+mod write_file;
+
+pub use get_folder::main as get_folder;
+pub use write_file::main as write_file;
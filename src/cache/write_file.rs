@@ -0,0 +1,35 @@
+use std::fs;
+use std::io;
+use std::path;
+use std::process;
+
+pub fn main(file: &path::Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(folder) = file.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    // Writing to a temporary file first means concurrent builds never read a
+    // partially written file.
+    let temporary_file = file.with_extension(format!("{}.tmp", process::id()));
+    fs::write(&temporary_file, contents)?;
+    fs::rename(temporary_file, file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let folder = tempfile::tempdir().unwrap();
+        let file = folder.path().join("a").join("b");
+
+        main(&file, b"c").unwrap();
+
+        let actual = (
+            fs::read(&file).unwrap(),
+            fs::read_dir(folder.path().join("a")).unwrap().count(),
+        );
+        let expected = (b"c".to_vec(), 1);
+        assert_eq!(actual, expected);
+    }
+}
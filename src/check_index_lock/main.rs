@@ -0,0 +1,130 @@
+use crate::model;
+use std::io;
+use std::path;
+
+// The lock must cover the files of all `cfg` variants, so a listed file is
+// only gone if it is gone from each variant. Expansion merely reads the lock
+// unless updating is requested, which appends new files in the order met.
+pub fn main(
+    locks: &[model::IndexLock],
+    update: bool,
+    write_file: &dyn Fn(&path::Path, &str) -> io::Result<()>,
+) -> model::Result<()> {
+    let Some(lock) = locks.first() else {
+        return Ok(());
+    };
+
+    if let Some(path) = lock
+        .missing_paths
+        .iter()
+        .find(|path| locks.iter().all(|lock| lock.missing_paths.contains(path)))
+    {
+        return Err(model::Error::MissingLockedFile {
+            path: path.clone(),
+            lock_file: lock.file.clone(),
+        });
+    }
+
+    let mut new_paths = vec![];
+    for path in locks.iter().flat_map(|lock| &lock.new_paths) {
+        if !new_paths.contains(path) {
+            new_paths.push(path.clone());
+        }
+    }
+
+    if new_paths.is_empty() {
+        Ok(())
+    } else if update {
+        let contents = lock
+            .locked_paths
+            .iter()
+            .chain(&new_paths)
+            .map(|path| format!("{path}\n"))
+            .collect::<String>();
+        write_file(&lock.file, &contents).map_err(|source| model::Error::FileWrite {
+            path: lock.file.clone(),
+            source: model::IoError(source),
+        })
+    } else {
+        Err(model::Error::UnlockedFiles {
+            paths: new_paths,
+            lock_file: lock.file.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell;
+
+    fn get_lock(new_paths: &[&str], missing_paths: &[&str]) -> model::IndexLock {
+        model::IndexLock {
+            file: "/x.lock".into(),
+            locked_paths: vec!["a".into(), "b".into()],
+            new_paths: new_paths.iter().map(|path| path.to_string()).collect(),
+            missing_paths: missing_paths.iter().map(|path| path.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn given_no_new_files_it_handles() {
+        let actual = main(
+            &[get_lock(&[], &["a"]), get_lock(&[], &[])],
+            false,
+            &|_, _| unreachable!(),
+        );
+
+        assert_eq!(actual, Ok(()));
+    }
+
+    #[test]
+    fn given_file_missing_from_all_variants_it_errs() {
+        let actual = main(
+            &[get_lock(&[], &["a", "b"]), get_lock(&[], &["b"])],
+            true,
+            &|_, _| unreachable!(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::MissingLockedFile {
+            path: "b".into(),
+            lock_file: "/x.lock".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_new_files_it_errs() {
+        let actual = main(
+            &[get_lock(&["d", "c"], &[]), get_lock(&["c", "e"], &[])],
+            false,
+            &|_, _| unreachable!(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::UnlockedFiles {
+            paths: vec!["d".into(), "c".into(), "e".into()],
+            lock_file: "/x.lock".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_update_it_appends_new_files_of_all_variants() {
+        let written = cell::RefCell::new(None);
+
+        let actual = main(
+            &[get_lock(&["d", "c"], &[]), get_lock(&["c", "e"], &[])],
+            true,
+            &|file, contents| {
+                *written.borrow_mut() = Some((file.to_path_buf(), contents.to_string()));
+                Ok(())
+            },
+        );
+
+        let actual = (actual, written.into_inner());
+        let expected = (Ok(()), Some(("/x.lock".into(), "a\nb\nd\nc\ne\n".into())));
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections;
+use std::iter;
+
+// The longest matching suffix wins, so `.js.map` takes precedence over `.map`.
+pub fn main(
+    variants: &collections::BTreeMap<String, String>,
+    relative: &[String],
+) -> Option<(String, Vec<String>)> {
+    let (filename, folders) = relative.split_last()?;
+    let (name, stem) = variants
+        .iter()
+        .filter_map(|(name, suffix)| {
+            filename
+                .strip_suffix(suffix.as_str())
+                .filter(|stem| !stem.is_empty())
+                .map(|stem| (name, stem))
+        })
+        .min_by_key(|(_, stem)| stem.len())?;
+
+    let stem = folders
+        .iter()
+        .cloned()
+        .chain(iter::once(stem.into()))
+        .collect();
+    Some((name.clone(), stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_overlapping_suffixes_it_prefers_longest() {
+        let actual = main(
+            &[("a".into(), ".gz".into()), ("b".into(), ".tar.gz".into())].into(),
+            &["c".into(), "d.tar.gz".into()],
+        );
+
+        let expected = Some(("b".into(), vec!["c".into(), "d".into()]));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_name_equal_to_suffix_it_is_no_variant() {
+        let actual = main(&[("a".into(), ".gz".into())].into(), &[".gz".into()]);
+
+        assert_eq!(actual, None);
+    }
+}
@@ -0,0 +1,3 @@
+mod get_variant;
+
+pub use get_variant::main as get_variant;
@@ -0,0 +1,373 @@
+use crate::model;
+use ignore::gitignore;
+use ignore::overrides;
+use std::iter;
+
+pub struct Filter(Matcher);
+
+enum Matcher {
+    Gitignore(overrides::Override),
+    // Unlike an override, this keeps apart paths that no pattern matches.
+    GitignoreFaithful(gitignore::Gitignore),
+    Sets {
+        included: regex::bytes::RegexSet,
+        excluded: regex::bytes::RegexSet,
+    },
+}
+
+impl Filter {
+    pub fn new(patterns: &str, syntax: &model::PatternSyntax) -> model::Result<Self> {
+        let matcher = match syntax.language {
+            model::PatternLanguage::Gitignore => {
+                let mut builder = overrides::OverrideBuilder::new(ROOT);
+                builder.case_insensitive(syntax.case_insensitive)?;
+                for pattern in patterns.lines() {
+                    builder.add(pattern)?;
+                }
+                Matcher::Gitignore(builder.build()?)
+            }
+
+            model::PatternLanguage::GitignoreFaithful => {
+                let mut builder = gitignore::GitignoreBuilder::new(ROOT);
+                builder.case_insensitive(syntax.case_insensitive)?;
+                for pattern in patterns.lines() {
+                    builder.add_line(None, pattern)?;
+                }
+                Matcher::GitignoreFaithful(builder.build()?)
+            }
+
+            model::PatternLanguage::Glob | model::PatternLanguage::Regex => {
+                let mut included = vec![];
+                let mut excluded = vec![];
+                // Like in the other syntaxes, "#" starts a comment and "!" negates.
+                for pattern in patterns
+                    .lines()
+                    .filter(|pattern| !(pattern.trim().is_empty() || pattern.starts_with('#')))
+                {
+                    match pattern.strip_prefix('!') {
+                        None => included.push(get_regex(pattern, syntax)?),
+                        Some(pattern) => excluded.push(get_regex(pattern, syntax)?),
+                    }
+                }
+                Matcher::Sets {
+                    included: get_regex_set(included, syntax)?,
+                    excluded: get_regex_set(excluded, syntax)?,
+                }
+            }
+        };
+        Ok(Filter(matcher))
+    }
+
+    pub fn is_match(&self, relative_path: &str) -> bool {
+        match &self.0 {
+            Matcher::Gitignore(filter) => {
+                let components = relative_path.split(FOLDER_SEPARATOR).collect::<Vec<_>>();
+
+                (1..components.len()).all(|length| {
+                    let folder = components[..length].join(FOLDER_SEPARATOR);
+                    !filter.matched(folder, true).is_ignore()
+                }) && filter.matched(relative_path, false).is_whitelist()
+            }
+
+            // As in Git, a path takes the decision of the last pattern that
+            // matches it or else of its closest matched folder, and a folder
+            // that is excluded cannot have any of its contents included.
+            Matcher::GitignoreFaithful(filter) => {
+                let mut folders = relative_path
+                    .match_indices(FOLDER_SEPARATOR)
+                    .map(|(index, _)| &relative_path[..index])
+                    .collect::<Vec<_>>();
+                folders.reverse();
+
+                !folders
+                    .iter()
+                    .any(|folder| filter.matched(folder, true).is_whitelist())
+                    && iter::once(filter.matched(relative_path, false))
+                        .chain(folders.iter().map(|folder| filter.matched(folder, true)))
+                        .find(|decision| !decision.is_none())
+                        .is_some_and(|decision| decision.is_ignore())
+            }
+
+            Matcher::Sets { included, excluded } => {
+                included.is_match(relative_path.as_bytes())
+                    && !excluded.is_match(relative_path.as_bytes())
+            }
+        }
+    }
+
+    // Only a `.gitignore` pattern can exclude a folder as a whole.
+    pub fn is_excluded_folder(&self, relative_path: &str) -> bool {
+        match &self.0 {
+            Matcher::Gitignore(filter) => filter.matched(relative_path, true).is_ignore(),
+            Matcher::GitignoreFaithful(filter) => {
+                filter.matched(relative_path, true).is_whitelist()
+            }
+            Matcher::Sets { .. } => false,
+        }
+    }
+}
+
+const ROOT: &str = ".";
+
+const FOLDER_SEPARATOR: &str = "/";
+
+fn get_regex(pattern: &str, syntax: &model::PatternSyntax) -> model::Result<String> {
+    match syntax.language {
+        model::PatternLanguage::Glob => globset::GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .case_insensitive(syntax.case_insensitive)
+            .build()
+            .map(|glob| glob.regex().into())
+            .map_err(|error| model::Error::InvalidPattern {
+                pattern: pattern.into(),
+                message: error.kind().to_string(),
+            }),
+
+        _ => {
+            // Validated one by one so an error names the pattern.
+            regex::bytes::Regex::new(pattern).map_err(|error| model::Error::InvalidPattern {
+                pattern: pattern.into(),
+                message: error.to_string(),
+            })?;
+            Ok(pattern.into())
+        }
+    }
+}
+
+fn get_regex_set(
+    regexes: Vec<String>,
+    syntax: &model::PatternSyntax,
+) -> model::Result<regex::bytes::RegexSet> {
+    regex::bytes::RegexSetBuilder::new(&regexes)
+        .case_insensitive(syntax.case_insensitive)
+        .build()
+        .map_err(|error| model::Error::InvalidPattern {
+            pattern: regexes.join("\n"),
+            message: error.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_empty_patterns() {
+        let filter = Filter::new("", &model::PatternSyntax::default()).unwrap();
+
+        let actual = filter.is_match("a");
+
+        assert!(!actual);
+    }
+
+    #[test]
+    fn handles_unanchored_patterns() {
+        let filter = Filter::new("*.json", &model::PatternSyntax::default()).unwrap();
+
+        let actual = [
+            filter.is_match("a.json"),
+            filter.is_match("b/c.json"),
+            filter.is_match("d.md"),
+        ];
+
+        let expected = [true, true, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_anchored_patterns() {
+        let filter = Filter::new("/a/*", &model::PatternSyntax::default()).unwrap();
+
+        let actual = [
+            filter.is_match("a/b"),
+            filter.is_match("a/b/c"),
+            filter.is_match("c/a/b"),
+        ];
+
+        let expected = [true, false, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_negated_patterns() {
+        let filter = Filter::new(
+            "**
+!*.md
+!/a/b/",
+            &model::PatternSyntax::default(),
+        )
+        .unwrap();
+
+        let actual = [
+            filter.is_match("a/c"),
+            filter.is_match("a/d.md"),
+            filter.is_match("a/b/c"),
+            filter.is_match("a/b/c/d"),
+        ];
+
+        let expected = [true, false, false, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_invalid_pattern_it_errs() {
+        let actual = Filter::new("[", &model::PatternSyntax::default());
+
+        let actual = actual.is_err();
+        assert!(actual);
+    }
+
+    #[test]
+    fn handles_case_insensitive_gitignore_patterns() {
+        let filter = Filter::new(
+            "/a/*.JSON",
+            &model::PatternSyntax {
+                case_insensitive: true,
+                ..model::PatternSyntax::default()
+            },
+        )
+        .unwrap();
+
+        let actual = [filter.is_match("A/b.json"), filter.is_match("a/c.md")];
+
+        let expected = [true, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_glob_patterns() {
+        let filter = Filter::new(
+            "a/*.json
+**/*.md
+!**/c.md
+# Comment.",
+            &model::PatternSyntax {
+                language: model::PatternLanguage::Glob,
+                case_insensitive: false,
+            },
+        )
+        .unwrap();
+
+        let actual = [
+            filter.is_match("a/b.json"),
+            filter.is_match("a/b/c.json"),
+            filter.is_match("b.md"),
+            filter.is_match("a/b/d.md"),
+            filter.is_match("a/c.md"),
+            filter.is_match("a/B.JSON"),
+        ];
+
+        let expected = [true, false, true, true, false, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_case_insensitive_glob_patterns() {
+        let filter = Filter::new(
+            "*.json",
+            &model::PatternSyntax {
+                language: model::PatternLanguage::Glob,
+                case_insensitive: true,
+            },
+        )
+        .unwrap();
+
+        let actual = [filter.is_match("A.JSON"), filter.is_match("b/a.json")];
+
+        let expected = [true, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_regex_patterns() {
+        let filter = Filter::new(
+            r"^a/[^/]+\.json$
+\.md$
+!^b/",
+            &model::PatternSyntax {
+                language: model::PatternLanguage::Regex,
+                case_insensitive: true,
+            },
+        )
+        .unwrap();
+
+        let actual = [
+            filter.is_match("a/b.json"),
+            filter.is_match("a/b/c.json"),
+            filter.is_match("c/D.MD"),
+            filter.is_match("b/d.md"),
+        ];
+
+        let expected = [true, false, true, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_invalid_regex_it_errs() {
+        let actual = Filter::new(
+            "a\nb(",
+            &model::PatternSyntax {
+                language: model::PatternLanguage::Regex,
+                case_insensitive: false,
+            },
+        );
+
+        let actual = match actual {
+            Err(model::Error::InvalidPattern { pattern, .. }) => pattern,
+            _ => unreachable!(),
+        };
+        let expected = "b(";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_faithful_folder_patterns() {
+        let filter = Filter::new(
+            "/a/
+!/a/b/
+!*.md
+/a/b/c/d",
+            &model::PatternSyntax {
+                language: model::PatternLanguage::GitignoreFaithful,
+                case_insensitive: false,
+            },
+        )
+        .unwrap();
+
+        let actual = [
+            filter.is_match("a/c"),
+            filter.is_match("a/d/e"),
+            filter.is_match("a/e.md"),
+            filter.is_match("a/b/c"),
+            filter.is_match("a/b/c/d"),
+            filter.is_match("b/a/c"),
+            filter.is_match("a"),
+        ];
+
+        let expected = [true, true, false, false, false, false, false];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_faithful_file_patterns() {
+        let filter = Filter::new(
+            "*.json
+!/a/*.json",
+            &model::PatternSyntax {
+                language: model::PatternLanguage::GitignoreFaithful,
+                case_insensitive: true,
+            },
+        )
+        .unwrap();
+
+        let actual = [
+            filter.is_match("B.JSON"),
+            filter.is_match("b/c.json"),
+            filter.is_match("a/c.json"),
+            filter.is_match("a/b/c.json"),
+        ];
+
+        let expected = [true, true, false, true];
+        assert_eq!(actual, expected);
+    }
+}
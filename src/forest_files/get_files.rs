@@ -0,0 +1,60 @@
+use crate::model;
+
+// Files are in order of their paths, with the files of a folder in a row.
+pub fn main(forest: &model::Forest) -> Vec<&model::File> {
+    let mut files = vec![];
+    collect_files(forest, &mut files);
+    files
+}
+
+fn collect_files<'a>(forest: &'a model::Forest, files: &mut Vec<&'a model::File>) {
+    for tree in forest.values() {
+        match tree {
+            model::Tree::File(file) => files.push(file),
+            model::Tree::Folder(folder) => collect_files(&folder.forest, files),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let forest = [
+            (
+                "a".into(),
+                model::Tree::Folder(model::Folder {
+                    forest: [(
+                        "b".into(),
+                        model::Tree::File(Box::new(model::File {
+                            relative_path: "a/b".into(),
+                            ..model::stubs::file()
+                        })),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    ..model::stubs::folder()
+                }),
+            ),
+            (
+                "c".into(),
+                model::Tree::File(Box::new(model::File {
+                    relative_path: "c".into(),
+                    ..model::stubs::file()
+                })),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let actual = main(&forest)
+            .into_iter()
+            .map(|file| &file.relative_path[..])
+            .collect::<Vec<_>>();
+
+        let expected = ["a/b", "c"];
+        assert_eq!(actual, expected);
+    }
+}
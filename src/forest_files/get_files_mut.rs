@@ -0,0 +1,16 @@
+use crate::model;
+
+pub fn main(forest: &mut model::Forest) -> Vec<&mut model::File> {
+    let mut files = vec![];
+    collect_files(forest, &mut files);
+    files
+}
+
+fn collect_files<'a>(forest: &'a mut model::Forest, files: &mut Vec<&'a mut model::File>) {
+    for tree in forest.values_mut() {
+        match tree {
+            model::Tree::File(file) => files.push(file),
+            model::Tree::Folder(folder) => collect_files(&mut folder.forest, files),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod get_files;
+mod get_files_mut;
+
+pub use get_files::main as get_files;
+pub use get_files_mut::main as get_files_mut;
@@ -0,0 +1,214 @@
+use super::sanitize_name;
+use crate::model;
+use std::collections;
+
+// An alias copies the file of its target, including its index, so both refer
+// to the same entry in `ASSETS`.
+pub fn main(
+    aliases: &collections::BTreeMap<String, String>,
+    forest: &mut model::Forest,
+) -> model::Result<()> {
+    let aliases = aliases
+        .iter()
+        .map(|(alias, target)| {
+            let alias = get_components(alias);
+            let target = get_components(target);
+            match get_file(forest, &target) {
+                None => Err(model::Error::UnknownAliasTarget {
+                    alias: alias.join(FOLDER_SEPARATOR),
+                    target: target.join(FOLDER_SEPARATOR),
+                }),
+
+                Some(file) => Ok((alias, file.clone())),
+            }
+        })
+        .collect::<model::Result<Vec<_>>>()?;
+
+    for (alias, file) in aliases {
+        let relative_path = alias.join(FOLDER_SEPARATOR);
+        let file = model::File {
+            identifier: sanitize_name::main(
+                alias.last().map_or("", |name| name.as_str()),
+                sanitize_name::Convention::ScreamingSnakeCase,
+            ),
+            relative_path: relative_path.clone(),
+            alias_of: Some(file.relative_path.clone()),
+            ..file
+        };
+
+        if !add_file(forest, &alias, file) {
+            return Err(model::Error::AliasCollision(relative_path));
+        }
+    }
+
+    Ok(())
+}
+
+const FOLDER_SEPARATOR: &str = "/";
+
+fn get_components(path: &str) -> Vec<String> {
+    path.split(FOLDER_SEPARATOR)
+        .filter(|component| !component.is_empty())
+        .map(|component| component.into())
+        .collect()
+}
+
+fn get_file<'a>(forest: &'a model::Forest, path: &[String]) -> Option<&'a model::File> {
+    match path.split_first()? {
+        (name, []) => match forest.get(name)? {
+            model::Tree::File(file) if file.alias_of.is_none() => Some(file),
+            _ => None,
+        },
+
+        (name, rest) => match forest.get(name)? {
+            model::Tree::Folder(folder) => get_file(&folder.forest, rest),
+            model::Tree::File(_) => None,
+        },
+    }
+}
+
+fn add_file(forest: &mut model::Forest, path: &[String], file: model::File) -> bool {
+    match path.split_first() {
+        None => false,
+
+        Some((name, [])) => {
+            if forest.contains_key(name) {
+                false
+            } else {
+                forest.insert(name.clone(), model::Tree::File(Box::new(file)));
+                true
+            }
+        }
+
+        Some((name, rest)) => match forest.entry(name.clone()).or_insert_with(|| {
+            model::Tree::Folder(model::Folder {
+                identifier: sanitize_name::main(name, sanitize_name::Convention::SnakeCase),
+                forest: model::Forest::new(),
+            })
+        }) {
+            model::Tree::File(_) => false,
+            model::Tree::Folder(folder) => add_file(&mut folder.forest, rest, file),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_forest() -> model::Forest {
+        [(
+            "a".into(),
+            model::Tree::Folder(model::Folder {
+                identifier: quote::format_ident!("a"),
+                forest: [(
+                    "b.html".into(),
+                    model::Tree::File(Box::new(model::File {
+                        identifier: quote::format_ident!("B_HTML"),
+                        index: 3,
+                        relative_path: "a/b.html".into(),
+                        absolute_path: "/a/b.html".into(),
+                        ..model::stubs::file()
+                    })),
+                )]
+                .into_iter()
+                .collect(),
+            }),
+        )]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn handles() {
+        let mut forest = get_forest();
+
+        let actual = main(
+            &[
+                ("/a/c.html".into(), "a/b.html".into()),
+                ("d/e.html".into(), "/a/b.html".into()),
+            ]
+            .into(),
+            &mut forest,
+        );
+
+        actual.unwrap();
+        let alias = model::File {
+            identifier: quote::format_ident!("B_HTML"),
+            index: 3,
+            relative_path: "a/b.html".into(),
+            absolute_path: "/a/b.html".into(),
+            alias_of: Some("a/b.html".into()),
+            ..model::stubs::file()
+        };
+        let mut expected = get_forest();
+        if let Some(model::Tree::Folder(folder)) = expected.get_mut("a") {
+            folder.forest.insert(
+                "c.html".into(),
+                model::Tree::File(Box::new(model::File {
+                    identifier: quote::format_ident!("r#C_HTML"),
+                    relative_path: "a/c.html".into(),
+                    ..alias.clone()
+                })),
+            );
+        }
+        expected.insert(
+            "d".into(),
+            model::Tree::Folder(model::Folder {
+                identifier: quote::format_ident!("r#d"),
+                forest: [(
+                    "e.html".into(),
+                    model::Tree::File(Box::new(model::File {
+                        identifier: quote::format_ident!("r#E_HTML"),
+                        relative_path: "d/e.html".into(),
+                        ..alias
+                    })),
+                )]
+                .into_iter()
+                .collect(),
+            }),
+        );
+        assert_eq!(forest, expected);
+    }
+
+    #[test]
+    fn given_unknown_alias_target_it_errs() {
+        let actual = main(&[("c".into(), "/a".into())].into(), &mut get_forest());
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::UnknownAliasTarget {
+            alias: "c".into(),
+            target: "a".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_alias_collision_it_errs() {
+        for alias in ["a/b.html", "a", "a/b.html/c", ""] {
+            let actual = main(
+                &[(alias.into(), "a/b.html".into())].into(),
+                &mut get_forest(),
+            );
+
+            let actual = actual.unwrap_err();
+            let expected = model::Error::AliasCollision(get_components(alias).join("/"));
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn given_alias_of_alias_it_errs() {
+        let actual = main(
+            &[("c".into(), "a/b.html".into()), ("d".into(), "c".into())].into(),
+            &mut get_forest(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::UnknownAliasTarget {
+            alias: "d".into(),
+            target: "c".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::model;
+
+pub fn main(visitors: &[model::Visitor], forest: &model::Forest) -> model::Result<()> {
+    if uses_main_file(visitors) {
+        check_forest(forest)
+    } else {
+        Ok(())
+    }
+}
+
+fn uses_main_file(visitors: &[model::Visitor]) -> bool {
+    let is_main_file_populator = |populator: &model::Populator| {
+        matches!(
+            populator,
+            model::Populator::ContentsBytes
+                | model::Populator::ContentsStr
+                | model::Populator::FingerprintedPath
+                | model::Populator::GetBytes
+                | model::Populator::GetStr
+        )
+    };
+
+    visitors.iter().any(|visitor| match visitor {
+        model::Visitor::Array(model::Initializer::Default(populators)) => match populators {
+            model::TypeStructure::Unit => false,
+            model::TypeStructure::TypeAlias(populator) => is_main_file_populator(populator),
+            model::TypeStructure::NamedFields(fields) => fields
+                .iter()
+                .any(|(_, populator)| is_main_file_populator(populator)),
+            model::TypeStructure::TupleFields(populators) => {
+                populators.iter().any(is_main_file_populator)
+            }
+        },
+        _ => false,
+    })
+}
+
+// Only a group of variants without a file of its own has neither an absolute
+// path nor contents, unlike a virtual file.
+fn check_forest(forest: &model::Forest) -> model::Result<()> {
+    for tree in forest.values() {
+        match tree {
+            model::Tree::File(file) if file.absolute_path.is_empty() && file.contents.is_none() => {
+                return Err(model::Error::NoMainFile(file.relative_path.clone()));
+            }
+            model::Tree::File(_) => {}
+            model::Tree::Folder(folder) => check_forest(&folder.forest)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_forest() -> model::Forest {
+        [(
+            "a".into(),
+            model::Tree::Folder(model::Folder {
+                forest: [(
+                    "b".into(),
+                    model::Tree::File(Box::new(model::File {
+                        relative_path: "a/b".into(),
+                        absolute_path: String::new(),
+                        ..model::stubs::file()
+                    })),
+                )]
+                .into_iter()
+                .collect(),
+                ..model::stubs::folder()
+            }),
+        )]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn given_main_file_populators_it_errs_for_missing_main_file() {
+        let actual = main(
+            &[model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::NamedFields(vec![(
+                    quote::format_ident!("contents_str"),
+                    model::Populator::ContentsStr,
+                )]),
+            ))],
+            &get_forest(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::NoMainFile("a/b".into());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_no_main_file_populators_it_handles_missing_main_file() {
+        let actual = main(
+            &[model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::NamedFields(vec![(
+                    quote::format_ident!("relative_path"),
+                    model::Populator::RelativePath,
+                )]),
+            ))],
+            &get_forest(),
+        );
+
+        assert_eq!(actual, Ok(()));
+    }
+}
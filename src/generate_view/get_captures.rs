@@ -0,0 +1,234 @@
+use crate::model;
+use std::collections;
+
+pub fn main(
+    regex: &regex::Regex,
+    visitors: &[model::Visitor],
+    forest: &mut model::Forest,
+    shadowed_files: &mut [model::File],
+) -> model::Result<()> {
+    let fields = get_capture_fields(visitors);
+    set_captures(regex, &fields, forest)?;
+    for file in shadowed_files {
+        file.captures = get_captures(regex, &fields, file)?;
+    }
+    Ok(())
+}
+
+fn get_capture_fields(visitors: &[model::Visitor]) -> Vec<(&str, bool)> {
+    visitors
+        .iter()
+        .flat_map(|visitor| match visitor {
+            model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::NamedFields(fields),
+            )) => fields
+                .iter()
+                .filter_map(|(_, populator)| match populator {
+                    model::Populator::Capture { name, integer } => Some((&name[..], *integer)),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        })
+        .collect()
+}
+
+fn set_captures(
+    regex: &regex::Regex,
+    fields: &[(&str, bool)],
+    forest: &mut model::Forest,
+) -> model::Result<()> {
+    for tree in forest.values_mut() {
+        match tree {
+            model::Tree::File(file) => file.captures = get_captures(regex, fields, file)?,
+            model::Tree::Folder(folder) => set_captures(regex, fields, &mut folder.forest)?,
+        }
+    }
+    Ok(())
+}
+
+fn get_captures(
+    regex: &regex::Regex,
+    fields: &[(&str, bool)],
+    file: &model::File,
+) -> model::Result<collections::BTreeMap<String, String>> {
+    let mismatch = || model::Error::CaptureMismatch {
+        relative_path: file.relative_path.clone(),
+        regex: regex.as_str().into(),
+    };
+    let captures = regex.captures(&file.relative_path).ok_or_else(mismatch)?;
+
+    for &(field, integer) in fields {
+        let value = captures.name(field).ok_or_else(mismatch)?.as_str();
+        if integer && !is_integer(value) {
+            return Err(model::Error::InvalidCapture {
+                relative_path: file.relative_path.clone(),
+                field: field.into(),
+                value: value.into(),
+            });
+        }
+    }
+
+    Ok(regex
+        .capture_names()
+        .flatten()
+        .filter_map(|name| Some((name.into(), captures.name(name)?.as_str().into())))
+        .collect())
+}
+
+fn is_integer(value: &str) -> bool {
+    value.parse::<i128>().is_ok() || value.parse::<u128>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_forest(relative_paths: &[&str]) -> model::Forest {
+        relative_paths
+            .iter()
+            .map(|relative_path| {
+                (
+                    relative_path.to_string(),
+                    model::Tree::File(Box::new(model::File {
+                        relative_path: relative_path.to_string(),
+                        ..model::stubs::file()
+                    })),
+                )
+            })
+            .collect()
+    }
+
+    fn get_visitors(fields: &[(&str, bool)]) -> Vec<model::Visitor> {
+        vec![model::Visitor::Array(model::Initializer::Default(
+            model::TypeStructure::NamedFields(
+                fields
+                    .iter()
+                    .map(|&(name, integer)| {
+                        (
+                            quote::format_ident!("{name}"),
+                            model::Populator::Capture {
+                                name: name.into(),
+                                integer,
+                            },
+                        )
+                    })
+                    .collect(),
+            ),
+        ))]
+    }
+
+    #[test]
+    fn handles() {
+        let regex =
+            regex::Regex::new(r"^V(?<version>[0-9]+)__(?<name>\w+)(?<suffix>-x)?\.sql$").unwrap();
+        let mut forest = get_forest(&["V2__add_users.sql"]);
+
+        let actual = main(
+            &regex,
+            &get_visitors(&[("version", true), ("name", false)]),
+            &mut forest,
+            &mut [],
+        );
+
+        actual.unwrap();
+        let actual = forest;
+        let expected = [(
+            "V2__add_users.sql".into(),
+            model::Tree::File(Box::new(model::File {
+                relative_path: "V2__add_users.sql".into(),
+                captures: [
+                    ("name".into(), "add_users".into()),
+                    ("version".into(), "2".into()),
+                ]
+                .into(),
+                ..model::stubs::file()
+            })),
+        )]
+        .into();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_shadowed_files() {
+        let regex = regex::Regex::new(r"^(?<locale>[a-z]+)\.ftl$").unwrap();
+        let mut shadowed_files = [model::File {
+            relative_path: "de.ftl".into(),
+            layer: Some("a".into()),
+            ..model::stubs::file()
+        }];
+
+        let actual = main(
+            &regex,
+            &get_visitors(&[("locale", false)]),
+            &mut get_forest(&["de.ftl"]),
+            &mut shadowed_files,
+        );
+
+        actual.unwrap();
+        let actual = shadowed_files;
+        let expected = [model::File {
+            relative_path: "de.ftl".into(),
+            layer: Some("a".into()),
+            captures: [("locale".into(), "de".into())].into(),
+            ..model::stubs::file()
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_unmatched_path_it_errs() {
+        let regex = regex::Regex::new(r"^(?<locale>[a-z]+)\.ftl$").unwrap();
+        let mut forest = get_forest(&["de.ftl", "README.md"]);
+
+        let actual = main(
+            &regex,
+            &get_visitors(&[("locale", false)]),
+            &mut forest,
+            &mut [],
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::CaptureMismatch {
+            relative_path: "README.md".into(),
+            regex: r"^(?<locale>[a-z]+)\.ftl$".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_missing_capture_it_errs() {
+        let regex = regex::Regex::new(r"^a(?<b>b)?$").unwrap();
+        let mut forest = get_forest(&["a"]);
+
+        let actual = main(&regex, &get_visitors(&[("b", false)]), &mut forest, &mut []);
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::CaptureMismatch {
+            relative_path: "a".into(),
+            regex: r"^a(?<b>b)?$".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_non_integer_for_integer_field_it_errs() {
+        let regex = regex::Regex::new(r"^(?<version>.+)\.sql$").unwrap();
+        let mut forest = get_forest(&["1a.sql"]);
+
+        let actual = main(
+            &regex,
+            &get_visitors(&[("version", true)]),
+            &mut forest,
+            &mut [],
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::InvalidCapture {
+            relative_path: "1a.sql".into(),
+            field: "version".into(),
+            value: "1a".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::model;
 use std::collections;
+use syn::ext::IdentExt;
 
 pub fn main(
-    structure: model::TypeStructure<()>,
+    structure: model::TypeStructure<syn::Type>,
+    captures: Option<&regex::Regex>,
+    variants: &collections::BTreeMap<String, String>,
 ) -> model::Result<model::TypeStructure<model::Populator>> {
     match structure {
         model::TypeStructure::Unit => Ok(model::TypeStructure::Unit),
@@ -14,12 +17,23 @@ pub fn main(
             Ok(model::TypeStructure::NamedFields(
                 fields
                     .into_iter()
-                    .map(|(field, _)| match standard_field_populators.get(&field) {
-                        None => Err(model::Error::NonstandardField {
-                            field: field.clone(),
-                            standard_fields: standard_field_populators.keys().cloned().collect(),
-                        }),
-                        Some(populator) => Ok((field, populator.clone())),
+                    .map(|(field, type_)| {
+                        match get_populator(
+                            &standard_field_populators,
+                            captures,
+                            variants,
+                            &field,
+                            &type_,
+                        ) {
+                            None => Err(model::Error::NonstandardField {
+                                field: field.clone(),
+                                standard_fields: standard_field_populators
+                                    .keys()
+                                    .cloned()
+                                    .collect(),
+                            }),
+                            Some(populator) => Ok((field, populator)),
+                        }
                     })
                     .collect::<model::Result<_>>()?,
             ))
@@ -45,6 +59,10 @@ fn get_standard_field_populators() -> collections::BTreeMap<syn::Ident, model::P
             quote::format_ident!("contents_str"),
             model::Populator::ContentsStr,
         ),
+        (
+            quote::format_ident!("fingerprinted_path"),
+            model::Populator::FingerprintedPath,
+        ),
         (
             quote::format_ident!("get_bytes"),
             model::Populator::GetBytes,
@@ -59,13 +77,87 @@ fn get_standard_field_populators() -> collections::BTreeMap<syn::Ident, model::P
     .collect()
 }
 
+fn get_populator(
+    standard_field_populators: &collections::BTreeMap<syn::Ident, model::Populator>,
+    captures: Option<&regex::Regex>,
+    variants: &collections::BTreeMap<String, String>,
+    field: &syn::Ident,
+    type_: &syn::Type,
+) -> Option<model::Populator> {
+    if let Some(populator) = standard_field_populators.get(field) {
+        return Some(populator.clone());
+    }
+
+    let name = field.unraw().to_string();
+    if captures.is_some_and(|captures| {
+        captures
+            .capture_names()
+            .flatten()
+            .any(|capture| capture == name)
+    }) {
+        return Some(model::Populator::Capture {
+            name,
+            integer: is_integer(type_),
+        });
+    }
+
+    variants
+        .contains_key(&name)
+        .then(|| model::Populator::Variant {
+            str_: is_optional_str(type_),
+            name,
+        })
+}
+
+fn is_integer(type_: &syn::Type) -> bool {
+    match type_ {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => INTEGER_TYPES
+            .iter()
+            .any(|integer_type| path.is_ident(integer_type)),
+        _ => false,
+    }
+}
+
+// Variants are bytes unless the field is typed like `Option<&'static str>`.
+fn is_optional_str(type_: &syn::Type) -> bool {
+    match type_ {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .filter(|segment| segment.ident == "Option")
+            .is_some_and(|segment| match &segment.arguments {
+                syn::PathArguments::AngleBracketed(arguments) => {
+                    matches!(
+                        arguments.args.first(),
+                        Some(syn::GenericArgument::Type(syn::Type::Reference(reference)))
+                            if matches!(
+                                reference.elem.as_ref(),
+                                syn::Type::Path(syn::TypePath { qself: None, path })
+                                    if path.is_ident("str")
+                            )
+                    )
+                }
+                _ => false,
+            }),
+        _ => false,
+    }
+}
+
+const INTEGER_TYPES: [&str; 12] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn handles_unit() {
-        let actual = main(model::TypeStructure::Unit);
+        let actual = main(
+            model::TypeStructure::Unit,
+            None,
+            &collections::BTreeMap::new(),
+        );
 
         let actual = actual.unwrap();
         let expected = model::TypeStructure::Unit;
@@ -74,7 +166,11 @@ mod tests {
 
     #[test]
     fn handles_type_alias() {
-        let actual = main(model::TypeStructure::TypeAlias(()));
+        let actual = main(
+            model::TypeStructure::TypeAlias(syn::parse_quote! { &'static str }),
+            None,
+            &collections::BTreeMap::new(),
+        );
 
         let actual = actual.unwrap_err();
         let expected = model::Error::NoInitializer;
@@ -87,10 +183,20 @@ mod tests {
 
         #[test]
         fn given_standard_fields_only_it_handles() {
-            let actual = main(model::TypeStructure::NamedFields(vec![
-                (quote::format_ident!("relative_path"), ()),
-                (quote::format_ident!("contents_str"), ()),
-            ]));
+            let actual = main(
+                model::TypeStructure::NamedFields(vec![
+                    (
+                        quote::format_ident!("relative_path"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                    (
+                        quote::format_ident!("contents_str"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                ]),
+                None,
+                &collections::BTreeMap::new(),
+            );
 
             let actual = actual.unwrap();
             let expected = model::TypeStructure::NamedFields(vec![
@@ -106,12 +212,95 @@ mod tests {
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn given_capture_fields_it_handles() {
+            let regex = regex::Regex::new(r"^V(?<version>[0-9]+)__(?<type>\w+)\.sql$").unwrap();
+
+            let actual = main(
+                model::TypeStructure::NamedFields(vec![
+                    (quote::format_ident!("version"), syn::parse_quote! { u32 }),
+                    (
+                        syn::parse_quote! { r#type },
+                        syn::parse_quote! { &'static str },
+                    ),
+                ]),
+                Some(&regex),
+                &collections::BTreeMap::new(),
+            );
+
+            let actual = actual.unwrap();
+            let expected = model::TypeStructure::NamedFields(vec![
+                (
+                    quote::format_ident!("version"),
+                    model::Populator::Capture {
+                        name: "version".into(),
+                        integer: true,
+                    },
+                ),
+                (
+                    syn::parse_quote! { r#type },
+                    model::Populator::Capture {
+                        name: "type".into(),
+                        integer: false,
+                    },
+                ),
+            ]);
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_variant_fields_it_handles() {
+            let actual = main(
+                model::TypeStructure::NamedFields(vec![
+                    (
+                        quote::format_ident!("map"),
+                        syn::parse_quote! { Option<&'static str> },
+                    ),
+                    (
+                        quote::format_ident!("gzip"),
+                        syn::parse_quote! { Option<&'static [u8]> },
+                    ),
+                ]),
+                None,
+                &[("gzip".into(), ".gz".into()), ("map".into(), ".map".into())].into(),
+            );
+
+            let actual = actual.unwrap();
+            let expected = model::TypeStructure::NamedFields(vec![
+                (
+                    quote::format_ident!("map"),
+                    model::Populator::Variant {
+                        name: "map".into(),
+                        str_: true,
+                    },
+                ),
+                (
+                    quote::format_ident!("gzip"),
+                    model::Populator::Variant {
+                        name: "gzip".into(),
+                        str_: false,
+                    },
+                ),
+            ]);
+            assert_eq!(actual, expected);
+        }
+
         #[test]
         fn given_nonstandard_field_it_errs() {
-            let actual = main(model::TypeStructure::NamedFields(vec![
-                (quote::format_ident!("relative_path"), ()),
-                (quote::format_ident!("abc"), ()),
-            ]));
+            let actual = main(
+                model::TypeStructure::NamedFields(vec![
+                    (
+                        quote::format_ident!("relative_path"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                    (
+                        quote::format_ident!("abc"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                ]),
+                None,
+                &collections::BTreeMap::new(),
+            );
 
             let actual = actual.unwrap_err();
             let expected = model::Error::NonstandardField {
@@ -119,6 +308,7 @@ mod tests {
                 standard_fields: vec![
                     quote::format_ident!("contents_bytes"),
                     quote::format_ident!("contents_str"),
+                    quote::format_ident!("fingerprinted_path"),
                     quote::format_ident!("get_bytes"),
                     quote::format_ident!("get_str"),
                     quote::format_ident!("relative_path"),
@@ -129,13 +319,36 @@ mod tests {
 
         #[test]
         fn handles_each_standard_field() {
-            let actual = main(model::TypeStructure::NamedFields(vec![
-                (quote::format_ident!("contents_bytes"), ()),
-                (quote::format_ident!("contents_str"), ()),
-                (quote::format_ident!("get_bytes"), ()),
-                (quote::format_ident!("get_str"), ()),
-                (quote::format_ident!("relative_path"), ()),
-            ]));
+            let actual = main(
+                model::TypeStructure::NamedFields(vec![
+                    (
+                        quote::format_ident!("contents_bytes"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                    (
+                        quote::format_ident!("contents_str"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                    (
+                        quote::format_ident!("fingerprinted_path"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                    (
+                        quote::format_ident!("get_bytes"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                    (
+                        quote::format_ident!("get_str"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                    (
+                        quote::format_ident!("relative_path"),
+                        syn::parse_quote! { &'static str },
+                    ),
+                ]),
+                None,
+                &collections::BTreeMap::new(),
+            );
 
             let actual = actual.unwrap();
             let expected = model::TypeStructure::NamedFields(vec![
@@ -147,6 +360,10 @@ mod tests {
                     quote::format_ident!("contents_str"),
                     model::Populator::ContentsStr,
                 ),
+                (
+                    quote::format_ident!("fingerprinted_path"),
+                    model::Populator::FingerprintedPath,
+                ),
                 (
                     quote::format_ident!("get_bytes"),
                     model::Populator::GetBytes,
@@ -167,7 +384,11 @@ mod tests {
 
         #[test]
         fn given_no_fields_it_handles() {
-            let actual = main(model::TypeStructure::TupleFields(vec![]));
+            let actual = main(
+                model::TypeStructure::TupleFields(vec![]),
+                None,
+                &collections::BTreeMap::new(),
+            );
 
             let actual = actual.unwrap();
             let expected = model::TypeStructure::TupleFields(vec![]);
@@ -176,7 +397,11 @@ mod tests {
 
         #[test]
         fn given_fields_it_errs() {
-            let actual = main(model::TypeStructure::TupleFields(vec![()]));
+            let actual = main(
+                model::TypeStructure::TupleFields(vec![syn::parse_quote! { &'static str }]),
+                None,
+                &collections::BTreeMap::new(),
+            );
 
             let actual = actual.unwrap_err();
             let expected = model::Error::NoInitializer;
@@ -1,21 +1,110 @@
 use super::sanitize_name;
+use crate::file_variants;
+use crate::layers;
 use crate::model;
+use std::collections;
 use std::iter;
+use std::mem;
 
-pub fn main(paths: Vec<model::Path>) -> model::Result<model::Forest> {
+pub fn main(
+    paths: Vec<model::Path>,
+    layers: &[String],
+    variants: &collections::BTreeMap<String, String>,
+) -> model::Result<(model::Forest, Vec<model::File>)> {
     let mut forest = model::Forest::new();
+    let mut layering = Layering {
+        layers,
+        shadowed_files: vec![],
+    };
 
-    for path in paths.into_iter() {
-        add_path(&mut forest, path)?;
+    for (path, file_variants) in group_variants(paths, variants) {
+        add_path(&mut forest, &mut layering, path, file_variants)?;
     }
 
     let mut index = 0;
     overwrite_indices_in_order(&mut forest, &mut index);
 
-    Ok(forest)
+    let mut shadowed_files = layering.shadowed_files;
+    shadowed_files.sort_by_key(|file| {
+        (
+            file.relative_path.clone(),
+            layers::get_rank(layers, file.layer.as_deref()),
+        )
+    });
+    for file in &mut shadowed_files {
+        file.index = index;
+        index += 1;
+    }
+
+    Ok((forest, shadowed_files))
+}
+
+type Group = (
+    model::Path,
+    collections::BTreeMap<String, model::FileVariant>,
+);
+
+// Files whose name ends with a variant suffix join the file of their stem in
+// the same layer, which may be missing, as with `case.input`/`case.expected`.
+fn group_variants(
+    paths: Vec<model::Path>,
+    variants: &collections::BTreeMap<String, String>,
+) -> Vec<Group> {
+    let mut groups = Vec::<Group>::new();
+    let mut group_indices = collections::BTreeMap::new();
+
+    let (variant_paths, main_paths): (Vec<_>, Vec<_>) = paths
+        .into_iter()
+        .map(|path| (file_variants::get_variant(variants, &path.relative), path))
+        .partition(|(variant, _)| variant.is_some());
+
+    for (_, path) in main_paths {
+        group_indices.insert((path.relative.clone(), path.layer.clone()), groups.len());
+        groups.push((path, collections::BTreeMap::new()));
+    }
+
+    for (variant, path) in variant_paths {
+        if let Some((name, stem)) = variant {
+            let key = (stem, path.layer.clone());
+            let index = *group_indices.entry(key.clone()).or_insert_with(|| {
+                groups.push((
+                    model::Path {
+                        relative: key.0,
+                        absolute: String::new(),
+                        contents: None,
+                        size: None,
+                        hash: None,
+                        fingerprinted_path: None,
+                        layer: key.1,
+                    },
+                    collections::BTreeMap::new(),
+                ));
+                groups.len() - 1
+            });
+            groups[index].1.insert(
+                name,
+                model::FileVariant {
+                    absolute_path: path.absolute,
+                    contents: path.contents,
+                },
+            );
+        }
+    }
+
+    groups
+}
+
+struct Layering<'a> {
+    layers: &'a [String],
+    shadowed_files: Vec<model::File>,
 }
 
-fn add_path(forest: &mut model::Forest, path: model::Path) -> model::Result<()> {
+fn add_path(
+    forest: &mut model::Forest,
+    layering: &mut Layering,
+    path: model::Path,
+    variants: collections::BTreeMap<String, model::FileVariant>,
+) -> model::Result<()> {
     match path.relative.last() {
         None => Err(model::Error::UnexpectedEmptyRelativePath {
             absolute_path: path.absolute.into(),
@@ -30,12 +119,22 @@ fn add_path(forest: &mut model::Forest, path: model::Path) -> model::Result<()>
                 index: 0,
                 relative_path: path.relative.join(NORMALIZED_FOLDER_SEPARATOR),
                 absolute_path: path.absolute,
+                contents: path.contents,
+                size: path.size,
+                hash: path.hash,
+                fingerprinted_path: path.fingerprinted_path,
+                shared_contents: None,
+                layer: path.layer,
+                captures: collections::BTreeMap::new(),
+                variants,
+                override_: None,
+                alias_of: None,
             };
 
             let mut reverse_path = path.relative;
             reverse_path.reverse();
 
-            add_file(forest, reverse_path, file)
+            add_file(forest, layering, reverse_path, file)
         }
     }
 }
@@ -44,6 +143,7 @@ const NORMALIZED_FOLDER_SEPARATOR: &str = "/";
 
 fn add_file(
     parent: &mut model::Forest,
+    layering: &mut Layering,
     mut reverse_path: Vec<String>,
     file: model::File,
 ) -> model::Result<()> {
@@ -59,12 +159,32 @@ fn add_file(
                 Ok(())
             }
 
+            Some(model::Tree::File(existing_file)) if reverse_path.is_empty() => {
+                // A file in a later layer shadows one in an earlier layer.
+                let rank = layers::get_rank(layering.layers, file.layer.as_deref());
+                let existing_rank =
+                    layers::get_rank(layering.layers, existing_file.layer.as_deref());
+
+                if rank > existing_rank {
+                    let shadowed_file = mem::replace(existing_file.as_mut(), file);
+                    layering.shadowed_files.push(shadowed_file);
+                    Ok(())
+                } else if rank < existing_rank {
+                    layering.shadowed_files.push(file);
+                    Ok(())
+                } else {
+                    Err(model::Error::UnexpectedPathCollision(
+                        file.relative_path.into(),
+                    ))
+                }
+            }
+
             Some(model::Tree::File(_)) => Err(model::Error::UnexpectedPathCollision(
                 file.relative_path.into(),
             )),
 
             Some(model::Tree::Folder(model::Folder { forest, .. })) => {
-                add_file(forest, reverse_path, file)
+                add_file(forest, layering, reverse_path, file)
             }
         },
     }
@@ -80,9 +200,9 @@ fn get_singleton_tree(reverse_path: Vec<String>, file: model::File, root: &str)
             .collect::<Vec<_>>(),
     );
 
-    let mut tree = model::Tree::File(file);
+    let mut tree = model::Tree::File(Box::new(file));
 
-    for (child, parent) in reverse_path.into_iter().zip(parents.into_iter()) {
+    for (child, parent) in reverse_path.into_iter().zip(parents) {
         let forest = [(child, tree)].into_iter().collect();
         tree = model::Tree::Folder(model::Folder {
             identifier: parent,
@@ -121,45 +241,79 @@ mod tests {
 
     #[test]
     fn handles_empty_set() {
-        let actual = main(vec![]);
+        let actual = main(vec![], &[], &collections::BTreeMap::new());
 
         let actual = actual.unwrap();
-        let expected = model::Forest::new();
+        let expected = (model::Forest::new(), vec![]);
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn handles_files() {
-        let actual = main(vec![
-            model::Path {
-                relative: vec!["B".into()],
-                absolute: "/a/B".into(),
-            },
-            model::Path {
-                relative: vec!["c".into()],
-                absolute: "/a/c".into(),
-            },
-        ]);
+        let actual = main(
+            vec![
+                model::Path {
+                    relative: vec!["B".into()],
+                    absolute: "/a/B".into(),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    layer: None,
+                },
+                model::Path {
+                    relative: vec!["c".into()],
+                    absolute: "/a/c".into(),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    layer: None,
+                },
+            ],
+            &[],
+            &collections::BTreeMap::new(),
+        );
 
-        let actual = actual.unwrap();
+        let actual = actual.unwrap().0;
         let expected = [
             (
                 "B".into(),
-                model::Tree::File(model::File {
+                model::Tree::File(Box::new(model::File {
                     identifier: quote::format_ident!("r#B"),
                     index: 0,
                     relative_path: "B".into(),
                     absolute_path: "/a/B".into(),
-                }),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    shared_contents: None,
+                    layer: None,
+                    captures: collections::BTreeMap::new(),
+                    variants: collections::BTreeMap::new(),
+                    override_: None,
+                    alias_of: None,
+                })),
             ),
             (
                 "c".into(),
-                model::Tree::File(model::File {
+                model::Tree::File(Box::new(model::File {
                     identifier: quote::format_ident!("r#C"),
                     index: 1,
                     relative_path: "c".into(),
                     absolute_path: "/a/c".into(),
-                }),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    shared_contents: None,
+                    layer: None,
+                    captures: collections::BTreeMap::new(),
+                    variants: collections::BTreeMap::new(),
+                    override_: None,
+                    alias_of: None,
+                })),
             ),
         ]
         .into_iter()
@@ -169,31 +323,60 @@ mod tests {
 
     #[test]
     fn handles_folders() {
-        let actual = main(vec![
-            model::Path {
-                relative: vec!["a".into()],
-                absolute: "/a".into(),
-            },
-            model::Path {
-                relative: vec!["b".into(), "a".into(), "b".into()],
-                absolute: "/b/a/b".into(),
-            },
-            model::Path {
-                relative: vec!["b".into(), "c".into()],
-                absolute: "/b/c".into(),
-            },
-        ]);
+        let actual = main(
+            vec![
+                model::Path {
+                    relative: vec!["a".into()],
+                    absolute: "/a".into(),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    layer: None,
+                },
+                model::Path {
+                    relative: vec!["b".into(), "a".into(), "b".into()],
+                    absolute: "/b/a/b".into(),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    layer: None,
+                },
+                model::Path {
+                    relative: vec!["b".into(), "c".into()],
+                    absolute: "/b/c".into(),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    layer: None,
+                },
+            ],
+            &[],
+            &collections::BTreeMap::new(),
+        );
 
-        let actual = actual.unwrap();
+        let actual = actual.unwrap().0;
         let expected = [
             (
                 "a".into(),
-                model::Tree::File(model::File {
+                model::Tree::File(Box::new(model::File {
                     identifier: quote::format_ident!("r#A"),
                     index: 0,
                     relative_path: "a".into(),
                     absolute_path: "/a".into(),
-                }),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    shared_contents: None,
+                    layer: None,
+                    captures: collections::BTreeMap::new(),
+                    variants: collections::BTreeMap::new(),
+                    override_: None,
+                    alias_of: None,
+                })),
             ),
             (
                 "b".into(),
@@ -206,12 +389,22 @@ mod tests {
                                 identifier: quote::format_ident!("r#a"),
                                 forest: [(
                                     "b".into(),
-                                    model::Tree::File(model::File {
+                                    model::Tree::File(Box::new(model::File {
                                         identifier: quote::format_ident!("r#B"),
                                         index: 1,
                                         relative_path: "b/a/b".into(),
                                         absolute_path: "/b/a/b".into(),
-                                    }),
+                                        contents: None,
+                                        size: None,
+                                        hash: None,
+                                        fingerprinted_path: None,
+                                        shared_contents: None,
+                                        layer: None,
+                                        captures: collections::BTreeMap::new(),
+                                        variants: collections::BTreeMap::new(),
+                                        override_: None,
+                                        alias_of: None,
+                                    })),
                                 )]
                                 .into_iter()
                                 .collect(),
@@ -219,12 +412,22 @@ mod tests {
                         ),
                         (
                             "c".into(),
-                            model::Tree::File(model::File {
+                            model::Tree::File(Box::new(model::File {
                                 identifier: quote::format_ident!("r#C"),
                                 index: 2,
                                 relative_path: "b/c".into(),
                                 absolute_path: "/b/c".into(),
-                            }),
+                                contents: None,
+                                size: None,
+                                hash: None,
+                                fingerprinted_path: None,
+                                shared_contents: None,
+                                layer: None,
+                                captures: collections::BTreeMap::new(),
+                                variants: collections::BTreeMap::new(),
+                                override_: None,
+                                alias_of: None,
+                            })),
                         ),
                     ]
                     .into_iter()
@@ -239,10 +442,19 @@ mod tests {
 
     #[test]
     fn given_empty_relative_path_it_errs() {
-        let actual = main(vec![model::Path {
-            relative: vec![],
-            absolute: "/a/b".into(),
-        }]);
+        let actual = main(
+            vec![model::Path {
+                relative: vec![],
+                absolute: "/a/b".into(),
+                contents: None,
+                size: None,
+                hash: None,
+                fingerprinted_path: None,
+                layer: None,
+            }],
+            &[],
+            &collections::BTreeMap::new(),
+        );
 
         let actual = actual.unwrap_err();
         let expected = model::Error::UnexpectedEmptyRelativePath {
@@ -253,19 +465,166 @@ mod tests {
 
     #[test]
     fn given_path_collision_it_errs() {
-        let actual = main(vec![
-            model::Path {
-                relative: vec!["a".into(), "b".into()],
-                ..model::stubs::path()
-            },
-            model::Path {
-                relative: vec!["a".into(), "b".into()],
-                ..model::stubs::path()
-            },
-        ]);
+        let actual = main(
+            vec![
+                model::Path {
+                    relative: vec!["a".into(), "b".into()],
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["a".into(), "b".into()],
+                    ..model::stubs::path()
+                },
+            ],
+            &[],
+            &collections::BTreeMap::new(),
+        );
 
         let actual = actual.unwrap_err();
         let expected = model::Error::UnexpectedPathCollision("a/b".into());
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn given_layers_it_shadows_files_in_earlier_layers() {
+        let actual = main(
+            vec![
+                model::Path {
+                    relative: vec!["a".into()],
+                    absolute: "/c/a".into(),
+                    layer: Some("c".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["a".into()],
+                    absolute: "/a".into(),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["a".into()],
+                    absolute: "/b/a".into(),
+                    layer: Some("b".into()),
+                    ..model::stubs::path()
+                },
+            ],
+            &["b".into(), "c".into()],
+            &collections::BTreeMap::new(),
+        );
+
+        let actual = actual.unwrap();
+        let actual = (
+            actual
+                .0
+                .values()
+                .map(|tree| match tree {
+                    model::Tree::File(file) => (file.absolute_path.clone(), file.index),
+                    model::Tree::Folder(_) => unreachable!(),
+                })
+                .collect::<Vec<_>>(),
+            actual
+                .1
+                .into_iter()
+                .map(|file| (file.absolute_path, file.index))
+                .collect::<Vec<_>>(),
+        );
+        let expected = (
+            vec![("/c/a".into(), 0)],
+            vec![("/a".into(), 1), ("/b/a".into(), 2)],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_variants() {
+        let actual = main(
+            vec![
+                model::Path {
+                    relative: vec!["app.js.map".into()],
+                    absolute: "/app.js.map".into(),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["app.js".into()],
+                    absolute: "/app.js".into(),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["a".into(), "case.input".into()],
+                    absolute: "/a/case.input".into(),
+                    contents: Some(vec![1]),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["a".into(), "case.expected".into()],
+                    absolute: "/a/case.expected".into(),
+                    ..model::stubs::path()
+                },
+            ],
+            &[],
+            &[
+                ("expected".into(), ".expected".into()),
+                ("input".into(), ".input".into()),
+                ("map".into(), ".map".into()),
+            ]
+            .into(),
+        );
+
+        let actual = actual.unwrap();
+        let actual = get_files(&actual.0)
+            .into_iter()
+            .map(|file| {
+                (
+                    file.relative_path.clone(),
+                    file.absolute_path.clone(),
+                    file.variants.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (
+                "a/case".into(),
+                String::new(),
+                [
+                    (
+                        "expected".into(),
+                        model::FileVariant {
+                            absolute_path: "/a/case.expected".into(),
+                            contents: None,
+                        },
+                    ),
+                    (
+                        "input".into(),
+                        model::FileVariant {
+                            absolute_path: "/a/case.input".into(),
+                            contents: Some(vec![1]),
+                        },
+                    ),
+                ]
+                .into(),
+            ),
+            (
+                "app.js".into(),
+                "/app.js".into(),
+                [(
+                    "map".into(),
+                    model::FileVariant {
+                        absolute_path: "/app.js.map".into(),
+                        contents: None,
+                    },
+                )]
+                .into(),
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    fn get_files(forest: &model::Forest) -> Vec<&model::File> {
+        forest
+            .values()
+            .flat_map(|tree| match tree {
+                model::Tree::File(file) => vec![file.as_ref()],
+                model::Tree::Folder(folder) => get_files(&folder.forest),
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,145 @@
+use super::sanitize_name;
+use crate::filter;
+use crate::forest_files;
+use crate::model;
+use std::collections;
+use syn::ext::IdentExt;
+
+pub fn main(
+    groups: &collections::BTreeMap<String, String>,
+    pattern_syntax: &model::PatternSyntax,
+    forest: &model::Forest,
+) -> model::Result<Vec<model::Group>> {
+    let files = forest_files::get_files(forest);
+    let mut names = collections::HashMap::new();
+
+    groups
+        .iter()
+        .map(|(name, patterns)| {
+            let filter = filter::Filter::new(patterns, pattern_syntax)?;
+            let mut indices = files
+                .iter()
+                .filter(|file| filter.is_match(&file.relative_path))
+                .map(|file| file.index)
+                .collect::<Vec<_>>();
+            indices.sort_unstable();
+
+            let identifier =
+                sanitize_name::main(name, sanitize_name::Convention::ScreamingSnakeCase);
+            if let Some(other_name) = names.insert(identifier.to_string(), name) {
+                return Err(model::Error::GroupCollision {
+                    names: vec![other_name.clone(), name.clone()],
+                    identifier: identifier.unraw().to_string(),
+                });
+            }
+
+            Ok(model::Group {
+                identifier,
+                indices,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let forest = [
+            (
+                "a.png".into(),
+                model::Tree::File(Box::new(model::File {
+                    index: 2,
+                    relative_path: "a.png".into(),
+                    ..model::stubs::file()
+                })),
+            ),
+            (
+                "b".into(),
+                model::Tree::Folder(model::Folder {
+                    forest: [
+                        (
+                            "c.png".into(),
+                            model::Tree::File(Box::new(model::File {
+                                index: 0,
+                                relative_path: "b/c.png".into(),
+                                ..model::stubs::file()
+                            })),
+                        ),
+                        (
+                            "d.js".into(),
+                            model::Tree::File(Box::new(model::File {
+                                index: 1,
+                                relative_path: "b/d.js".into(),
+                                ..model::stubs::file()
+                            })),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    ..model::stubs::folder()
+                }),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let actual = main(
+            &[
+                ("images".into(), "*.png".into()),
+                ("in-b".into(), "/b/*".into()),
+                ("fonts".into(), "*.woff2".into()),
+            ]
+            .into(),
+            &model::PatternSyntax::default(),
+            &forest,
+        );
+
+        let actual = actual.unwrap();
+        let expected = vec![
+            model::Group {
+                identifier: quote::format_ident!("r#FONTS"),
+                indices: vec![],
+            },
+            model::Group {
+                identifier: quote::format_ident!("r#IMAGES"),
+                indices: vec![0, 2],
+            },
+            model::Group {
+                identifier: quote::format_ident!("r#IN_B"),
+                indices: vec![0, 1],
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_group_collision_it_errs() {
+        let actual = main(
+            &[("a-b".into(), "*".into()), ("a_b".into(), "*".into())].into(),
+            &model::PatternSyntax::default(),
+            &model::Forest::new(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::GroupCollision {
+            names: vec!["a-b".into(), "a_b".into()],
+            identifier: "A_B".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_invalid_pattern_it_errs() {
+        let actual = main(
+            &[("images".into(), "[".into())].into(),
+            &model::PatternSyntax::default(),
+            &model::Forest::new(),
+        );
+
+        let actual = actual.is_err();
+        assert!(actual);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::filter;
+use crate::model;
+use syn::ext::IdentExt;
+
+pub fn main(
+    overrides: &[model::Override],
+    pattern_syntax: &model::PatternSyntax,
+    visitors: &[model::Visitor],
+    forest: &mut model::Forest,
+    shadowed_files: &mut [model::File],
+) -> model::Result<()> {
+    let fields = get_fields(visitors);
+    for override_ in overrides {
+        if let Some(field) = override_
+            .fields
+            .keys()
+            .find(|field| !fields.contains(field))
+        {
+            return Err(model::Error::UnknownOverrideField {
+                paths: override_.paths.clone(),
+                field: field.clone(),
+            });
+        }
+    }
+
+    let filters = overrides
+        .iter()
+        .map(|override_| filter::Filter::new(&override_.paths, pattern_syntax))
+        .collect::<model::Result<Vec<_>>>()?;
+    set_overrides(overrides, &filters, forest);
+    for file in shadowed_files {
+        set_override(overrides, &filters, file);
+    }
+    Ok(())
+}
+
+// Fields are named or, for tuple structs, indexed like `0`.
+fn get_fields(visitors: &[model::Visitor]) -> Vec<String> {
+    visitors
+        .iter()
+        .flat_map(|visitor| match visitor {
+            model::Visitor::Array(model::Initializer::Default(populators)) => match populators {
+                model::TypeStructure::NamedFields(fields) => fields
+                    .iter()
+                    .map(|(field, _)| field.unraw().to_string())
+                    .collect(),
+                model::TypeStructure::TupleFields(populators) => (0..populators.len())
+                    .map(|index| index.to_string())
+                    .collect(),
+                model::TypeStructure::Unit | model::TypeStructure::TypeAlias(_) => vec![],
+            },
+            _ => vec![],
+        })
+        .collect()
+}
+
+// Like with `paths`, the last matching override wins.
+fn set_overrides(
+    overrides: &[model::Override],
+    filters: &[filter::Filter],
+    forest: &mut model::Forest,
+) {
+    for tree in forest.values_mut() {
+        match tree {
+            model::Tree::File(file) => set_override(overrides, filters, file),
+            model::Tree::Folder(folder) => set_overrides(overrides, filters, &mut folder.forest),
+        }
+    }
+}
+
+fn set_override(overrides: &[model::Override], filters: &[filter::Filter], file: &mut model::File) {
+    file.override_ = overrides
+        .iter()
+        .zip(filters)
+        .rfind(|(_, filter)| filter.is_match(&file.relative_path))
+        .map(|(override_, _)| override_.clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections;
+
+    fn get_override(paths: &str, initializer: &str) -> model::Override {
+        model::Override {
+            paths: paths.into(),
+            initializer: Some(syn::parse_str(initializer).unwrap()),
+            transform: None,
+            fields: collections::BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn handles_last_matching_override() {
+        let overrides = [
+            get_override("*.svg", "a"),
+            get_override("/b/*", "b"),
+            get_override("*.png", "c"),
+        ];
+        let mut forest = [
+            (
+                "a.svg".into(),
+                model::Tree::File(Box::new(model::File {
+                    relative_path: "a.svg".into(),
+                    ..model::stubs::file()
+                })),
+            ),
+            (
+                "b".into(),
+                model::Tree::Folder(model::Folder {
+                    forest: [(
+                        "c.svg".into(),
+                        model::Tree::File(Box::new(model::File {
+                            relative_path: "b/c.svg".into(),
+                            ..model::stubs::file()
+                        })),
+                    )]
+                    .into_iter()
+                    .collect(),
+                    ..model::stubs::folder()
+                }),
+            ),
+            (
+                "d.txt".into(),
+                model::Tree::File(Box::new(model::File {
+                    relative_path: "d.txt".into(),
+                    ..model::stubs::file()
+                })),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let actual = main(
+            &overrides,
+            &model::PatternSyntax::default(),
+            &[],
+            &mut forest,
+            &mut [],
+        );
+
+        actual.unwrap();
+        let actual = get_overrides(&forest);
+        let expected = vec![Some(overrides[0].clone()), Some(overrides[1].clone()), None];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_shadowed_files() {
+        let overrides = [get_override("*.svg", "a")];
+        let mut shadowed_files = [model::File {
+            relative_path: "b.svg".into(),
+            layer: Some("c".into()),
+            ..model::stubs::file()
+        }];
+
+        let actual = main(
+            &overrides,
+            &model::PatternSyntax::default(),
+            &[],
+            &mut model::Forest::new(),
+            &mut shadowed_files,
+        );
+
+        actual.unwrap();
+        let actual = shadowed_files.map(|file| file.override_);
+        let expected = [Some(overrides[0].clone())];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_unknown_field_it_errs() {
+        let actual = main(
+            &[model::Override {
+                fields: [("b".into(), syn::parse_quote! { "" })].into(),
+                ..get_override("*", "a")
+            }],
+            &model::PatternSyntax::default(),
+            &[model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::NamedFields(vec![(
+                    syn::parse_quote! { r#a },
+                    model::Populator::RelativePath,
+                )]),
+            ))],
+            &mut model::Forest::new(),
+            &mut [],
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::UnknownOverrideField {
+            paths: "*".into(),
+            field: "b".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    fn get_overrides(forest: &model::Forest) -> Vec<Option<model::Override>> {
+        forest
+            .values()
+            .flat_map(|tree| match tree {
+                model::Tree::File(file) => vec![file.override_.clone()],
+                model::Tree::Folder(folder) => get_overrides(&folder.forest),
+            })
+            .collect()
+    }
+}
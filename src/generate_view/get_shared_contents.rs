@@ -0,0 +1,133 @@
+use crate::forest_files;
+use crate::model;
+use std::collections;
+
+pub fn main(forest: &mut model::Forest) -> Vec<model::SharedContents> {
+    let mut files = forest_files::get_files(forest);
+    files.sort_by_key(|file| file.index);
+
+    let mut file_counts = collections::HashMap::<_, usize>::new();
+    for file in &files {
+        if let Some(hash) = &file.hash {
+            *file_counts.entry(hash).or_default() += 1;
+        }
+    }
+
+    let mut shared_contents = vec![];
+    let mut shared_indices = collections::HashMap::new();
+    let mut file_shared_indices = collections::HashMap::new();
+
+    for file in &files {
+        if let Some(hash) = &file.hash {
+            let file_count = file_counts[hash];
+            if file_count > 1 {
+                let shared_index = *shared_indices.entry(hash).or_insert_with(|| {
+                    shared_contents.push(model::SharedContents {
+                        absolute_path: file.absolute_path.clone(),
+                        contents: file.contents.clone(),
+                        size: file.size.unwrap_or_default(),
+                        file_count,
+                    });
+                    shared_contents.len() - 1
+                });
+                file_shared_indices.insert(file.index, shared_index);
+            }
+        }
+    }
+
+    set_shared_indices(forest, &file_shared_indices);
+    shared_contents
+}
+
+fn set_shared_indices(
+    forest: &mut model::Forest,
+    file_shared_indices: &collections::HashMap<usize, usize>,
+) {
+    for tree in forest.values_mut() {
+        match tree {
+            model::Tree::File(file) => {
+                file.shared_contents = file_shared_indices.get(&file.index).copied()
+            }
+            model::Tree::Folder(folder) => {
+                set_shared_indices(&mut folder.forest, file_shared_indices)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let mut forest = [
+            (
+                "a".into(),
+                model::Tree::File(Box::new(model::File {
+                    index: 0,
+                    absolute_path: "/a".into(),
+                    size: Some(3),
+                    hash: Some("1".into()),
+                    ..model::stubs::file()
+                })),
+            ),
+            (
+                "b".into(),
+                model::Tree::Folder(model::Folder {
+                    forest: [
+                        (
+                            "c".into(),
+                            model::Tree::File(Box::new(model::File {
+                                index: 1,
+                                hash: Some("2".into()),
+                                ..model::stubs::file()
+                            })),
+                        ),
+                        (
+                            "d".into(),
+                            model::Tree::File(Box::new(model::File {
+                                index: 2,
+                                hash: Some("1".into()),
+                                ..model::stubs::file()
+                            })),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    ..model::stubs::folder()
+                }),
+            ),
+            (
+                "e".into(),
+                model::Tree::File(Box::new(model::File {
+                    index: 3,
+                    hash: Some("1".into()),
+                    ..model::stubs::file()
+                })),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let actual = main(&mut forest);
+
+        let expected = vec![model::SharedContents {
+            absolute_path: "/a".into(),
+            contents: None,
+            size: 3,
+            file_count: 3,
+        }];
+        assert_eq!(actual, expected);
+        let actual = get_shared_indices(&forest);
+        let expected = vec![Some(0), None, Some(0), Some(0)];
+        assert_eq!(actual, expected);
+    }
+
+    fn get_shared_indices(forest: &model::Forest) -> Vec<Option<usize>> {
+        forest_files::get_files(forest)
+            .into_iter()
+            .map(|file| file.shared_contents)
+            .collect()
+    }
+}
@@ -1,10 +1,14 @@
 use super::get_default_initializer;
 use crate::model;
+use std::collections;
 use std::iter;
 
 pub fn main(
     template: model::Template,
-    structure: model::TypeStructure<()>,
+    structure: model::TypeStructure<syn::Type>,
+    fingerprints: bool,
+    captures: Option<&regex::Regex>,
+    variants: &collections::BTreeMap<String, String>,
 ) -> model::Result<Vec<model::Visitor>> {
     Ok(match template {
         model::Template::Default {
@@ -12,12 +16,19 @@ pub fn main(
             identifiers,
         } => {
             let initializer = match initializer {
-                None => model::Initializer::Default(get_default_initializer::main(structure)?),
+                None => {
+                    let populators = get_default_initializer::main(structure, captures, variants)?;
+                    if !fingerprints && has_fingerprinted_path(&populators) {
+                        return Err(model::Error::NoFingerprint);
+                    }
+                    model::Initializer::Default(populators)
+                }
                 Some(macro_) => model::Initializer::Macro(macro_),
             };
 
             iter::once(model::Visitor::Array(initializer))
                 .chain(iter::once(model::Visitor::Identifiers).filter(|_| identifiers))
+                .chain(iter::once(model::Visitor::Fingerprints).filter(|_| fingerprints))
                 .collect()
         }
 
@@ -27,6 +38,22 @@ pub fn main(
     })
 }
 
+fn has_fingerprinted_path(populators: &model::TypeStructure<model::Populator>) -> bool {
+    let is_fingerprinted_path =
+        |populator: &model::Populator| matches!(populator, model::Populator::FingerprintedPath);
+
+    match populators {
+        model::TypeStructure::Unit => false,
+        model::TypeStructure::TypeAlias(populator) => is_fingerprinted_path(populator),
+        model::TypeStructure::NamedFields(fields) => fields
+            .iter()
+            .any(|(_, populator)| is_fingerprinted_path(populator)),
+        model::TypeStructure::TupleFields(populators) => {
+            populators.iter().any(is_fingerprinted_path)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,6 +70,9 @@ mod tests {
                     identifiers: false,
                 },
                 model::TypeStructure::Unit,
+                false,
+                None,
+                &collections::BTreeMap::new(),
             );
 
             let actual = actual.unwrap();
@@ -60,6 +90,9 @@ mod tests {
                     identifiers: false,
                 },
                 model::stubs::type_structure(),
+                false,
+                None,
+                &collections::BTreeMap::new(),
             );
 
             let actual = actual.unwrap();
@@ -77,6 +110,9 @@ mod tests {
                     identifiers: false,
                 },
                 model::stubs::type_structure(),
+                false,
+                None,
+                &collections::BTreeMap::new(),
             );
 
             let actual = actual.unwrap();
@@ -94,6 +130,9 @@ mod tests {
                     identifiers: true,
                 },
                 model::stubs::type_structure(),
+                false,
+                None,
+                &collections::BTreeMap::new(),
             );
 
             let actual = actual.unwrap();
@@ -103,6 +142,56 @@ mod tests {
             ];
             assert_eq!(actual, expected);
         }
+
+        #[test]
+        fn handles_with_fingerprints() {
+            let actual = main(
+                model::Template::Default {
+                    initializer: None,
+                    identifiers: false,
+                },
+                model::TypeStructure::NamedFields(vec![(
+                    quote::format_ident!("fingerprinted_path"),
+                    syn::parse_quote! { &'static str },
+                )]),
+                true,
+                None,
+                &collections::BTreeMap::new(),
+            );
+
+            let actual = actual.unwrap();
+            let expected = vec![
+                model::Visitor::Array(model::Initializer::Default(
+                    model::TypeStructure::NamedFields(vec![(
+                        quote::format_ident!("fingerprinted_path"),
+                        model::Populator::FingerprintedPath,
+                    )]),
+                )),
+                model::Visitor::Fingerprints,
+            ];
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_fingerprinted_path_without_fingerprints_it_errs() {
+            let actual = main(
+                model::Template::Default {
+                    initializer: None,
+                    identifiers: false,
+                },
+                model::TypeStructure::NamedFields(vec![(
+                    quote::format_ident!("fingerprinted_path"),
+                    syn::parse_quote! { &'static str },
+                )]),
+                false,
+                None,
+                &collections::BTreeMap::new(),
+            );
+
+            let actual = actual.unwrap_err();
+            let expected = model::Error::NoFingerprint;
+            assert_eq!(actual, expected);
+        }
     }
 
     #[test]
@@ -114,6 +203,9 @@ mod tests {
                 visit_file: syn::parse_str("visit_file").unwrap(),
             }]),
             model::stubs::type_structure(),
+            false,
+            None,
+            &collections::BTreeMap::new(),
         );
 
         let actual = actual.unwrap();
@@ -1,25 +1,99 @@
+use super::add_aliases;
+use super::check_main_files;
+use super::get_captures;
 use super::get_forest;
+use super::get_groups;
+use super::get_overrides;
+use super::get_shared_contents;
 use super::get_visitors;
+use super::set_indices;
+use crate::layers;
 use crate::model;
 
 pub fn main(
     configuration: model::Configuration,
-    type_: model::Type<()>,
+    type_: model::Type<syn::Type>,
     paths: Vec<model::Path>,
-) -> model::Result<model::View> {
-    let visitors = get_visitors::main(configuration.template, type_.structure)?;
-    let forest = get_forest::main(paths)?;
-    Ok(model::View {
+    skipped_files: Vec<model::SkippedFile>,
+) -> model::Result<(model::View, Option<model::IndexLock>)> {
+    let captures = configuration
+        .captures
+        .map(|pattern| {
+            regex::Regex::new(&pattern).map_err(|error| model::Error::InvalidPattern {
+                pattern,
+                message: error.to_string(),
+            })
+        })
+        .transpose()?;
+    let visitors = get_visitors::main(
+        configuration.template,
+        type_.structure,
+        configuration.fingerprint.is_some(),
+        captures.as_ref(),
+        &configuration.variants,
+    )?;
+    let layers = layers::get_names(&configuration.sources);
+    let (mut forest, mut shadowed_files) =
+        get_forest::main(paths, &layers, &configuration.variants)?;
+    check_main_files::main(&visitors, &forest)?;
+    get_overrides::main(
+        &configuration.overrides,
+        &configuration.pattern_syntax,
+        &visitors,
+        &mut forest,
+        &mut shadowed_files,
+    )?;
+    if let Some(captures) = &captures {
+        get_captures::main(captures, &visitors, &mut forest, &mut shadowed_files)?;
+    }
+    if let model::Order::Capture(name) = &configuration.order
+        && !captures.as_ref().is_some_and(|captures| {
+            captures
+                .capture_names()
+                .flatten()
+                .any(|capture| capture == name)
+        })
+    {
+        return Err(model::Error::UnknownOrderCapture(name.clone()));
+    }
+    let index_lock = set_indices::main(
+        &configuration.order,
+        configuration.index_lock.as_deref(),
+        &mut forest,
+    )?;
+    let groups = get_groups::main(
+        &configuration.groups,
+        &configuration.pattern_syntax,
+        &forest,
+    )?;
+    // Files are only skipped on request, which is when they are reported.
+    let is_skipping = configuration.special_files == model::SpecialFiles::Skip
+        || configuration.unreadable_files == model::UnreadableFiles::Skip
+        || configuration.oversized_files == model::OversizedFiles::Skip;
+    let shared_contents = configuration
+        .deduplicate
+        .then(|| get_shared_contents::main(&mut forest));
+    add_aliases::main(&configuration.aliases, &mut forest)?;
+    let view = model::View {
         type_: type_.name,
         visitors,
         forest,
+        shared_contents,
+        layers: configuration.runtime_layers.then_some(model::Layers {
+            names: layers,
+            shadowed_files,
+        }),
+        groups,
+        skipped_files: is_skipping.then_some(skipped_files),
         debug: configuration.debug,
-    })
+    };
+    Ok((view, index_lock))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections;
 
     #[test]
     fn handles() {
@@ -39,29 +113,54 @@ mod tests {
             vec![model::Path {
                 relative: vec!["b".into()],
                 absolute: "/a/b".into(),
+                contents: None,
+                size: None,
+                hash: None,
+                fingerprinted_path: None,
+                layer: None,
             }],
+            vec![],
         );
 
         let actual = actual.unwrap();
-        let expected = model::View {
-            type_: quote::format_ident!("Asset"),
-            visitors: vec![
-                model::Visitor::Array(model::Initializer::Macro(syn::parse_str("abc").unwrap())),
-                model::Visitor::Identifiers,
-            ],
-            forest: [(
-                "b".into(),
-                model::Tree::File(model::File {
-                    identifier: quote::format_ident!("r#B"),
-                    index: 0,
-                    relative_path: "b".into(),
-                    absolute_path: "/a/b".into(),
-                }),
-            )]
-            .into_iter()
-            .collect(),
-            debug: true,
-        };
+        let expected = (
+            model::View {
+                type_: quote::format_ident!("Asset"),
+                visitors: vec![
+                    model::Visitor::Array(model::Initializer::Macro(
+                        syn::parse_str("abc").unwrap(),
+                    )),
+                    model::Visitor::Identifiers,
+                ],
+                forest: [(
+                    "b".into(),
+                    model::Tree::File(Box::new(model::File {
+                        identifier: quote::format_ident!("r#B"),
+                        index: 0,
+                        relative_path: "b".into(),
+                        absolute_path: "/a/b".into(),
+                        contents: None,
+                        size: None,
+                        hash: None,
+                        fingerprinted_path: None,
+                        shared_contents: None,
+                        layer: None,
+                        captures: collections::BTreeMap::new(),
+                        variants: collections::BTreeMap::new(),
+                        override_: None,
+                        alias_of: None,
+                    })),
+                )]
+                .into_iter()
+                .collect(),
+                shared_contents: None,
+                layers: None,
+                groups: vec![],
+                skipped_files: None,
+                debug: true,
+            },
+            None,
+        );
         assert_eq!(actual, expected);
     }
 }
@@ -1,7 +1,14 @@
+mod add_aliases;
+mod check_main_files;
+mod get_captures;
 mod get_default_initializer;
 mod get_forest;
+mod get_groups;
+mod get_overrides;
+mod get_shared_contents;
 mod get_visitors;
 mod main;
 mod sanitize_name;
+mod set_indices;
 
 pub use main::main;
@@ -0,0 +1,314 @@
+use crate::forest_files;
+use crate::model;
+use std::cmp;
+use std::collections;
+use std::env;
+use std::fs;
+use std::io;
+use std::path;
+
+// Only reads an index lock, as expansion may happen often and for several
+// `cfg` variants, which the lock must cover together.
+pub fn main(
+    order: &model::Order,
+    index_lock: Option<&path::Path>,
+    forest: &mut model::Forest,
+) -> model::Result<Option<model::IndexLock>> {
+    match index_lock {
+        None => set_indices(order, None, forest),
+
+        Some(file) => {
+            let file = match env::var("CARGO_MANIFEST_DIR") {
+                Ok(folder) if file.is_relative() => path::Path::new(&folder).join(file),
+                _ => file.into(),
+            };
+            let contents = match fs::read_to_string(&file) {
+                Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+                contents => contents.map_err(|source| model::Error::FileRead {
+                    path: file.clone(),
+                    source: model::IoError(source),
+                })?,
+            };
+
+            set_indices(order, Some((&file, &contents)), forest)
+        }
+    }
+}
+
+// Locked files present come first, keeping their order but closing gaps of
+// files only present in other variants, followed by files new to the lock.
+fn set_indices(
+    order: &model::Order,
+    lock: Option<(&path::Path, &str)>,
+    forest: &mut model::Forest,
+) -> model::Result<Option<model::IndexLock>> {
+    let mut files = forest_files::get_files_mut(forest);
+
+    if let model::Order::List(list) = order
+        && let Some(path) = list
+            .iter()
+            .find(|path| !files.iter().any(|file| &file.relative_path == *path))
+    {
+        return Err(model::Error::UnknownOrderPath(path.clone()));
+    }
+    files.sort_by(|left, right| compare(order, left, right));
+
+    match lock {
+        None => {
+            for (index, file) in files.into_iter().enumerate() {
+                file.index = index;
+            }
+            Ok(None)
+        }
+
+        Some((lock_file, contents)) => {
+            let mut locked_paths = vec![];
+            for path in contents.lines().map(str::trim) {
+                if !path.is_empty() && !locked_paths.contains(&path) {
+                    locked_paths.push(path);
+                }
+            }
+
+            let mut missing_paths = vec![];
+            let mut locked_indices = collections::HashMap::new();
+            for path in &locked_paths {
+                if files.iter().any(|file| file.relative_path == *path) {
+                    locked_indices.insert(*path, locked_indices.len());
+                } else {
+                    missing_paths.push(path.to_string());
+                }
+            }
+
+            let locked_count = locked_indices.len();
+            let mut new_paths = vec![];
+            for file in files {
+                file.index = match locked_indices.get(&file.relative_path[..]) {
+                    Some(index) => *index,
+                    None => {
+                        new_paths.push(file.relative_path.clone());
+                        locked_count + new_paths.len() - 1
+                    }
+                };
+            }
+
+            Ok(Some(model::IndexLock {
+                file: lock_file.into(),
+                locked_paths: locked_paths.into_iter().map(String::from).collect(),
+                new_paths,
+                missing_paths,
+            }))
+        }
+    }
+}
+
+// Ties are broken by the relative path for a total order.
+fn compare(order: &model::Order, left: &model::File, right: &model::File) -> cmp::Ordering {
+    let left_path = &left.relative_path;
+    let right_path = &right.relative_path;
+
+    match order {
+        model::Order::Path => cmp::Ordering::Equal,
+        model::Order::Natural => compare_natural(left_path, right_path),
+        model::Order::CaseInsensitive => left_path.to_lowercase().cmp(&right_path.to_lowercase()),
+        model::Order::List(list) => {
+            let get_position = |path| list.iter().position(|entry| entry == path);
+            match (get_position(left_path), get_position(right_path)) {
+                (Some(left), Some(right)) => left.cmp(&right),
+                (Some(_), None) => cmp::Ordering::Less,
+                (None, Some(_)) => cmp::Ordering::Greater,
+                (None, None) => cmp::Ordering::Equal,
+            }
+        }
+        model::Order::Capture(name) => {
+            let get_value = |file: &model::File| file.captures.get(name).cloned();
+            compare_natural(
+                &get_value(left).unwrap_or_default(),
+                &get_value(right).unwrap_or_default(),
+            )
+        }
+    }
+    .then_with(|| left_path.cmp(right_path))
+}
+
+// Runs of digits compare by their numeric value, so `a2` comes before `a10`.
+fn compare_natural(left: &str, right: &str) -> cmp::Ordering {
+    let left_chunks = get_chunks(left);
+    let right_chunks = get_chunks(right);
+
+    for (left, right) in left_chunks.iter().zip(&right_chunks) {
+        let ordering = match (is_number(left), is_number(right)) {
+            (true, true) => {
+                let left = left.trim_start_matches('0');
+                let right = right.trim_start_matches('0');
+                left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+            }
+            _ => left.cmp(right),
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    left_chunks.len().cmp(&right_chunks.len())
+}
+
+fn get_chunks(string: &str) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut start = 0;
+    let mut is_digit = None;
+
+    for (index, character) in string.char_indices() {
+        let is_character_digit = character.is_ascii_digit();
+        if is_digit.is_some_and(|is_digit| is_digit != is_character_digit) {
+            chunks.push(&string[start..index]);
+            start = index;
+        }
+        is_digit = Some(is_character_digit);
+    }
+
+    if start < string.len() {
+        chunks.push(&string[start..]);
+    }
+    chunks
+}
+
+fn is_number(chunk: &str) -> bool {
+    chunk.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_forest(relative_paths: &[&str]) -> model::Forest {
+        relative_paths
+            .iter()
+            .map(|relative_path| {
+                (
+                    relative_path.to_string(),
+                    model::Tree::File(Box::new(model::File {
+                        relative_path: relative_path.to_string(),
+                        captures: relative_path
+                            .split_once('_')
+                            .map(|(_, value)| ("key".into(), value.into()))
+                            .into_iter()
+                            .collect(),
+                        ..model::stubs::file()
+                    })),
+                )
+            })
+            .collect()
+    }
+
+    fn get_relative_paths(forest: &model::Forest) -> Vec<String> {
+        let mut files = forest
+            .values()
+            .map(|tree| match tree {
+                model::Tree::File(file) => (file.index, file.relative_path.clone()),
+                model::Tree::Folder(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files.into_iter().map(|(_, path)| path).collect()
+    }
+
+    #[test]
+    fn handles_each_order() {
+        let paths = ["a10", "a2", "B", "b", "x_10", "y_9"];
+
+        let actual = [
+            model::Order::Path,
+            model::Order::Natural,
+            model::Order::CaseInsensitive,
+            model::Order::List(vec!["y_9".into(), "b".into()]),
+            model::Order::Capture("key".into()),
+        ]
+        .map(|order| {
+            let mut forest = get_forest(&paths);
+            set_indices(&order, None, &mut forest).unwrap();
+            get_relative_paths(&forest)
+        });
+
+        let expected = [
+            ["B", "a10", "a2", "b", "x_10", "y_9"],
+            ["B", "a2", "a10", "b", "x_10", "y_9"],
+            ["a10", "a2", "B", "b", "x_10", "y_9"],
+            ["y_9", "b", "B", "a10", "a2", "x_10"],
+            ["B", "a10", "a2", "b", "y_9", "x_10"],
+        ]
+        .map(|paths| paths.map(String::from));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_unknown_path_in_order_list_it_errs() {
+        let actual = set_indices(
+            &model::Order::List(vec!["b".into()]),
+            None,
+            &mut get_forest(&["a"]),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::UnknownOrderPath("b".into());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_lock_with_new_files() {
+        let mut forest = get_forest(&["a", "b", "c", "d"]);
+
+        let actual = set_indices(
+            &model::Order::Path,
+            Some((path::Path::new("/x.lock"), "c\n\na\n")),
+            &mut forest,
+        );
+
+        let actual = (actual.unwrap(), get_relative_paths(&forest));
+        let expected = (
+            Some(model::IndexLock {
+                file: "/x.lock".into(),
+                locked_paths: vec!["c".into(), "a".into()],
+                new_paths: vec!["b".into(), "d".into()],
+                missing_paths: vec![],
+            }),
+            vec!["c".into(), "a".into(), "b".into(), "d".into()],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_missing_locked_file_it_closes_gap() {
+        let mut forest = get_forest(&["a", "c"]);
+
+        let actual = set_indices(
+            &model::Order::Path,
+            Some((path::Path::new("/x.lock"), "c\nb\na\n")),
+            &mut forest,
+        );
+
+        let actual = (actual.unwrap(), get_relative_paths(&forest));
+        let expected = (
+            Some(model::IndexLock {
+                file: "/x.lock".into(),
+                locked_paths: vec!["c".into(), "b".into(), "a".into()],
+                new_paths: vec![],
+                missing_paths: vec!["b".into()],
+            }),
+            vec!["c".into(), "a".into()],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_natural_comparison() {
+        let actual = [("a2", "a10"), ("a02", "a2"), ("a", "a1"), ("1b", "1a")]
+            .map(|(left, right)| compare_natural(left, right));
+
+        let expected = [
+            cmp::Ordering::Less,
+            cmp::Ordering::Equal,
+            cmp::Ordering::Less,
+            cmp::Ordering::Greater,
+        ];
+        assert_eq!(actual, expected);
+    }
+}
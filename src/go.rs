@@ -1,38 +1,171 @@
+use super::check_index_lock;
 use super::generate_view;
+use super::hash_files;
 use super::list_files;
 use super::model;
 use super::print;
+use super::split_variants;
+use super::transform_files;
+use std::env;
+use std::fs;
+use syn::ext::IdentExt;
 
 pub fn main(
     configuration: model::Configuration,
     item: proc_macro2::TokenStream,
-    type_: model::Type<()>,
+    type_: model::Type<syn::Type>,
+    get_variable: &dyn Fn(&str) -> Option<String>,
 ) -> model::Result<proc_macro2::TokenStream> {
+    let mut tracked_files = configuration.configuration_files.clone();
+    let mut variants = split_variants::main(configuration)?;
+    let mut index_locks = vec![];
+
+    let code = if let [variant] = &mut variants[..]
+        && variant.condition.is_none()
+    {
+        let configuration = variant.configuration.clone();
+        let (code, index_lock) = go(configuration, item, type_, get_variable)?;
+        index_locks.extend(index_lock);
+        code
+    } else {
+        print_variants(variants, item, type_, get_variable, &mut index_locks)?
+    };
+
+    check_index_lock::main(
+        &index_locks,
+        env::var_os(UPDATE_LOCKS_VARIABLE).is_some(),
+        &|file, contents| fs::write(file, contents),
+    )?;
+    // Changing an index lock rebuilds the crate as long as it exists.
+    tracked_files.extend(
+        index_locks
+            .into_iter()
+            .map(|lock| lock.file)
+            .filter(|file| file.is_file())
+            .take(1),
+    );
+    let tracked_files = print::print_configuration_files(&tracked_files);
+
+    Ok(quote::quote! {
+        #code
+
+        #tracked_files
+    })
+}
+
+const UPDATE_LOCKS_VARIABLE: &str = "IFTREE_UPDATE_LOCKS";
+
+// A variant that fails only fails compilation under its condition.
+fn print_variants(
+    variants: Vec<model::Variant>,
+    item: proc_macro2::TokenStream,
+    type_: model::Type<syn::Type>,
+    get_variable: &dyn Fn(&str) -> Option<String>,
+    index_locks: &mut Vec<model::IndexLock>,
+) -> model::Result<proc_macro2::TokenStream> {
+    let variants = variants
+        .into_iter()
+        .enumerate()
+        .map(|(index, variant)| {
+            let code = go(
+                variant.configuration,
+                quote::quote! {},
+                type_.clone(),
+                get_variable,
+            )
+            .map(|(code, index_lock)| {
+                index_locks.extend(index_lock);
+                code
+            });
+            match &variant.condition {
+                None => code,
+                Some(condition) => {
+                    let code = code.unwrap_or_else(|error| {
+                        let message = error.to_string();
+                        quote::quote! { compile_error!(#message); }
+                    });
+                    let module = quote::format_ident!(
+                        "__{}_{index}",
+                        type_.name.unraw().to_string().to_lowercase(),
+                    );
+                    Ok(print::print_condition(condition, &module, code))
+                }
+            }
+        })
+        .collect::<model::Result<proc_macro2::TokenStream>>()?;
+
+    Ok(quote::quote! {
+        #item
+
+        #variants
+    })
+}
+
+fn go(
+    configuration: model::Configuration,
+    item: proc_macro2::TokenStream,
+    type_: model::Type<syn::Type>,
+    get_variable: &dyn Fn(&str) -> Option<String>,
+) -> model::Result<(proc_macro2::TokenStream, Option<model::IndexLock>)> {
     // Pipeline overview:
-    // 1. I/O only happens here.
-    let paths = list_files::main(&configuration)?;
+    // 1. Files are read here. The only other I/O is on the index lock, which is
+    //    read in step 2 and checked or written once all variants are done.
+    let (paths, skipped_files) = list_files::main(&configuration, get_variable)?;
+    let paths = transform_files::main(&configuration, paths, get_variable)?;
+    let paths = hash_files::main(&configuration, paths)?;
     // 2. Construct a view model.
-    let view = generate_view::main(configuration, type_, paths)?;
+    let (view, index_lock) = generate_view::main(configuration, type_, paths, skipped_files)?;
     // 3. Generate code ("view").
-    Ok(print::main(item, view))
+    Ok((print::main(item, view), index_lock))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections;
 
     #[test]
     fn handles() {
         let actual = main(
             model::Configuration {
-                paths: "/assets/*.md".into(),
-                base_folder: "examples".into(),
-                root_folder_variable: "CARGO_MANIFEST_DIR".into(),
+                sources: vec![model::Source {
+                    paths: "/assets/*.md".into(),
+                    base_folder: "examples".into(),
+                    root_folder_variable: "CARGO_MANIFEST_DIR".into(),
+                    mount: vec![],
+                    layer: None,
+                    cfg: None,
+                }],
+                virtual_files: vec![],
+                pattern_syntax: model::PatternSyntax::default(),
+                standard_filters: model::StandardFilters::default(),
+                metadata_filters: model::MetadataFilters::default(),
+                oversized_files: model::OversizedFiles::Fail,
+                symlinks: model::Symlinks::Keep,
+                special_files: model::SpecialFiles::Reject,
+                unreadable_files: model::UnreadableFiles::Fail,
+                required: vec![],
+                min_files: None,
+                max_files: None,
+                require_pattern_matches: false,
+                rewrites: vec![],
+                aliases: collections::BTreeMap::new(),
+                captures: None,
+                variants: collections::BTreeMap::new(),
+                order: model::Order::Path,
+                index_lock: None,
+                groups: collections::BTreeMap::new(),
                 template: model::Template::Default {
                     initializer: None,
                     identifiers: true,
                 },
+                transforms: vec![],
+                overrides: vec![],
+                fingerprint: None,
+                deduplicate: false,
+                runtime_layers: false,
                 debug: false,
+                configuration_files: vec![],
             },
             quote::quote! {
                 pub struct Asset {
@@ -43,9 +176,10 @@ mod tests {
                 name: quote::format_ident!("Asset"),
                 structure: model::TypeStructure::NamedFields(vec![(
                     quote::format_ident!("relative_path"),
-                    (),
+                    syn::parse_quote! { &'static str },
                 )]),
             },
+            &|_| None,
         );
 
         let actual = actual.unwrap().to_string();
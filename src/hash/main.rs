@@ -0,0 +1,21 @@
+use sha2::Digest;
+
+pub fn main(contents: &[u8]) -> String {
+    sha2::Sha256::digest(contents)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let actual = main(b"abc");
+
+        let expected = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections;
+use std::ops;
+use std::str;
+
+// A reference is a path in quotes or in a CSS `url(…)`, optionally followed by
+// a query or fragment. The returned ranges cover the file names only, which is
+// the part that a fingerprint changes.
+pub fn main(
+    contents: &[u8],
+    relative_path: &str,
+    indices: &collections::HashMap<&str, usize>,
+) -> Vec<(ops::Range<usize>, usize)> {
+    let folder = relative_path
+        .rsplit_once('/')
+        .map_or("", |(folder, _)| folder);
+
+    let mut references = vec![];
+    let mut start = 0;
+
+    while start < contents.len() {
+        if !is_path_byte(contents[start]) {
+            start += 1;
+            continue;
+        }
+
+        let end = contents[start..]
+            .iter()
+            .position(|&byte| !is_path_byte(byte))
+            .map_or(contents.len(), |length| start + length);
+
+        if let Some(closing) = get_closing(&contents[..start])
+            && contents
+                .get(end)
+                .is_some_and(|&byte| byte == closing || matches!(byte, b'?' | b'#'))
+            && let Ok(reference) = str::from_utf8(&contents[start..end])
+            && let Some(path) = resolve(folder, reference)
+            && let Some(&path_index) = indices.get(&path[..])
+        {
+            let name_length = reference.rsplit('/').next().map_or(0, str::len);
+            references.push((end - name_length..end, path_index));
+        }
+
+        start = end;
+    }
+
+    references
+}
+
+fn is_path_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'/' | b'_')
+}
+
+fn get_closing(preceding: &[u8]) -> Option<u8> {
+    match preceding.last() {
+        Some(quote @ (b'"' | b'\'')) => Some(*quote),
+        Some(b'(') if preceding.ends_with(b"url(") => Some(b')'),
+        _ => None,
+    }
+}
+
+// A reference starting with "/" is relative to the base folder, otherwise to
+// the folder of the referencing file.
+fn resolve(folder: &str, reference: &str) -> Option<String> {
+    let (mut segments, reference) = match reference.strip_prefix('/') {
+        None => (
+            folder
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .collect(),
+            reference,
+        ),
+        Some(reference) if !reference.starts_with('/') => (vec![], reference),
+        Some(_) => return None,
+    };
+
+    for segment in reference.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_references() {
+        let actual = main(
+            b"<link href=\"/css/a.css\"><img src='b.png?v=1'>",
+            "index.html",
+            &[("b.png", 0), ("css/a.css", 1)].into(),
+        );
+
+        let expected = vec![(17..22, 1), (34..39, 0)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_css_urls() {
+        let actual = main(
+            b"a { background: url(../img/a.png) } b { background: url(\"b.png\") }",
+            "css/c.css",
+            &[("img/a.png", 0), ("css/b.png", 1)].into(),
+        );
+
+        let expected = vec![(27..32, 0), (57..62, 1)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn resolves_relative_to_referencing_file() {
+        let actual = main(
+            b"'b.png' './b.png' '/b.png'",
+            "css/a.css",
+            &[("b.png", 0), ("css/b.png", 1)].into(),
+        );
+
+        let expected = vec![(1..6, 1), (11..16, 1), (20..25, 0)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn ignores_unquoted_paths() {
+        let actual = main(
+            b"See LICENSE or index.html, b.png",
+            "README.md",
+            &[("LICENSE", 0), ("index.html", 1), ("b.png", 2)].into(),
+        );
+
+        let expected = vec![];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn ignores_partial_names() {
+        let actual = main(
+            b"'xa.js' 'a.js.map' 'a.json' '//a.js' '../a.js'",
+            "b.html",
+            &[("a.js", 0)].into(),
+        );
+
+        let expected = vec![];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,49 @@
+pub fn main(relative_path: &str, hash: &str, length: usize) -> String {
+    let fingerprint = &hash[..length];
+    let name_start = relative_path.rfind('/').map_or(0, |index| index + 1);
+
+    match relative_path[name_start..].rfind('.') {
+        Some(extension_start) if extension_start > 0 => {
+            let (stem, extension) = relative_path.split_at(name_start + extension_start);
+            format!("{stem}.{fingerprint}{extension}")
+        }
+        _ => format!("{relative_path}.{fingerprint}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_extension() {
+        let actual = main("app.js", "3f2a1c9b0d", 8);
+
+        let expected = "app.3f2a1c9b.js";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_multiple_extensions() {
+        let actual = main("a/b.min.css", "3f2a1c9b0d", 4);
+
+        let expected = "a/b.min.3f2a.css";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_no_extension() {
+        let actual = main("a.b/LICENSE", "3f2a1c9b0d", 4);
+
+        let expected = "a.b/LICENSE.3f2a";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_dotfile() {
+        let actual = main(".env", "3f2a1c9b0d", 4);
+
+        let expected = ".env.3f2a";
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,416 @@
+use super::find_references;
+use super::get_fingerprinted_path;
+use crate::filter;
+use crate::hash;
+use crate::layers;
+use crate::model;
+use std::collections;
+use std::fs;
+use std::io;
+use std::ops;
+
+pub fn main(
+    configuration: &model::Configuration,
+    paths: Vec<model::Path>,
+) -> model::Result<Vec<model::Path>> {
+    if configuration.fingerprint.is_none() && !configuration.deduplicate {
+        return Ok(paths);
+    }
+
+    hash_paths(
+        configuration.fingerprint.as_ref(),
+        &configuration.pattern_syntax,
+        &layers::get_names(&configuration.sources),
+        paths,
+        &|path| fs::read(path),
+    )
+}
+
+fn hash_paths(
+    fingerprint: Option<&model::Fingerprint>,
+    pattern_syntax: &model::PatternSyntax,
+    layer_names: &[String],
+    paths: Vec<model::Path>,
+    read_file: &dyn Fn(&str) -> io::Result<Vec<u8>>,
+) -> model::Result<Vec<model::Path>> {
+    let filter = fingerprint
+        .and_then(|fingerprint| fingerprint.rewrite_references.as_deref())
+        .map(|patterns| filter::Filter::new(patterns, pattern_syntax))
+        .transpose()?;
+
+    let relative_paths = paths
+        .iter()
+        .map(|path| path.relative.join(NORMALIZED_FOLDER_SEPARATOR))
+        .collect::<Vec<_>>();
+
+    // A reference resolves to the file of the latest layer with that path.
+    let mut indices = collections::HashMap::new();
+    for (index, (path, relative_path)) in paths.iter().zip(&relative_paths).enumerate() {
+        let rank = layers::get_rank(layer_names, path.layer.as_deref());
+        indices
+            .entry(relative_path.as_str())
+            .and_modify(|existing: &mut (usize, usize)| {
+                if rank > existing.0 {
+                    *existing = (rank, index);
+                }
+            })
+            .or_insert((rank, index));
+    }
+    let indices = indices
+        .into_iter()
+        .map(|(relative_path, (_, index))| (relative_path, index))
+        .collect();
+
+    let files = paths
+        .iter()
+        .zip(&relative_paths)
+        .enumerate()
+        .map(|(index, (path, relative_path))| {
+            let contents = match &path.contents {
+                None => read_file(&path.absolute).map_err(|source| model::Error::FileRead {
+                    path: path.absolute.clone().into(),
+                    source: model::IoError(source),
+                })?,
+                Some(contents) => contents.clone(),
+            };
+
+            let references = match &filter {
+                Some(filter) if filter.is_match(relative_path) => {
+                    find_references::main(&contents, relative_path, &indices)
+                        .into_iter()
+                        // A file cannot contain its own fingerprint.
+                        .filter(|(_, path_index)| *path_index != index)
+                        .collect()
+                }
+                _ => vec![],
+            };
+
+            Ok((contents, references))
+        })
+        .collect::<model::Result<Vec<_>>>()?;
+
+    // Files are resolved in dependency order as rewriting references changes
+    // the contents and thus the fingerprint of the referencing file.
+    let mut hashes: Vec<Option<Hash>> = vec![None; files.len()];
+    while hashes.iter().any(Option::is_none) {
+        let resolvable = files
+            .iter()
+            .enumerate()
+            .filter(|(index, (_, references))| {
+                hashes[*index].is_none()
+                    && references
+                        .iter()
+                        .all(|(_, path_index)| hashes[*path_index].is_some())
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        if resolvable.is_empty() {
+            return Err(model::Error::CyclicReferences(
+                paths
+                    .iter()
+                    .zip(&hashes)
+                    .filter(|(_, hash)| hash.is_none())
+                    .map(|(path, _)| path.absolute.clone().into())
+                    .collect(),
+            ));
+        }
+
+        for index in resolvable {
+            let (contents, references) = &files[index];
+            let rewritten_contents = if references.is_empty() {
+                None
+            } else {
+                Some(rewrite_references(contents, references, &|path_index| {
+                    hashes[path_index]
+                        .as_ref()
+                        .and_then(|hash| hash.fingerprinted_path.as_deref())
+                        .and_then(|path| path.rsplit('/').next())
+                        .unwrap_or_default()
+                        .into()
+                }))
+            };
+
+            let contents = rewritten_contents.as_ref().unwrap_or(contents);
+            let hash = hash::main(contents);
+            let fingerprinted_path = fingerprint.map(|fingerprint| {
+                get_fingerprinted_path::main(&relative_paths[index], &hash, fingerprint.length)
+            });
+            hashes[index] = Some(Hash {
+                size: contents.len(),
+                hash,
+                fingerprinted_path,
+                rewritten_contents,
+            });
+        }
+    }
+
+    Ok(paths
+        .into_iter()
+        .zip(hashes.into_iter().flatten())
+        .map(|(path, hash)| model::Path {
+            contents: hash.rewritten_contents.or(path.contents),
+            size: Some(hash.size),
+            hash: Some(hash.hash),
+            fingerprinted_path: hash.fingerprinted_path,
+            ..path
+        })
+        .collect())
+}
+
+const NORMALIZED_FOLDER_SEPARATOR: &str = "/";
+
+#[derive(Clone)]
+struct Hash {
+    size: usize,
+    hash: String,
+    fingerprinted_path: Option<String>,
+    rewritten_contents: Option<Vec<u8>>,
+}
+
+fn rewrite_references(
+    contents: &[u8],
+    references: &[(ops::Range<usize>, usize)],
+    get_fingerprinted_name: &dyn Fn(usize) -> String,
+) -> Vec<u8> {
+    let mut rewritten_contents = Vec::with_capacity(contents.len());
+    let mut end = 0;
+    for (range, path_index) in references {
+        rewritten_contents.extend_from_slice(&contents[end..range.start]);
+        rewritten_contents.extend_from_slice(get_fingerprinted_name(*path_index).as_bytes());
+        end = range.end;
+    }
+    rewritten_contents.extend_from_slice(&contents[end..]);
+    rewritten_contents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_no_fingerprint_it_handles() {
+        let actual = main(
+            &model::Configuration {
+                fingerprint: None,
+                ..model::stubs::configuration()
+            },
+            vec![model::stubs::path()],
+        );
+
+        let actual = actual.unwrap();
+        let expected = vec![model::stubs::path()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_deduplicate_only_it_hashes_without_fingerprints() {
+        let actual = main(
+            &model::Configuration {
+                deduplicate: true,
+                ..model::stubs::configuration()
+            },
+            vec![model::Path {
+                contents: Some(b"a".into()),
+                ..model::stubs::path()
+            }],
+        );
+
+        let actual = actual.unwrap();
+        let expected = vec![model::Path {
+            contents: Some(b"a".into()),
+            size: Some(1),
+            hash: Some(hash::main(b"a")),
+            fingerprinted_path: None,
+            ..model::stubs::path()
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_files() {
+        let actual = hash_paths(
+            Some(&model::Fingerprint {
+                length: 4,
+                ..model::stubs::fingerprint()
+            }),
+            &model::PatternSyntax::default(),
+            &[],
+            vec![
+                model::Path {
+                    relative: vec!["a".into(), "b.js".into()],
+                    absolute: "/a/b.js".into(),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["c".into()],
+                    contents: Some(b"d".into()),
+                    ..model::stubs::path()
+                },
+            ],
+            &|path| Ok(path.as_bytes().into()),
+        );
+
+        let actual = actual.unwrap();
+        let b_hash = hash::main(b"/a/b.js");
+        let c_hash = hash::main(b"d");
+        let expected = vec![
+            model::Path {
+                relative: vec!["a".into(), "b.js".into()],
+                absolute: "/a/b.js".into(),
+                size: Some(7),
+                hash: Some(b_hash.clone()),
+                fingerprinted_path: Some(format!("a/b.{}.js", &b_hash[..4])),
+                ..model::stubs::path()
+            },
+            model::Path {
+                relative: vec!["c".into()],
+                contents: Some(b"d".into()),
+                size: Some(1),
+                hash: Some(c_hash.clone()),
+                fingerprinted_path: Some(format!("c.{}", &c_hash[..4])),
+                ..model::stubs::path()
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rewrites_references_in_dependency_order() {
+        let actual = hash_paths(
+            Some(&model::Fingerprint {
+                length: 64,
+                rewrite_references: Some("*.css\n*.html".into()),
+            }),
+            &model::PatternSyntax::default(),
+            &[],
+            vec![
+                model::Path {
+                    relative: vec!["a.html".into()],
+                    contents: Some(b"<link href='/b.css'><img src='c.png'>".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["b.css".into()],
+                    contents: Some(b"url(c.png) url(b.css)".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["c.png".into()],
+                    contents: Some(b"a.html".into()),
+                    ..model::stubs::path()
+                },
+            ],
+            &|_| unreachable!(),
+        );
+
+        let actual = actual
+            .unwrap()
+            .into_iter()
+            .map(|path| String::from_utf8(path.contents.unwrap()).unwrap())
+            .collect::<Vec<_>>();
+        let c_hash = hash::main(b"a.html");
+        let b_contents = format!("url(c.{c_hash}.png) url(b.css)");
+        let b_hash = hash::main(b_contents.as_bytes());
+        let expected = vec![
+            format!("<link href='/b.{b_hash}.css'><img src='c.{c_hash}.png'>"),
+            b_contents,
+            "a.html".into(),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn resolves_references_to_latest_layer() {
+        let actual = hash_paths(
+            Some(&model::Fingerprint {
+                length: 64,
+                rewrite_references: Some("*.css".into()),
+            }),
+            &model::PatternSyntax::default(),
+            &["x".into(), "y".into()],
+            vec![
+                model::Path {
+                    relative: vec!["a.css".into()],
+                    contents: Some(b"url(b.png)".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["b.png".into()],
+                    contents: Some(b"y".into()),
+                    layer: Some("y".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["b.png".into()],
+                    contents: Some(b"x".into()),
+                    layer: Some("x".into()),
+                    ..model::stubs::path()
+                },
+            ],
+            &|_| unreachable!(),
+        );
+
+        let actual = String::from_utf8(actual.unwrap().swap_remove(0).contents.unwrap()).unwrap();
+        let expected = format!("url(b.{}.png)", hash::main(b"y"));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_cyclic_references_it_errs() {
+        let actual = hash_paths(
+            Some(&model::Fingerprint {
+                rewrite_references: Some("*.css".into()),
+                ..model::stubs::fingerprint()
+            }),
+            &model::PatternSyntax::default(),
+            &[],
+            vec![
+                model::Path {
+                    relative: vec!["a.css".into()],
+                    absolute: "/a.css".into(),
+                    contents: Some(b"url(b.css)".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["b.css".into()],
+                    absolute: "/b.css".into(),
+                    contents: Some(b"url(a.css)".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["c.css".into()],
+                    absolute: "/c.css".into(),
+                    contents: Some(b"".into()),
+                    ..model::stubs::path()
+                },
+            ],
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::CyclicReferences(vec!["/a.css".into(), "/b.css".into()]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_unreadable_file_it_errs() {
+        let actual = hash_paths(
+            Some(&model::stubs::fingerprint()),
+            &model::PatternSyntax::default(),
+            &[],
+            vec![model::Path {
+                absolute: "/a".into(),
+                ..model::stubs::path()
+            }],
+            &|_| Err(io::Error::new(io::ErrorKind::NotFound, "b")),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::FileRead {
+            path: "/a".into(),
+            source: model::IoError(io::Error::new(io::ErrorKind::NotFound, "b")),
+        };
+        assert_eq!(actual, expected);
+    }
+}
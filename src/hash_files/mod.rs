@@ -0,0 +1,5 @@
+mod find_references;
+mod get_fingerprinted_path;
+mod main;
+
+pub use main::main;
@@ -0,0 +1,38 @@
+use crate::model;
+
+pub fn main(sources: &[model::Source]) -> Vec<String> {
+    let mut names = vec![];
+    for layer in sources.iter().filter_map(|source| source.layer.as_ref()) {
+        if !names.contains(layer) {
+            names.push(layer.clone());
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_layers_in_order_of_first_source() {
+        let actual = main(&[
+            model::Source {
+                layer: Some("b".into()),
+                ..model::stubs::source()
+            },
+            model::stubs::source(),
+            model::Source {
+                layer: Some("a".into()),
+                ..model::stubs::source()
+            },
+            model::Source {
+                layer: Some("b".into()),
+                ..model::stubs::source()
+            },
+        ]);
+
+        let expected = vec![String::from("b"), String::from("a")];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,24 @@
+// Files outside of any layer come first, then the layers in order.
+pub fn main(names: &[String], layer: Option<&str>) -> usize {
+    layer.map_or(0, |layer| {
+        names
+            .iter()
+            .position(|name| name == layer)
+            .map_or(0, |index| index + 1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let names = [String::from("a"), String::from("b")];
+
+        let actual = [None, Some("a"), Some("b")].map(|layer| main(&names, layer));
+
+        let expected = [0, 1, 2];
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,5 @@
+mod get_names;
+mod get_rank;
+
+pub use get_names::main as get_names;
+pub use get_rank::main as get_rank;
@@ -161,11 +161,23 @@
 //!
 //!   File contents as a byte array, using
 //!   [`std::include_bytes`](https://doc.rust-lang.org/std/macro.include_bytes.html).
+//!   If [transforms](#transform) apply to the file, its transformed contents are
+//!   used instead.
 //!
 //! - **`contents_str`**`: &'static str`
 //!
 //!   File contents interpreted as a UTF-8 string, using
 //!   [`std::include_str`](https://doc.rust-lang.org/std/macro.include_str.html).
+//!   If [transforms](#transform) apply to the file, its transformed contents are
+//!   used instead.
+//!
+//! - **`fingerprinted_path`**`: &'static str`
+//!
+//!   Like `relative_path` but with a hash of the file contents inserted before
+//!   the filename extension, for example, `app.3f2a1c9b.js`. Such a path changes
+//!   whenever the contents change, so it can be served with long-lived
+//!   `Cache-Control` headers. This requires the
+//!   [`fingerprint` configuration](#fingerprint).
 //!
 //! - **`get_bytes`**`: fn() -> std::borrow::Cow<'static, [u8]>`
 //!
@@ -182,6 +194,9 @@
 //!   using
 //!   [`std::include_bytes`](https://doc.rust-lang.org/std/macro.include_bytes.html).
 //!
+//!   If [transforms](#transform) apply to the file, it always returns the
+//!   transformed contents included at compile time, also in debug builds.
+//!
 //! - **`get_str`**`: fn() -> std::borrow::Cow<'static, str>`
 //!
 //!   Same as `get_bytes` but for the file contents interpreted as a UTF-8 string,
@@ -194,6 +209,9 @@
 //!   (`Cargo.toml`) by default. Path components are separated by a slash `/`,
 //!   independent of your platform.
 //!
+//! Further fields can be populated from the relative path with the
+//! [`captures` configuration](#captures).
+//!
 //! ## Custom file data
 //!
 //! To associate custom data with your files, you can plug in a macro that
@@ -343,6 +361,39 @@
 //! The `iftree::include_file_tree` macro is configured via a
 //! [TOML](https://toml.io) string with the following fields.
 //!
+//! Defaults for all macro invocations of a crate can be given in its `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.iftree]
+//! deduplicate = true
+//! metadata_filters = { max_size = 5_000_000 }
+//! ```
+//!
+//! Fields given to the macro take precedence over fields of a
+//! [`configuration_file`](#configuration_file), which in turn take precedence over
+//! these defaults. Either way, a field replaces a default as a whole.
+//!
+//! In the fields `paths`, `paths_file`, and `base_folder`, as well as in path
+//! pattern files, variables like `${env:NAME}` or `${cargo:pkg_name}` are replaced
+//! at compile time, the same way as by the
+//! [`substitute_variables` transform](#transform). To keep a literal `${`, escape
+//! it as `$${`.
+//!
+//! Changing a [`configuration_file`](#configuration_file) or a
+//! [`paths_file`](#paths_file) rebuilds your crate.
+//!
+//! ## `aliases`
+//!
+//! Additional relative paths for files, given as a table from alias to the
+//! relative path of a file such as `{ 'index.html' = 'static/home.html' }`. An
+//! alias gets an identifier of its own that refers to the same entry in `ASSETS`
+//! as its target, so the asset keeps the relative path of the target. Targets refer
+//! to relative paths after the [`rewrite` configuration](#rewrite) and cannot be
+//! aliases themselves. Compilation fails with an error naming the alias if its
+//! target is missing or if the alias collides with another file or folder.
+//!
+//! **Default**: `{}`
+//!
 //! ## `base_folder`
 //!
 //! Path patterns are interpreted as relative to this folder.
@@ -359,6 +410,57 @@
 //! See
 //! [example](https://github.com/evolutics/iftree/blob/main/examples/configuration_base_folder.rs).
 //!
+//! ## `captures`
+//!
+//! A regular expression over the relative path of each file whose named capture
+//! groups populate fields of the same name in the generated initializer, like
+//! `'^V(?<version>[0-9]+)__(?<name>\w+)\.sql$'` for SQL migrations such as
+//! `V12__add_users.sql`:
+//!
+//! ```ignore
+//! #[iftree::include_file_tree(
+//!     r"
+//! paths = '/migrations/*.sql'
+//! captures = '^migrations/V(?<version>[0-9]+)__(?<name>\w+)\.sql$'
+//! "
+//! )]
+//! pub struct Migration {
+//!     version: u32,
+//!     name: &'static str,
+//!     contents_str: &'static str,
+//! }
+//! ```
+//!
+//! A field of an integer type like `u32` gets an integer, and other fields get a
+//! string. If a file does not match the regular expression, lacks a capture for a
+//! field, or a capture for an integer field is not an integer, compilation fails
+//! with an error naming the file. Anchor the regular expression with `^` and `$`
+//! to match whole paths.
+//!
+//! **Default**: none
+//!
+//! ## `case_insensitive`
+//!
+//! Whether path patterns match regardless of letter case. This applies to all
+//! patterns such as those of the [`paths` configuration](#paths) and of
+//! [transforms](#transform).
+//!
+//! **Default**: `false`
+//!
+//! ## `configuration_file`
+//!
+//! A TOML file with further fields of this configuration, which lets crates share a
+//! configuration. Unless absolute, the file path is relative to the folder given by
+//! the environment variable `CARGO_MANIFEST_DIR`. This field can only be given to
+//! the macro itself.
+//!
+//! ```ignore
+//! #[iftree::include_file_tree("configuration_file = 'iftree.toml'")]
+//! pub struct Asset;
+//! ```
+//!
+//! **Default**: No file
+//!
 //! ## `debug`
 //!
 //! Whether to generate a string variable `DEBUG` with debug information such as the
@@ -369,6 +471,248 @@
 //! See
 //! [example](https://github.com/evolutics/iftree/blob/main/examples/configuration_debug.rs).
 //!
+//! ## `deduplicate`
+//!
+//! Whether to include files with identical (transformed) contents only once.
+//! Duplicates are detected by hashing the contents at compile time. The
+//! [standard fields](#standard-fields) `contents_bytes`, `contents_str`,
+//! `get_bytes`, and `get_str` of all duplicates then refer to one shared static,
+//! which keeps the binary small if many files are the same, like placeholder
+//! images or fallback translations.
+//!
+//! With the [`debug` configuration](#debug), the `DEBUG` string ends with a
+//! line on how many bytes are saved.
+//!
+//! Note that custom initializers and visitors are not affected.
+//!
+//! **Default**: `false`
+//!
+//! ## `file`
+//!
+//! Virtual files with inline contents that do not exist on disk, like a
+//! `version.txt` or a `robots.txt`. Each `[[file]]` table has these fields:
+//!
+//! - `path`: The relative path of the file, like `'meta/version.txt'`.
+//! - `contents`: The contents as a string.
+//! - `substitute_variables`: Whether to replace variables like
+//!   `${cargo:pkg_version}` or `${env:…}` in the contents, as with the
+//!   [`transform` configuration](#transform). Defaults to `false`.
+//!
+//! ```toml
+//! [[file]]
+//! path = 'robots.txt'
+//! contents = '''
+//! User-agent: *
+//! Disallow:
+//! '''
+//!
+//! [[file]]
+//! path = 'version.txt'
+//! contents = '${cargo:pkg_version}'
+//! substitute_variables = true
+//! ```
+//!
+//! Virtual files join the listed files before rewrites and transforms and get
+//! identifiers, indices, and fields like any other file. Their absolute path is
+//! empty, though.
+//!
+//! **Default**: `[]`
+//!
+//! ## `fingerprint`
+//!
+//! Whether to fingerprint files by their contents for cache busting. With a
+//! `[fingerprint]` table in your configuration, the SHA-256 hash of each file's
+//! (transformed) contents is computed at compile time. The standard field
+//! `fingerprinted_path` then becomes available, and the default template
+//! generates these items next to the `ASSETS` array:
+//!
+//! - `BUNDLE_HASH: &str`: a hash over all files, which changes whenever any file
+//!   is added, removed, renamed, or changed.
+//! - `get_by_fingerprinted_path(&str) -> Option<&'static Asset>`: a lookup from
+//!   fingerprinted path back to the asset, say, to serve a request.
+//! - `get_folder_hash(&str) -> Option<&'static str>`: a hash over all files in a
+//!   folder given by its relative path, where `""` is the base folder.
+//!
+//! The optional fields are:
+//!
+//! - `length`: The number of hexadecimal digits of the hash in fingerprinted
+//!   paths, from 1 to 64.
+//! - `rewrite_references`: A string with a path pattern per line, as in the
+//!   [`paths` configuration](#paths). In the files matched, such as CSS or HTML
+//!   files, references to other files are rewritten to the fingerprinted paths.
+//!   A reference is a path in quotes or in a CSS `url(…)`, like
+//!   `"/images/logo.svg"` relative to the base folder or
+//!   `url(../images/logo.svg)` relative to the referencing file. Rewritten files
+//!   are fingerprinted after the files they reference, so files referencing each
+//!   other in a cycle fail the build.
+//!
+//! ```toml
+//! [fingerprint]
+//! length = 8
+//! rewrite_references = '''
+//! *.css
+//! *.html
+//! '''
+//! ```
+//!
+//! **Default**: No fingerprints. If the table is given, `length = 8` and no
+//! references are rewritten.
+//!
+//! See
+//! [example](https://github.com/evolutics/iftree/blob/main/examples/configuration_fingerprint.rs).
+//!
+//! ## `groups`
+//!
+//! Named groups of files, each given by path patterns as in the
+//! [`paths` configuration](#paths), matched against relative paths. For each
+//! group, a static slice of references into `ASSETS` is generated in a module
+//! `groups` next to it, named after the group in screaming snake case and in the
+//! order of `ASSETS`. A file may belong to several groups. If two group names give
+//! the same identifier, compilation fails.
+//!
+//! ```toml
+//! [groups]
+//! images = '''
+//! *.png
+//! *.svg
+//! '''
+//! scripts = '*.js'
+//! ```
+//!
+//! This generates `groups::IMAGES: &[&Asset]` and `groups::SCRIPTS: &[&Asset]`, so
+//! you can iterate over a category of assets without filtering at runtime:
+//!
+//! ```ignore
+//! for image in groups::IMAGES {
+//!     println!("{}", image.relative_path);
+//! }
+//! ```
+//!
+//! **Default**: `{}`
+//!
+//! ## `index_lock`
+//!
+//! A file that keeps the indices of files stable, like `'assets.lock'`. Relative
+//! paths are resolved against the folder of your `Cargo.toml`. This is useful if
+//! indices into `ASSETS` are persisted, say, in save games or network protocols.
+//!
+//! The file lists one relative path per line, whose index is its line number
+//! counting from 0. Compiling only reads the file, so commit it along with your
+//! code. If files are new to it, compilation fails with an error listing them.
+//! Append them to the file, or build once with the environment variable
+//! `IFTREE_UPDATE_LOCKS` set, which appends them in the configured
+//! [`order`](#order). If a listed file is gone, compilation fails, as the indices
+//! of later files would shift otherwise; remove its line to accept that.
+//!
+//! With [`cfg` sources](#source), the file covers the files of all variants
+//! together. A file only gone under some `cfg` is no error, but then later files
+//! move up by one index there.
+//!
+//! **Default**: none
+//!
+//! ## `max_files`
+//!
+//! The maximum number of files. If more files are included, compilation fails.
+//! Together with [`min_files`](#min_files), this catches path patterns that select
+//! far more or fewer files than intended.
+//!
+//! **Default**: No maximum
+//!
+//! ## `metadata_filters`
+//!
+//! Filters on file metadata that exclude files in addition to the
+//! [`paths` configuration](#paths). This is a table with these optional fields:
+//!
+//! - `max_size`: A size in bytes. A larger file fails compilation, so you notice
+//!   a large file matched by mistake instead of embedding it. See
+//!   [`oversized_files`](#oversized_files) to skip such files instead.
+//! - `max_depth`: How many folders deep files may be, where `1` only selects
+//!   files directly in the base folder.
+//! - `content`: Either `'text'` or `'binary'` to only select such files. Like in
+//!   Git, a file is binary if its first 8000 bytes contain a null byte.
+//! - `modified_after`, `modified_before`: A TOML date or datetime such as
+//!   `2024-01-31` or `2024-01-31T12:00:00Z` to only select files modified strictly
+//!   after or before. A date or time without offset is taken as UTC.
+//!
+//! ```toml
+//! paths = '/my_assets/**'
+//! metadata_filters = { max_size = 5_000_000, content = 'text' }
+//! ```
+//!
+//! **Default**: `{}`
+//!
+//! ## `min_files`
+//!
+//! The minimum number of files. If fewer files are included, compilation fails.
+//! See also [`max_files`](#max_files).
+//!
+//! **Default**: No minimum
+//!
+//! ## `order`
+//!
+//! The order of files in `ASSETS`, which also determines their indices:
+//!
+//! - `'path'`: By relative path in Unicode code point order.
+//! - `'natural'`: Like `'path'` but runs of digits compare by their numeric value,
+//!   so `a2` comes before `a10`.
+//! - `'case_insensitive'`: Like `'path'` but regardless of letter case.
+//! - `{ list = ['index.html', 'about.html'] }`: The listed files first in the
+//!   given order, then the others by relative path.
+//! - `{ capture = 'version' }`: Naturally by the value of a named group of the
+//!   [`captures` configuration](#captures).
+//!
+//! Ties are broken by relative path.
+//!
+//! **Default**: `'path'`
+//!
+//! ## `override`
+//!
+//! Settings for subsets of files selected by path patterns. Each `[[override]]`
+//! table has these fields:
+//!
+//! - `paths`: Path patterns as in the [`paths` configuration](#paths) to select
+//!   the files.
+//! - `initializer`: A macro to initialize the selected files instead of the
+//!   default or [configured initializer](#templateinitializer).
+//! - `transform`: A further transform applied to the selected files after those of
+//!   the [`transform` configuration](#transform), with the same fields except
+//!   `paths`.
+//! - `fields`: A table of Rust expressions by field name, which initialize these
+//!   fields of the selected files instead of the
+//!   [standard fields](#standard-fields). Fields of tuple structs are named by
+//!   their index like `'0'`.
+//!
+//! ```toml
+//! [[override]]
+//! paths = '*.json'
+//! transform.minify_json = true
+//!
+//! [[override]]
+//! paths = '*.png'
+//! fields = { contents_str = '""' }
+//!
+//! [[override]]
+//! paths = '*.svg'
+//! initializer = 'svg_asset'
+//! ```
+//!
+//! For each file, the last override whose patterns match applies, like for
+//! `paths`. Its unset fields fall back to the configuration at the top level, not
+//! to earlier overrides.
+//!
+//! **Default**: `[]`
+//!
+//! ## `oversized_files`
+//!
+//! What to do with files larger than the `max_size` of the
+//! [`metadata_filters` configuration](#metadata_filters):
+//!
+//! - `'fail'`: Fail compilation with an error naming the file.
+//! - `'skip'`: Leave the file out and list it in a generated array
+//!   `SKIPPED_FILES` of `(path, reason)` pairs.
+//!
+//! **Default**: `'fail'`
+//!
 //! ## `paths`
 //!
 //! A string with a path pattern per line to filter files.
@@ -379,10 +723,15 @@
 //! - If the last matching pattern is not negated, the file is included.
 //! - If no pattern matches, the file is excluded.
 //!
+//! A folder excluded by a negated pattern like `!/x/y/` is not searched at all,
+//! which saves time for large folders such as `target`.
+//!
 //! The pattern language is as documented in the
 //! [`.gitignore` reference](https://git-scm.com/docs/gitignore), with this
 //! difference: you must use `x/y/*` instead of `x/y/` to include files in a folder
-//! `x/y/`; to also include subfolders (recursively), use `x/y/**`.
+//! `x/y/`; to also include subfolders (recursively), use `x/y/**`. To avoid this
+//! difference, configure
+//! [`pattern_syntax = 'gitignore_faithful'`](#pattern_syntax).
 //!
 //! By default, path patterns are relative to the environment variable
 //! `CARGO_MANIFEST_DIR`, which is the folder with your manifest (`Cargo.toml`). See
@@ -393,11 +742,115 @@
 //! - Exclude hidden files: `!.*`
 //! - Include files with filename extension `xyz` only: `*.xyz`
 //!
-//! This is a **required** option without default.
+//! Independently of these patterns, files are excluded by any `.iftreeignore` file
+//! in their folder or a parent folder. Such a file works like a `.gitignore` file
+//! scoped to its folder. This way, you can exclude files next to them without
+//! changing the macro invocation. The `.iftreeignore` files themselves are never
+//! included.
+//!
+//! This is a **required** option without default unless you configure
+//! [`source`](#source) or only [`file`](#file) instead.
 //!
 //! See
 //! [example](https://github.com/evolutics/iftree/blob/main/examples/configuration_paths.rs).
 //!
+//! ## `paths_file`
+//!
+//! A file with further path patterns, which are appended to those of the
+//! [`paths` configuration](#paths). This keeps long pattern lists reviewable and
+//! shareable among crates. Unless absolute, the file path is relative to the folder
+//! given by the environment variable `CARGO_MANIFEST_DIR`.
+//!
+//! Each [`source`](#source) may also have a `paths_file` of its own.
+//!
+//! ```toml
+//! paths_file = 'assets.patterns'
+//! ```
+//!
+//! **Default**: No file
+//!
+//! ## `pattern_syntax`
+//!
+//! The language of all path patterns such as those of the
+//! [`paths` configuration](#paths) and of [transforms](#transform):
+//!
+//! - `'gitignore'`: The `.gitignore` syntax as described for the
+//!   [`paths` configuration](#paths).
+//! - `'gitignore_faithful'`: Like `'gitignore'`, but a folder pattern like `x/y/`
+//!   includes all contents of the folder recursively, as in Git. A negated
+//!   folder pattern like `!x/y/z/` excludes all contents of that folder, which
+//!   later patterns cannot include again.
+//! - `'glob'`: A glob per line that must match the whole relative path like
+//!   `assets/**/*.png`. A `*` does not match a `/`, but `**` matches any number of
+//!   folders.
+//! - `'regex'`: A [regular expression](https://docs.rs/regex) per line that
+//!   matches relative paths anywhere unless anchored like `^assets/.*\.png$`.
+//!
+//! With `'glob'` and `'regex'`, a file is included if it matches any pattern but
+//! none of the patterns prefixed with `!`. Lines starting with `#` are comments in
+//! all syntaxes. Relative paths use `/` as folder separator on all platforms.
+//!
+//! ```toml
+//! paths = '''
+//! **/*.{png,jpg}
+//! !drafts/**
+//! '''
+//! pattern_syntax = 'glob'
+//! ```
+//!
+//! **Default**: `'gitignore'`
+//!
+//! ## `require_pattern_matches`
+//!
+//! Whether every path pattern of the [`paths` configuration](#paths) and of each
+//! [`source`](#source) must select at least one file. If a pattern selects none,
+//! say, due to a typo, compilation fails with an error naming the pattern. Negated
+//! patterns are exempt.
+//!
+//! **Default**: `false`
+//!
+//! ## `required`
+//!
+//! Relative paths of files that must be included such as
+//! `['index.html', 'favicon.ico']`. If one of them is missing, compilation fails
+//! with an error naming it.
+//!
+//! **Default**: `[]`
+//!
+//! ## `rewrite`
+//!
+//! Rules that rewrite the relative paths of files, say, to serve
+//! `frontend/dist/app.js` as `static/app.js` without changing the
+//! [`base_folder`](#base_folder) and thus the path patterns. Identifiers, lookups,
+//! and the standard field `relative_path` all use the rewritten paths.
+//!
+//! Each rule applies to the files matched by its optional `paths` option, which
+//! uses the same pattern language as the [`paths` configuration](#paths) and
+//! defaults to all files. Rules are applied in the configured order, each to the
+//! result of the previous ones.
+//!
+//! The following optional steps of a rule are applied in this order:
+//!
+//! 1. `strip_prefix`: Remove a leading folder path such as `'frontend/dist/'`
+//!    from paths that start with it.
+//! 1. `rename`: Replace the first match of a regular expression in the path, given
+//!    as a table like `{ regex = '^(.*)\.htm$', replacement = '$1.html' }`. The
+//!    replacement may refer to capture groups as `$1` or `$name`.
+//! 1. `extensions`: Map file extensions, like `{ htm = 'html' }`. An empty
+//!    extension such as `{ md = '' }` removes the extension.
+//! 1. `add_prefix`: Prepend a folder path such as `'static/'`.
+//!
+//! If a path ends up empty or in the place of another path, compilation fails with
+//! an error naming the file.
+//!
+//! ```toml
+//! [[rewrite]]
+//! strip_prefix = 'frontend/dist/'
+//! add_prefix = 'static/'
+//! ```
+//!
+//! **Default**: `[]`
+//!
 //! ## `root_folder_variable`
 //!
 //! An environment variable that is used to resolve a relative
@@ -407,6 +860,116 @@
 //!
 //! **Default**: `"CARGO_MANIFEST_DIR"`
 //!
+//! ## `runtime_layers`
+//!
+//! Whether to also include the files shadowed by [layers](#source) and to generate
+//! a function `select_layer` to choose a layer at runtime. For a layer name,
+//! `select_layer` returns a lookup by relative path that falls through to the
+//! earlier layers:
+//!
+//! ```ignore
+//! let get = select_layer("dark").unwrap();
+//! let asset = get("style.css").unwrap();
+//! ```
+//!
+//! The shadowed files are then appended to the `ASSETS` array. This requires the
+//! default [`template`](#templateinitializer).
+//!
+//! **Default**: `false`
+//!
+//! ## `source`
+//!
+//! Further sources of files, which are merged into one file tree together with the
+//! files of the [`paths` configuration](#paths). Each source is a table with these
+//! fields:
+//!
+//! - `paths`: Path patterns like in the [`paths` configuration](#paths).
+//! - `paths_file`: Like the [`paths_file` configuration](#paths_file).
+//! - `base_folder`: Like the [`base_folder` configuration](#base_folder), which it
+//!   defaults to.
+//! - `root_folder_variable`: Like the
+//!   [`root_folder_variable` configuration](#root_folder_variable), which it
+//!   defaults to.
+//! - `mount`: A folder path such as `'static/'` that is prepended to the relative
+//!   paths of the files in this source. Defaults to `''`.
+//! - `layer`: A layer name such as `'dark'` for files that override others.
+//!   Layers are ordered by the first source that names them. A file in a later
+//!   layer shadows a file with the same relative path in an earlier layer or
+//!   outside of any layer. Several sources may share a layer.
+//! - `cfg`: A [`cfg` predicate](https://doc.rust-lang.org/reference/conditional-compilation.html)
+//!   such as `'feature = "docs"'` or `'not(debug_assertions)'` under which this
+//!   source is included. For each combination of the predicates used, the
+//!   generated code is then gated with `#[cfg(…)]`, so `ASSETS`, indices, and the
+//!   `base` module are consistent whichever combination holds. Checks like the
+//!   [`required` configuration](#required) apply to each combination, and an
+//!   error in a combination only fails compilation where it holds. As the number
+//!   of combinations doubles with each distinct predicate, at most 6 predicates
+//!   are allowed.
+//!
+//! If two files in the same layer end up with the same relative path, or if one
+//! takes the place of a folder of another, compilation fails with an error naming
+//! both files.
+//!
+//! ```toml
+//! paths = '/frontend/dist/**'
+//!
+//! [[source]]
+//! paths = '**'
+//! base_folder = 'docs/build'
+//! mount = 'docs/'
+//! ```
+//!
+//! **Default**: `[]`
+//!
+//! ## `special_files`
+//!
+//! What to do with special files such as sockets, named pipes, or device files
+//! matched by the [`paths` configuration](#paths):
+//!
+//! - `'reject'`: Fail compilation with an error naming the file.
+//! - `'skip'`: Leave the file out and list it in a generated array
+//!   `SKIPPED_FILES` of `(path, reason)` pairs.
+//!
+//! **Default**: `'reject'`
+//!
+//! ## `standard_filters`
+//!
+//! Standard ignore rules that exclude files in addition to the
+//! [`paths` configuration](#paths). Either `true` to enable all of them or a table
+//! to enable each on its own:
+//!
+//! - `gitignore`: Respect `.gitignore` files.
+//! - `git_exclude`: Respect `.git/info/exclude` files.
+//! - `git_global`: Respect the global Git ignore file such as
+//!   `~/.config/git/ignore`.
+//! - `ignore`: Respect `.ignore` files.
+//! - `hidden`: Exclude hidden files, whose names start with a `.`.
+//!
+//! Ignore files in parent folders of the base folder apply as well. This also works
+//! outside of Git repositories, for example, in a packaged crate.
+//!
+//! ```toml
+//! paths = '/my_assets/**'
+//! standard_filters = { gitignore = true, hidden = true }
+//! ```
+//!
+//! **Default**: `false`
+//!
+//! ## `symlinks`
+//!
+//! How symbolic links are treated:
+//!
+//! - `'keep'`: Include a link to a file like a regular file, but do not descend
+//!   into a link to a folder. A dangling link is treated as a
+//!   [special file](#special_files).
+//! - `'follow'`: Follow all links, including links to folders. Link loops fail
+//!   compilation.
+//! - `'follow_within_base_folder'`: Like `'follow'`, but a link whose target is
+//!   outside of the base folder fails compilation unless the link itself is
+//!   excluded.
+//!
+//! **Default**: `'keep'`
+//!
 //! ## `template.identifiers`
 //!
 //! Whether to generate an identifier per file.
@@ -504,17 +1067,137 @@
 //! - [Nesting](https://github.com/evolutics/iftree/blob/main/examples/configuration_template_visitors_nesting.rs)
 //! - [Emulation of default code generation](https://github.com/evolutics/iftree/blob/main/examples/configuration_template_visitors_emulation.rs)
 //!
+//! ## `transform`
+//!
+//! Transforms applied to file contents at compile time. This way, included
+//! contents do not depend on how files are checked out, say, with CRLF line
+//! endings on one platform and LF on another.
+//!
+//! Each transform applies to the files matched by its `paths` option, which uses
+//! the same pattern language as the [`paths` configuration](#paths). If multiple
+//! transforms match a file, they are applied in the configured order.
+//!
+//! The transformed contents are used by the [standard fields](#standard-fields)
+//! `contents_bytes`, `contents_str`, `get_bytes`, and `get_str`. However, custom
+//! initializers and visitors are still passed the original file paths.
+//!
+//! The following optional steps of a transform are applied in this order:
+//!
+//! 1. `command`: Run an external command given as a list of program and
+//!    arguments, like `['sass', '--stdin']`. The file contents are passed via
+//!    standard input and replaced by the standard output. The working directory
+//!    is the folder of the file. A nonzero exit status fails the build. Outputs
+//!    are cached in the Cargo target folder under `iftree/commands`, keyed by
+//!    command, working directory, and input, so the command is only rerun if any
+//!    of them changes.
+//! 1. `encoding`: Transcode from the given encoding to UTF-8. This is one of
+//!    `'latin-1'`, `'utf-8'` (validation only), `'utf-16be'`, `'utf-16le'`, or
+//!    `'auto'`. The latter detects UTF-16 by its byte order mark (BOM), keeps
+//!    valid UTF-8 as is, and falls back to Latin-1 otherwise. Note that a BOM is
+//!    transcoded, too.
+//! 1. `strip_bom = true`: Remove a leading UTF-8 BOM.
+//! 1. `substitute_variables = true`: Replace variables with their values at
+//!    compile time. Supported variables are `${cargo:…}` for Cargo environment
+//!    variables (for example, `${cargo:pkg_version}` for `CARGO_PKG_VERSION`),
+//!    `${env:…}` for any environment variable, and `${git:commit}` for the
+//!    current Git commit. An undefined variable fails the build. To keep a
+//!    literal `${`, escape it as `$${`. Unlike templating at runtime, the result
+//!    is a plain constant. Note that Cargo does not track these variables, so
+//!    changing only an environment variable or the Git commit does not rebuild
+//!    your crate. To rebuild then, add a build script that prints
+//!    `cargo::rerun-if-env-changed=…` for each environment variable or
+//!    `cargo::rerun-if-changed=…` for the Git files that change with commits.
+//! 1. `line_endings`: Normalize line endings to `'lf'` or `'crlf'`.
+//! 1. `trim_trailing_whitespace = true`: Remove whitespace at the end of each
+//!    line.
+//! 1. `minify_json = true`: Validate JSON and remove whitespace outside of
+//!    strings.
+//!
+//! To configure transforms, add this to your configuration for each transform:
+//!
+//! ```toml
+//! [[transform]]
+//! paths = '*.txt'
+//! encoding = 'auto'
+//! strip_bom = true
+//! line_endings = 'lf'
+//! ```
+//!
+//! **Default**: `[]`
+//!
+//! See
+//! [example](https://github.com/evolutics/iftree/blob/main/examples/configuration_transform.rs).
+//!
+//! ## `unreadable_files`
+//!
+//! What to do with files or folders that cannot be read, say, due to missing
+//! permissions:
+//!
+//! - `'fail'`: Fail compilation with an error naming the path.
+//! - `'skip'`: Leave the path out and list it in a generated array
+//!   `SKIPPED_FILES` of `(path, reason)` pairs.
+//!
+//! **Default**: `'fail'`
+//!
+//! ## `variants`
+//!
+//! Names of file variants with their suffixes, like
+//! `{ map = '.map', gzip = '.gz', brotli = '.br' }`. Files whose names end with
+//! such a suffix are grouped with the file of their stem in the same folder into
+//! one asset, whose fields of the same names as the variants hold the variant
+//! contents if present:
+//!
+//! ```ignore
+//! #[iftree::include_file_tree(
+//!     r"
+//! paths = '/dist/**'
+//! variants = { map = '.map', gzip = '.gz' }
+//! "
+//! )]
+//! pub struct Asset {
+//!     relative_path: &'static str,
+//!     contents_str: &'static str,
+//!     map: Option<&'static str>,
+//!     gzip: Option<&'static [u8]>,
+//! }
+//! ```
+//!
+//! Here, `dist/app.js`, `dist/app.js.map`, and `dist/app.js.gz` become a single
+//! asset `dist/app.js`. A variant field of type `Option<&'static str>` gets the
+//! contents as a string and any other type gets bytes. If several suffixes match a
+//! filename, the longest one wins.
+//!
+//! A group may lack a file of its own stem, as with test fixtures
+//! `case.input`/`case.expected`, which become an asset `case` with variants
+//! `input` and `expected`. Such an asset has no contents of its own, so then
+//! compilation fails on fields like `contents_str`, and the absolute path passed
+//! to custom initializers or visitors is empty.
+//!
+//! **Default**: `{}`
+//!
 //! # Further resources
 //!
 //! - [Changelog](https://github.com/evolutics/iftree/blob/main/CHANGELOG.md)
 //! - [Latest revision of this documentation](https://github.com/evolutics/iftree/blob/main/README.md)
 
+mod cache;
+mod check_index_lock;
+mod file_variants;
+mod filter;
+mod forest_files;
 mod generate_view;
 mod go;
+mod hash;
+mod hash_files;
+mod layers;
 mod list_files;
 mod model;
 mod parse;
 mod print;
+mod split_variants;
+mod transform_files;
+
+use std::cell;
 
 /// See the [module level documentation](self).
 #[proc_macro_attribute]
@@ -522,11 +1205,18 @@ pub fn include_file_tree(
     parameters: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let configuration = syn::parse_macro_input!(parameters);
+    let token = syn::parse_macro_input!(parameters as syn::LitStr);
     let item2 = item.clone().into();
     let type_ = syn::parse_macro_input!(item);
 
-    match go::main(configuration, item2, type_) {
+    let git_commit = cell::OnceCell::new();
+    let get_variable = |name: &str| transform_files::resolve_variable(name, &git_commit);
+    let configuration = match parse::parse_configuration(&token, &get_variable) {
+        Err(error) => return error.to_compile_error().into(),
+        Ok(configuration) => configuration,
+    };
+
+    match go::main(configuration, item2, type_, &get_variable) {
         Err(error) => panic!("{error}"),
         Ok(code) => code.into(),
     }
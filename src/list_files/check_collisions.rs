@@ -0,0 +1,149 @@
+use crate::model;
+use std::collections;
+use std::iter;
+use std::ptr;
+
+pub fn main(paths: &[model::Path]) -> model::Result<()> {
+    let mut files = collections::BTreeMap::new();
+    let mut layer_files = collections::BTreeMap::new();
+    for path in paths {
+        files.entry(&path.relative[..]).or_insert(path);
+        layer_files
+            .entry((&path.relative[..], &path.layer))
+            .or_insert(path);
+    }
+
+    for path in paths {
+        // A file collides with a file of the same path in the same layer, as
+        // other layers shadow each other, or with a file in any layer that
+        // takes one of its folders as a file.
+        let collision = iter::once(layer_files.get(&(&path.relative[..], &path.layer)))
+            .chain(
+                (1..path.relative.len())
+                    .rev()
+                    .map(|length| files.get(&path.relative[..length])),
+            )
+            .flatten()
+            .find(|other| {
+                other.absolute != path.absolute
+                    // Virtual files share the empty absolute path.
+                    || (path.absolute.is_empty() && !ptr::eq(**other, path))
+            });
+
+        if let Some(other) = collision {
+            return Err(model::Error::PathCollision {
+                relative_path: other.relative.join("/").into(),
+                absolute_paths: [other.absolute.clone().into(), path.absolute.clone().into()],
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_distinct_paths() {
+        let actual = main(&[
+            model::Path {
+                relative: vec!["a".into(), "b".into()],
+                absolute: "/c/a/b".into(),
+                ..model::stubs::path()
+            },
+            model::Path {
+                relative: vec!["a".into(), "c".into()],
+                absolute: "/d/c".into(),
+                ..model::stubs::path()
+            },
+        ]);
+
+        actual.unwrap();
+    }
+
+    #[test]
+    fn given_same_relative_path_it_errs() {
+        let actual = main(&[
+            model::Path {
+                relative: vec!["a".into(), "b".into()],
+                absolute: "/c/a/b".into(),
+                ..model::stubs::path()
+            },
+            model::Path {
+                relative: vec!["a".into(), "b".into()],
+                absolute: "/d/b".into(),
+                ..model::stubs::path()
+            },
+        ]);
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::PathCollision {
+            relative_path: "a/b".into(),
+            absolute_paths: ["/c/a/b".into(), "/d/b".into()],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_file_in_place_of_folder_it_errs() {
+        let actual = main(&[
+            model::Path {
+                relative: vec!["a".into(), "b".into()],
+                absolute: "/c/a/b".into(),
+                ..model::stubs::path()
+            },
+            model::Path {
+                relative: vec!["a".into()],
+                absolute: "/d/a".into(),
+                ..model::stubs::path()
+            },
+        ]);
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::PathCollision {
+            relative_path: "a".into(),
+            absolute_paths: ["/d/a".into(), "/c/a/b".into()],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_same_relative_path_of_virtual_files_it_errs() {
+        let path = model::Path {
+            relative: vec!["a".into()],
+            absolute: String::new(),
+            contents: Some(vec![]),
+            ..model::stubs::path()
+        };
+
+        let actual = main(&[path.clone(), path]);
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::PathCollision {
+            relative_path: "a".into(),
+            absolute_paths: ["".into(), "".into()],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_same_relative_path_in_other_layer_it_handles() {
+        let actual = main(&[
+            model::Path {
+                relative: vec!["a".into()],
+                absolute: "/b/a".into(),
+                ..model::stubs::path()
+            },
+            model::Path {
+                relative: vec!["a".into()],
+                absolute: "/c/a".into(),
+                layer: Some("d".into()),
+                ..model::stubs::path()
+            },
+        ]);
+
+        actual.unwrap();
+    }
+}
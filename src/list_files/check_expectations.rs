@@ -0,0 +1,112 @@
+use crate::model;
+use std::collections;
+
+pub fn main(configuration: &model::Configuration, paths: &[model::Path]) -> model::Result<()> {
+    let relative_paths = paths
+        .iter()
+        .map(|path| path.relative.join("/"))
+        .collect::<collections::BTreeSet<_>>();
+
+    for required in &configuration.required {
+        if !relative_paths.contains(required.trim_start_matches('/')) {
+            return Err(model::Error::MissingRequiredFile(required.clone()));
+        }
+    }
+
+    let count = paths.len();
+
+    if let Some(min_files) = configuration.min_files
+        && count < min_files
+    {
+        return Err(model::Error::TooFewFiles { count, min_files });
+    }
+
+    if let Some(max_files) = configuration.max_files
+        && count > max_files
+    {
+        return Err(model::Error::TooManyFiles { count, max_files });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_paths() -> Vec<model::Path> {
+        [vec!["a"], vec!["b", "c"]]
+            .into_iter()
+            .map(|relative| model::Path {
+                relative: relative.into_iter().map(|name| name.into()).collect(),
+                ..model::stubs::path()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn handles_met_expectations() {
+        let actual = main(
+            &model::Configuration {
+                required: vec!["a".into(), "/b/c".into()],
+                min_files: Some(2),
+                max_files: Some(2),
+                ..model::stubs::configuration()
+            },
+            &get_paths(),
+        );
+
+        actual.unwrap();
+    }
+
+    #[test]
+    fn given_missing_required_file_it_errs() {
+        let actual = main(
+            &model::Configuration {
+                required: vec!["a".into(), "b/d".into()],
+                ..model::stubs::configuration()
+            },
+            &get_paths(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::MissingRequiredFile("b/d".into());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_too_few_files_it_errs() {
+        let actual = main(
+            &model::Configuration {
+                min_files: Some(3),
+                ..model::stubs::configuration()
+            },
+            &get_paths(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::TooFewFiles {
+            count: 2,
+            min_files: 3,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_too_many_files_it_errs() {
+        let actual = main(
+            &model::Configuration {
+                max_files: Some(1),
+                ..model::stubs::configuration()
+            },
+            &get_paths(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::TooManyFiles {
+            count: 2,
+            max_files: 1,
+        };
+        assert_eq!(actual, expected);
+    }
+}
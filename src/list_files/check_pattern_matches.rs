@@ -0,0 +1,82 @@
+use super::get_relative_path;
+use crate::filter;
+use crate::model;
+use std::path;
+
+pub fn main(
+    source: &model::Source,
+    pattern_syntax: &model::PatternSyntax,
+    base_folder: &path::Path,
+    paths: &[path::PathBuf],
+) -> model::Result<()> {
+    let relative_paths = paths
+        .iter()
+        .filter_map(|path| get_relative_path::main(base_folder, path))
+        .collect::<Vec<_>>();
+
+    // Negated patterns and comments select nothing on their own.
+    for pattern in source
+        .paths
+        .lines()
+        .filter(|pattern| !(pattern.trim().is_empty() || pattern.starts_with(['!', '#'])))
+    {
+        let filter = filter::Filter::new(pattern, pattern_syntax)?;
+
+        if !relative_paths
+            .iter()
+            .any(|relative_path| filter.is_match(relative_path))
+        {
+            return Err(model::Error::UnmatchedPattern {
+                pattern: pattern.into(),
+                base_folder: base_folder.into(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_matched_patterns() {
+        let actual = main(
+            &model::Source {
+                paths: "/a/*.b
+
+# Comment.
+/c/**
+!/c/d"
+                    .into(),
+                ..model::stubs::source()
+            },
+            &model::PatternSyntax::default(),
+            path::Path::new("/e"),
+            &["/e/a/f.b".into(), "/e/c/g/h".into()],
+        );
+
+        actual.unwrap();
+    }
+
+    #[test]
+    fn given_unmatched_pattern_it_errs() {
+        let actual = main(
+            &model::Source {
+                paths: "/a/*.b\n/a/*.c".into(),
+                ..model::stubs::source()
+            },
+            &model::PatternSyntax::default(),
+            path::Path::new("/e"),
+            &["/e/a/f.b".into()],
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::UnmatchedPattern {
+            pattern: "/a/*.c".into(),
+            base_folder: "/e".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
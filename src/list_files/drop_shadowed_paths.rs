@@ -0,0 +1,74 @@
+use crate::layers;
+use crate::model;
+use std::collections;
+
+// Without layers at runtime, a shadowed file is never used, so it is dropped
+// before its contents are hashed or packed.
+pub fn main(paths: Vec<model::Path>, names: &[String]) -> Vec<model::Path> {
+    let mut ranks = collections::HashMap::new();
+    for path in &paths {
+        let rank = layers::get_rank(names, path.layer.as_deref());
+        ranks
+            .entry(path.relative.clone())
+            .and_modify(|existing_rank: &mut usize| *existing_rank = rank.max(*existing_rank))
+            .or_insert(rank);
+    }
+
+    paths
+        .into_iter()
+        .filter(|path| ranks[&path.relative] == layers::get_rank(names, path.layer.as_deref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let actual = main(
+            vec![
+                model::Path {
+                    relative: vec!["a".into()],
+                    absolute: "/b/a".into(),
+                    layer: Some("b".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["a".into()],
+                    absolute: "/c/a".into(),
+                    layer: Some("c".into()),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["a".into()],
+                    absolute: "/a".into(),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["d".into()],
+                    absolute: "/b/d".into(),
+                    layer: Some("b".into()),
+                    ..model::stubs::path()
+                },
+            ],
+            &["b".into(), "c".into()],
+        );
+
+        let expected = vec![
+            model::Path {
+                relative: vec!["a".into()],
+                absolute: "/c/a".into(),
+                layer: Some("c".into()),
+                ..model::stubs::path()
+            },
+            model::Path {
+                relative: vec!["d".into()],
+                absolute: "/b/d".into(),
+                layer: Some("b".into()),
+                ..model::stubs::path()
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+}
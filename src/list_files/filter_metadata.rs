@@ -0,0 +1,216 @@
+use crate::model;
+use std::cmp;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path;
+
+pub fn main(
+    filters: &model::MetadataFilters,
+    base_folder: &path::Path,
+    path: &path::Path,
+) -> io::Result<Selection> {
+    if filters.max_depth.is_some_and(|max_depth| {
+        path.strip_prefix(base_folder)
+            .map_or(0, |relative_path| relative_path.components().count())
+            > max_depth
+    }) {
+        return Ok(Selection::Exclude);
+    }
+
+    if filters.max_size.is_none()
+        && filters.modified_after.is_none()
+        && filters.modified_before.is_none()
+        && filters.content.is_none()
+    {
+        return Ok(Selection::Include);
+    }
+
+    let metadata = fs::metadata(path)?;
+
+    if let Some(max_size) = filters.max_size
+        && metadata.len() > max_size
+    {
+        // Unlike other filters, this guards against files included by mistake,
+        // so such files are reported.
+        return Ok(Selection::Oversized(metadata.len()));
+    }
+
+    if filters.modified_after.is_some() || filters.modified_before.is_some() {
+        let modified = metadata.modified()?;
+        if filters
+            .modified_after
+            .is_some_and(|after| modified <= after)
+            || filters
+                .modified_before
+                .is_some_and(|before| modified >= before)
+        {
+            return Ok(Selection::Exclude);
+        }
+    }
+
+    if let Some(content) = filters.content {
+        let is_binary = is_binary(path)?;
+        if is_binary != (content == model::Content::Binary) {
+            return Ok(Selection::Exclude);
+        }
+    }
+
+    Ok(Selection::Include)
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub enum Selection {
+    Include,
+    Exclude,
+    Oversized(u64),
+}
+
+// Like Git, a file is taken as binary if its beginning contains a null byte.
+fn is_binary(path: &path::Path) -> io::Result<bool> {
+    let mut beginning = vec![];
+    fs::File::open(path)?
+        .take(BINARY_CHECK_LENGTH)
+        .read_to_end(&mut beginning)?;
+    Ok(beginning.contains(&0))
+}
+
+const BINARY_CHECK_LENGTH: u64 = 8000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time;
+
+    #[test]
+    fn handles_no_filters() {
+        let actual = main(
+            &model::MetadataFilters::default(),
+            path::Path::new("/iftree/filter_metadata"),
+            path::Path::new("/iftree/filter_metadata/no_such_file"),
+        );
+
+        let actual = actual.unwrap();
+        let expected = Selection::Include;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_max_depth() {
+        let actual = ["a", "b/a", "b/c/a"].map(|file| {
+            main(
+                &model::MetadataFilters {
+                    max_depth: Some(2),
+                    ..model::MetadataFilters::default()
+                },
+                path::Path::new("/iftree/filter_metadata"),
+                &path::Path::new("/iftree/filter_metadata").join(file),
+            )
+            .unwrap()
+        });
+
+        let expected = [Selection::Include, Selection::Include, Selection::Exclude];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_max_size_it_skips_larger_files() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a"), "abc").unwrap();
+        fs::write(folder.path().join("b"), "abcd").unwrap();
+
+        let actual = ["a", "b"].map(|file| {
+            main(
+                &model::MetadataFilters {
+                    max_size: Some(3),
+                    ..model::MetadataFilters::default()
+                },
+                folder.path(),
+                &folder.path().join(file),
+            )
+            .unwrap()
+        });
+
+        let expected = [Selection::Include, Selection::Oversized(4)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_content() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a"), "abc").unwrap();
+        fs::write(folder.path().join("b"), b"a\0c").unwrap();
+
+        let actual = [model::Content::Binary, model::Content::Text].map(|content| {
+            ["a", "b"].map(|file| {
+                main(
+                    &model::MetadataFilters {
+                        content: Some(content),
+                        ..model::MetadataFilters::default()
+                    },
+                    folder.path(),
+                    &folder.path().join(file),
+                )
+                .unwrap()
+            })
+        });
+
+        let expected = [
+            [Selection::Exclude, Selection::Include],
+            [Selection::Include, Selection::Exclude],
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_modification_time() {
+        let folder = tempfile::tempdir().unwrap();
+        let file = folder.path().join("a");
+        fs::write(&file, "").unwrap();
+        let modified = fs::metadata(&file).unwrap().modified().unwrap();
+        let second = time::Duration::from_secs(1);
+
+        let actual = [
+            (Some(modified - second), None),
+            (Some(modified + second), None),
+            (None, Some(modified + second)),
+            (None, Some(modified - second)),
+        ]
+        .map(|(modified_after, modified_before)| {
+            main(
+                &model::MetadataFilters {
+                    modified_after,
+                    modified_before,
+                    ..model::MetadataFilters::default()
+                },
+                folder.path(),
+                &file,
+            )
+            .unwrap()
+        });
+
+        let expected = [
+            Selection::Include,
+            Selection::Exclude,
+            Selection::Include,
+            Selection::Exclude,
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_no_such_file_it_errs() {
+        let actual = main(
+            &model::MetadataFilters {
+                max_size: Some(1),
+                ..model::MetadataFilters::default()
+            },
+            path::Path::new("/iftree/filter_metadata"),
+            path::Path::new("/iftree/filter_metadata/no_such_file"),
+        );
+
+        let actual = actual.unwrap_err().kind();
+        let expected = io::ErrorKind::NotFound;
+        assert_eq!(actual, expected);
+    }
+}
@@ -3,23 +3,23 @@ use std::env;
 use std::path;
 
 pub fn main(
-    configuration: &model::Configuration,
+    source: &model::Source,
     get_environment_variable: &dyn Fn(&str) -> Result<String, env::VarError>,
 ) -> model::Result<path::PathBuf> {
-    if configuration.base_folder.is_absolute() {
-        Ok(configuration.base_folder.clone())
+    if source.base_folder.is_absolute() {
+        Ok(source.base_folder.clone())
     } else {
-        let mut base_folder = get_root_folder(configuration, get_environment_variable)?;
-        base_folder.push(&configuration.base_folder);
+        let mut base_folder = get_root_folder(source, get_environment_variable)?;
+        base_folder.push(&source.base_folder);
         Ok(base_folder)
     }
 }
 
 fn get_root_folder(
-    configuration: &model::Configuration,
+    source: &model::Source,
     get_environment_variable: &dyn Fn(&str) -> Result<String, env::VarError>,
 ) -> model::Result<path::PathBuf> {
-    let name = &configuration.root_folder_variable;
+    let name = &source.root_folder_variable;
 
     match get_environment_variable(name) {
         Err(source) => Err(model::Error::EnvironmentVariable {
@@ -45,9 +45,9 @@ mod tests {
             assert!(base_folder.is_absolute());
 
             let actual = main(
-                &model::Configuration {
+                &model::Source {
                     base_folder: base_folder.clone(),
-                    ..model::stubs::configuration()
+                    ..model::stubs::source()
                 },
                 &|_| unreachable!(),
             );
@@ -64,10 +64,10 @@ mod tests {
         #[test]
         fn given_environment_variable_it_handles_concatenation() {
             let actual = main(
-                &model::Configuration {
+                &model::Source {
                     base_folder: "b/c".into(),
                     root_folder_variable: "ROOT_FOLDER".into(),
-                    ..model::stubs::configuration()
+                    ..model::stubs::source()
                 },
                 &|name| {
                     Ok((if name == "ROOT_FOLDER" {
@@ -87,10 +87,10 @@ mod tests {
         #[test]
         fn given_no_such_environment_variable_it_errs() {
             let actual = main(
-                &model::Configuration {
+                &model::Source {
                     base_folder: "a/b".into(),
                     root_folder_variable: "ROOT_FOLDER".into(),
-                    ..model::stubs::configuration()
+                    ..model::stubs::source()
                 },
                 &|_| Err(env::VarError::NotPresent),
             );
@@ -3,19 +3,33 @@ use std::path;
 
 pub fn main(
     base_folder: path::PathBuf,
+    source: &model::Source,
     paths: Vec<path::PathBuf>,
 ) -> model::Result<Vec<model::Path>> {
     paths
         .into_iter()
-        .map(|path| get_path(&base_folder, path))
+        .map(|path| get_path(&base_folder, source, path))
         .collect()
 }
 
-fn get_path(base_folder: &path::Path, path: path::PathBuf) -> model::Result<model::Path> {
-    let relative = get_path_components(path.strip_prefix(base_folder)?)?;
+fn get_path(
+    base_folder: &path::Path,
+    source: &model::Source,
+    path: path::PathBuf,
+) -> model::Result<model::Path> {
+    let mut relative = source.mount.clone();
+    relative.extend(get_path_components(path.strip_prefix(base_folder)?)?);
     let absolute = get_path_string(&path)?;
 
-    Ok(model::Path { relative, absolute })
+    Ok(model::Path {
+        relative,
+        absolute,
+        contents: None,
+        size: None,
+        hash: None,
+        fingerprinted_path: None,
+        layer: source.layer.clone(),
+    })
 }
 
 fn get_path_components(path: &path::Path) -> model::Result<Vec<String>> {
@@ -40,19 +54,55 @@ mod tests {
 
     #[test]
     fn handles() {
-        let actual = main("/a/b".into(), vec!["/a/b/c".into(), "/a/b/a/b".into()]);
+        let actual = main(
+            "/a/b".into(),
+            &model::stubs::source(),
+            vec!["/a/b/c".into(), "/a/b/a/b".into()],
+        );
 
         let actual = actual.unwrap();
         let expected = vec![
             model::Path {
                 relative: vec!["c".into()],
                 absolute: "/a/b/c".into(),
+                contents: None,
+                size: None,
+                hash: None,
+                fingerprinted_path: None,
+                layer: None,
             },
             model::Path {
                 relative: vec!["a".into(), "b".into()],
                 absolute: "/a/b/a/b".into(),
+                contents: None,
+                size: None,
+                hash: None,
+                fingerprinted_path: None,
+                layer: None,
             },
         ];
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn handles_mount_and_layer() {
+        let actual = main(
+            "/a".into(),
+            &model::Source {
+                mount: vec!["b".into(), "c".into()],
+                layer: Some("e".into()),
+                ..model::stubs::source()
+            },
+            vec!["/a/d".into()],
+        );
+
+        let actual = actual.unwrap();
+        let expected = vec![model::Path {
+            relative: vec!["b".into(), "c".into(), "d".into()],
+            absolute: "/a/d".into(),
+            layer: Some("e".into()),
+            ..model::stubs::path()
+        }];
+        assert_eq!(actual, expected);
+    }
 }
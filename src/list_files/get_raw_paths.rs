@@ -1,60 +1,161 @@
+use super::filter_metadata;
+use super::get_relative_path;
+use super::walk_folder;
+use crate::filter;
 use crate::model;
-use ignore::overrides;
+use std::fs;
+use std::io;
 use std::path;
 
 pub fn main(
     configuration: &model::Configuration,
+    source: &model::Source,
     base_folder: &path::Path,
-) -> model::Result<Vec<path::PathBuf>> {
-    iterate_entries(configuration, base_folder)?
-        .filter_map(|entry| match entry {
-            Err(error) => Some(Err(error.into())),
-            Ok(entry) => match entry.metadata() {
-                Err(error) => Some(Err(error.into())),
-                Ok(metadata) => {
-                    if metadata.is_dir() {
-                        None
-                    } else {
-                        Some(Ok(entry.into_path()))
+) -> model::Result<(Vec<path::PathBuf>, Vec<model::SkippedFile>)> {
+    let filter = filter::Filter::new(&source.paths, &configuration.pattern_syntax)?;
+    let options = walk_folder::Options {
+        standard_filters: configuration.standard_filters,
+        symlinks: configuration.symlinks,
+        pruning: get_pruning(configuration, source),
+    };
+    let entries = walk_folder::main(base_folder, &options);
+
+    let mut paths = vec![];
+    let mut skipped_files = vec![];
+    for entry in entries.iter() {
+        let relative_path = entry
+            .get_path()
+            .and_then(|path| get_relative_path::main(base_folder, path));
+        let is_match = || {
+            relative_path
+                .as_ref()
+                .is_some_and(|relative_path| filter.is_match(relative_path))
+        };
+        let is_included_folder = || {
+            relative_path
+                .as_ref()
+                .is_some_and(|relative_path| !filter.is_excluded_folder(relative_path))
+        };
+
+        match entry {
+            walk_folder::Entry::File(_) if !is_match() => {}
+
+            walk_folder::Entry::File(path) => match select_file(configuration, base_folder, path) {
+                Ok(filter_metadata::Selection::Include) => paths.push(path.clone()),
+                Ok(filter_metadata::Selection::Exclude) => {}
+                Ok(filter_metadata::Selection::Oversized(size)) => {
+                    let max_size = configuration.metadata_filters.max_size.unwrap_or_default();
+                    match configuration.oversized_files {
+                        model::OversizedFiles::Fail => {
+                            return Err(model::Error::FileTooLarge {
+                                path: path.clone(),
+                                size,
+                                max_size,
+                            });
+                        }
+                        model::OversizedFiles::Skip => {
+                            let reason =
+                                format!("size of {size} bytes exceeds maximum of {max_size} bytes");
+                            skipped_files.push(get_skipped_file(path, reason));
+                        }
                     }
                 }
+                Err(error) => match configuration.unreadable_files {
+                    model::UnreadableFiles::Fail => {
+                        return Err(model::Error::FileRead {
+                            path: path.clone(),
+                            source: model::IoError(error),
+                        });
+                    }
+                    model::UnreadableFiles::Skip => {
+                        skipped_files.push(get_skipped_file(path, error.to_string()));
+                    }
+                },
             },
-        })
-        .collect()
+
+            walk_folder::Entry::SpecialFile(_) if !is_match() => {}
+
+            walk_folder::Entry::SpecialFile(path) => {
+                if configuration.special_files == model::SpecialFiles::Skip {
+                    skipped_files.push(get_skipped_file(path, "special file".into()));
+                } else {
+                    return Err(model::Error::SpecialFile(path.clone()));
+                }
+            }
+
+            walk_folder::Entry::EscapingSymlink {
+                path,
+                target,
+                is_folder,
+            } => {
+                let is_included = if *is_folder {
+                    is_included_folder()
+                } else {
+                    is_match()
+                };
+                if is_included {
+                    return Err(model::Error::SymlinkEscapesBaseFolder {
+                        path: path.clone(),
+                        target: target.clone(),
+                    });
+                }
+            }
+
+            // An error only matters for a selected file, for the base folder, or
+            // for a folder that may contain selected files, unless it cannot be
+            // told where it occurred.
+            walk_folder::Entry::Error {
+                path: Some(path), ..
+            } if !(is_match() || path == base_folder || path.is_dir() && is_included_folder()) => {}
+
+            walk_folder::Entry::Error { path, message } => {
+                match (configuration.unreadable_files, path) {
+                    (model::UnreadableFiles::Skip, Some(path)) => {
+                        skipped_files.push(get_skipped_file(path, message.clone()));
+                    }
+                    _ => return Err(ignore::Error::Io(io::Error::other(message.clone())).into()),
+                }
+            }
+        }
+    }
+
+    Ok((paths, skipped_files))
 }
 
-fn iterate_entries(
+// Only a negated pattern can exclude a folder as a whole.
+fn get_pruning(
     configuration: &model::Configuration,
-    base_folder: &path::Path,
-) -> model::Result<ignore::Walk> {
-    let filter = get_filter(configuration, base_folder)?;
-    Ok(ignore::WalkBuilder::new(base_folder)
-        .standard_filters(false)
-        .overrides(filter)
-        .build())
+    source: &model::Source,
+) -> Option<walk_folder::Pruning> {
+    (matches!(
+        configuration.pattern_syntax.language,
+        model::PatternLanguage::Gitignore | model::PatternLanguage::GitignoreFaithful
+    ) && source.paths.lines().any(|pattern| pattern.starts_with('!')))
+    .then(|| walk_folder::Pruning {
+        patterns: source.paths.clone(),
+        pattern_syntax: configuration.pattern_syntax,
+    })
 }
 
-fn get_filter(
+fn select_file(
     configuration: &model::Configuration,
     base_folder: &path::Path,
-) -> model::Result<overrides::Override> {
-    let mut builder = overrides::OverrideBuilder::new(base_folder);
-    for pattern in configuration.paths.lines() {
-        builder.add(pattern)?;
-    }
-    let filter = builder.build()?;
-
-    if filter.is_empty() {
-        ignore_everything(base_folder)
-    } else {
-        Ok(filter)
+    path: &path::Path,
+) -> io::Result<filter_metadata::Selection> {
+    let selection = filter_metadata::main(&configuration.metadata_filters, base_folder, path)?;
+    if selection == filter_metadata::Selection::Include
+        && configuration.unreadable_files == model::UnreadableFiles::Skip
+    {
+        fs::File::open(path)?;
     }
+    Ok(selection)
 }
 
-fn ignore_everything(base_folder: &path::Path) -> model::Result<overrides::Override> {
-    Ok(overrides::OverrideBuilder::new(base_folder)
-        .add("!*")?
-        .build()?)
+fn get_skipped_file(path: &path::Path, reason: String) -> model::SkippedFile {
+    model::SkippedFile {
+        path: path.to_string_lossy().into(),
+        reason,
+    }
 }
 
 #[cfg(test)]
@@ -64,28 +165,30 @@ mod tests {
     #[test]
     fn handles_empty_paths() {
         let actual = main(
-            &model::Configuration {
+            &model::stubs::configuration(),
+            &model::Source {
                 paths: String::new(),
-                ..model::stubs::configuration()
+                ..model::stubs::source()
             },
             path::Path::new("."),
         );
 
-        let actual = actual.unwrap();
+        let (actual, _) = actual.unwrap();
         assert!(actual.is_empty());
     }
 
     #[test]
     fn handles_single_path() {
         let actual = main(
-            &model::Configuration {
+            &model::stubs::configuration(),
+            &model::Source {
                 paths: "/examples/assets/**".into(),
-                ..model::stubs::configuration()
+                ..model::stubs::source()
             },
             path::Path::new("."),
         );
 
-        let mut actual = actual.unwrap();
+        let (mut actual, _) = actual.unwrap();
         actual.sort();
         let expected = Vec::<path::PathBuf>::from([
             "./examples/assets/.env".into(),
@@ -101,16 +204,17 @@ mod tests {
     #[test]
     fn handles_multiple_paths() {
         let actual = main(
-            &model::Configuration {
+            &model::stubs::configuration(),
+            &model::Source {
                 paths: "/examples/assets/configuration/**
 /examples/assets/world/**"
                     .into(),
-                ..model::stubs::configuration()
+                ..model::stubs::source()
             },
             path::Path::new("."),
         );
 
-        let mut actual = actual.unwrap();
+        let (mut actual, _) = actual.unwrap();
         actual.sort();
         let expected = Vec::<path::PathBuf>::from([
             "./examples/assets/configuration/menu.json".into(),
@@ -124,16 +228,17 @@ mod tests {
     #[test]
     fn handles_negated_patterns() {
         let actual = main(
-            &model::Configuration {
+            &model::stubs::configuration(),
+            &model::Source {
                 paths: "/examples/assets/**/*.json
 !/examples/assets/world/levels/"
                     .into(),
-                ..model::stubs::configuration()
+                ..model::stubs::source()
             },
             path::Path::new("."),
         );
 
-        let mut actual = actual.unwrap();
+        let (mut actual, _) = actual.unwrap();
         actual.sort();
         let expected = Vec::<path::PathBuf>::from([
             "./examples/assets/configuration/menu.json".into(),
@@ -145,18 +250,299 @@ mod tests {
     #[test]
     fn handles_without_hidden_files() {
         let actual = main(
-            &model::Configuration {
+            &model::stubs::configuration(),
+            &model::Source {
                 paths: "/examples/assets/*
 !.*"
                 .into(),
-                ..model::stubs::configuration()
+                ..model::stubs::source()
             },
             path::Path::new("."),
         );
 
-        let mut actual = actual.unwrap();
+        let (mut actual, _) = actual.unwrap();
         actual.sort();
         let expected = Vec::<path::PathBuf>::from(["./examples/assets/credits.md".into()]);
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn given_only_negated_patterns_it_selects_nothing() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a"), "").unwrap();
+        fs::write(folder.path().join("b.tmp"), "").unwrap();
+
+        let actual = main(
+            &model::stubs::configuration(),
+            &model::Source {
+                paths: "!*.tmp".into(),
+                ..model::stubs::source()
+            },
+            folder.path(),
+        );
+
+        let actual = actual.unwrap();
+        let expected = (vec![], vec![]);
+        assert_eq!(actual, expected);
+    }
+
+    mod given_faithful_gitignore_syntax {
+        use super::*;
+
+        fn get_paths(patterns: &str) -> Vec<path::PathBuf> {
+            let actual = main(
+                &model::Configuration {
+                    pattern_syntax: model::PatternSyntax {
+                        language: model::PatternLanguage::GitignoreFaithful,
+                        case_insensitive: false,
+                    },
+                    ..model::stubs::configuration()
+                },
+                &model::Source {
+                    paths: patterns.into(),
+                    ..model::stubs::source()
+                },
+                path::Path::new("."),
+            );
+
+            let (mut actual, _) = actual.unwrap();
+            actual.sort();
+            actual
+        }
+
+        #[test]
+        fn handles_empty_paths() {
+            let actual = get_paths("");
+
+            assert!(actual.is_empty());
+        }
+
+        #[test]
+        fn handles_single_path() {
+            let actual = get_paths("/examples/assets/");
+
+            let expected = Vec::<path::PathBuf>::from([
+                "./examples/assets/.env".into(),
+                "./examples/assets/configuration/menu.json".into(),
+                "./examples/assets/configuration/translations.csv".into(),
+                "./examples/assets/credits.md".into(),
+                "./examples/assets/world/levels/tutorial.json".into(),
+                "./examples/assets/world/physical_constants.json".into(),
+            ]);
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_multiple_paths() {
+            let actual = get_paths(
+                "/examples/assets/configuration/
+/examples/assets/world/",
+            );
+
+            let expected = Vec::<path::PathBuf>::from([
+                "./examples/assets/configuration/menu.json".into(),
+                "./examples/assets/configuration/translations.csv".into(),
+                "./examples/assets/world/levels/tutorial.json".into(),
+                "./examples/assets/world/physical_constants.json".into(),
+            ]);
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_negated_patterns() {
+            let actual = get_paths(
+                "/examples/assets/
+!/examples/assets/world/levels/
+!/examples/assets/*.*",
+            );
+
+            let expected = Vec::<path::PathBuf>::from([
+                "./examples/assets/configuration/menu.json".into(),
+                "./examples/assets/configuration/translations.csv".into(),
+                "./examples/assets/world/physical_constants.json".into(),
+            ]);
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_negated_folders_without_reinclusion() {
+            let actual = get_paths(
+                "/examples/assets/world/
+!/examples/assets/world/levels/
+/examples/assets/world/levels/tutorial.json",
+            );
+
+            let expected = Vec::<path::PathBuf>::from([
+                "./examples/assets/world/physical_constants.json".into(),
+            ]);
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_without_hidden_files() {
+            let actual = get_paths(
+                "/examples/assets/
+!.*",
+            );
+
+            let expected = Vec::<path::PathBuf>::from([
+                "./examples/assets/configuration/menu.json".into(),
+                "./examples/assets/configuration/translations.csv".into(),
+                "./examples/assets/credits.md".into(),
+                "./examples/assets/world/levels/tutorial.json".into(),
+                "./examples/assets/world/physical_constants.json".into(),
+            ]);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn given_oversized_file_it_handles_policy() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a"), "a").unwrap();
+        fs::write(folder.path().join("b"), "bb").unwrap();
+        let source = model::Source {
+            paths: "/*".into(),
+            ..model::stubs::source()
+        };
+
+        let actual =
+            [model::OversizedFiles::Fail, model::OversizedFiles::Skip].map(|oversized_files| {
+                main(
+                    &model::Configuration {
+                        metadata_filters: model::MetadataFilters {
+                            max_size: Some(1),
+                            ..model::MetadataFilters::default()
+                        },
+                        oversized_files,
+                        ..model::stubs::configuration()
+                    },
+                    &source,
+                    folder.path(),
+                )
+            });
+
+        let expected = [
+            Err(model::Error::FileTooLarge {
+                path: folder.path().join("b"),
+                size: 2,
+                max_size: 1,
+            }),
+            Ok((
+                vec![folder.path().join("a")],
+                vec![model::SkippedFile {
+                    path: folder.path().join("b").to_string_lossy().into(),
+                    reason: "size of 2 bytes exceeds maximum of 1 bytes".into(),
+                }],
+            )),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn given_special_file_it_handles_policy() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a"), "").unwrap();
+        let _socket = std::os::unix::net::UnixListener::bind(folder.path().join("b")).unwrap();
+        let source = model::Source {
+            paths: "/*".into(),
+            ..model::stubs::source()
+        };
+
+        let actual =
+            [model::SpecialFiles::Reject, model::SpecialFiles::Skip].map(|special_files| {
+                main(
+                    &model::Configuration {
+                        special_files,
+                        ..model::stubs::configuration()
+                    },
+                    &source,
+                    folder.path(),
+                )
+            });
+
+        let expected = [
+            Err(model::Error::SpecialFile(folder.path().join("b"))),
+            Ok((
+                vec![folder.path().join("a")],
+                vec![model::SkippedFile {
+                    path: folder.path().join("b").to_string_lossy().into(),
+                    reason: "special file".into(),
+                }],
+            )),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_no_such_base_folder_it_errs() {
+        let actual = main(
+            &model::stubs::configuration(),
+            &model::Source {
+                paths: "/*".into(),
+                ..model::stubs::source()
+            },
+            path::Path::new("/iftree/no_such_folder"),
+        );
+
+        let actual = actual.is_err();
+        assert!(actual);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn given_dangling_symlink_it_handles_only_if_selected() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a"), "").unwrap();
+        std::os::unix::fs::symlink(folder.path().join("c"), folder.path().join("b")).unwrap();
+
+        let actual = ["/a", "/*"].map(|paths| {
+            main(
+                &model::Configuration {
+                    symlinks: model::Symlinks::Keep,
+                    ..model::stubs::configuration()
+                },
+                &model::Source {
+                    paths: paths.into(),
+                    ..model::stubs::source()
+                },
+                folder.path(),
+            )
+        });
+
+        let expected = [
+            Ok((vec![folder.path().join("a")], vec![])),
+            Err(model::Error::SpecialFile(folder.path().join("b"))),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn given_escaping_symlink_it_errs() {
+        let folder = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        fs::write(outside.path().join("b"), "").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("b"), folder.path().join("a")).unwrap();
+
+        let actual = main(
+            &model::Configuration {
+                symlinks: model::Symlinks::FollowWithinBaseFolder,
+                ..model::stubs::configuration()
+            },
+            &model::Source {
+                paths: "/*".into(),
+                ..model::stubs::source()
+            },
+            folder.path(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::SymlinkEscapesBaseFolder {
+            path: folder.path().join("a"),
+            target: fs::canonicalize(outside.path().join("b")).unwrap(),
+        };
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,33 @@
+use std::path;
+
+pub fn main(base_folder: &path::Path, path: &path::Path) -> Option<String> {
+    let relative_path = path.strip_prefix(base_folder).ok()?;
+    Some(
+        relative_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let actual = main(path::Path::new("/a"), path::Path::new("/a/b/c"));
+
+        let expected = Some("b/c".into());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_path_outside_of_base_folder_it_handles() {
+        let actual = main(path::Path::new("/a"), path::Path::new("/b/c"));
+
+        let expected = None;
+        assert_eq!(actual, expected);
+    }
+}
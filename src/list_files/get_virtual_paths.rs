@@ -0,0 +1,94 @@
+use crate::model;
+use crate::transform_files;
+
+pub fn main(
+    virtual_files: &[model::VirtualFile],
+    get_variable: &dyn Fn(&str) -> Option<String>,
+) -> model::Result<Vec<model::Path>> {
+    virtual_files
+        .iter()
+        .map(|file| {
+            let contents = if file.substitute_variables {
+                transform_files::substitute_variables(file.contents.as_bytes(), get_variable)
+                    .map_err(|name| model::Error::UndefinedVariable {
+                        name,
+                        path: file.path.join("/").into(),
+                    })?
+            } else {
+                file.contents.as_bytes().to_vec()
+            };
+
+            // Without a file on disk, the absolute path stays empty and the
+            // contents count as transformed.
+            Ok(model::Path {
+                relative: file.path.clone(),
+                absolute: String::new(),
+                contents: Some(contents),
+                size: None,
+                hash: None,
+                fingerprinted_path: None,
+                layer: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let actual = main(
+            &[
+                model::VirtualFile {
+                    path: vec!["a".into(), "b.txt".into()],
+                    contents: "${x} ${y}".into(),
+                    substitute_variables: false,
+                },
+                model::VirtualFile {
+                    path: vec!["c.txt".into()],
+                    contents: "${x}".into(),
+                    substitute_variables: true,
+                },
+            ],
+            &|name| (name == "x").then(|| "1".into()),
+        );
+
+        let actual = actual.unwrap();
+        let expected = vec![
+            model::Path {
+                relative: vec!["a".into(), "b.txt".into()],
+                absolute: String::new(),
+                contents: Some(b"${x} ${y}".to_vec()),
+                ..model::stubs::path()
+            },
+            model::Path {
+                relative: vec!["c.txt".into()],
+                absolute: String::new(),
+                contents: Some(b"1".to_vec()),
+                ..model::stubs::path()
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_undefined_variable_it_errs() {
+        let actual = main(
+            &[model::VirtualFile {
+                path: vec!["a".into(), "b.txt".into()],
+                contents: "${x}".into(),
+                substitute_variables: true,
+            }],
+            &|_| None,
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::UndefinedVariable {
+            name: "x".into(),
+            path: "a/b.txt".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
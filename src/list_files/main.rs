@@ -1,13 +1,50 @@
+use super::check_collisions;
+use super::check_expectations;
+use super::check_pattern_matches;
+use super::drop_shadowed_paths;
 use super::get_base_folder;
 use super::get_paths;
 use super::get_raw_paths;
+use super::get_virtual_paths;
+use super::rewrite_paths;
+use crate::layers;
 use crate::model;
 use std::env;
 
-pub fn main(configuration: &model::Configuration) -> model::Result<Vec<model::Path>> {
-    let base_folder = get_base_folder::main(configuration, &|name| env::var(name))?;
-    let paths = get_raw_paths::main(configuration, &base_folder)?;
-    get_paths::main(base_folder, paths)
+pub fn main(
+    configuration: &model::Configuration,
+    get_variable: &dyn Fn(&str) -> Option<String>,
+) -> model::Result<(Vec<model::Path>, Vec<model::SkippedFile>)> {
+    let mut paths = vec![];
+    let mut skipped_files = vec![];
+    for source in &configuration.sources {
+        let base_folder = get_base_folder::main(source, &|name| env::var(name))?;
+        let (source_paths, source_skipped_files) =
+            get_raw_paths::main(configuration, source, &base_folder)?;
+        if configuration.require_pattern_matches {
+            check_pattern_matches::main(
+                source,
+                &configuration.pattern_syntax,
+                &base_folder,
+                &source_paths,
+            )?;
+        }
+        paths.extend(get_paths::main(base_folder, source, source_paths)?);
+        skipped_files.extend(source_skipped_files);
+    }
+    paths.extend(get_virtual_paths::main(
+        &configuration.virtual_files,
+        get_variable,
+    )?);
+    let paths = rewrite_paths::main(configuration, paths)?;
+    check_collisions::main(&paths)?;
+    check_expectations::main(configuration, &paths)?;
+    let paths = if configuration.runtime_layers {
+        paths
+    } else {
+        drop_shadowed_paths::main(paths, &layers::get_names(&configuration.sources))
+    };
+    Ok((paths, skipped_files))
 }
 
 #[cfg(test)]
@@ -18,14 +55,22 @@ mod tests {
 
     #[test]
     fn handles() {
-        let actual = main(&model::Configuration {
-            paths: "/assets/*.md".into(),
-            base_folder: "examples".into(),
-            root_folder_variable: "CARGO_MANIFEST_DIR".into(),
-            ..model::stubs::configuration()
-        });
+        let actual = main(
+            &model::Configuration {
+                sources: vec![model::Source {
+                    paths: "/assets/*.md".into(),
+                    base_folder: "examples".into(),
+                    root_folder_variable: "CARGO_MANIFEST_DIR".into(),
+                    mount: vec![],
+                    layer: None,
+                    cfg: None,
+                }],
+                ..model::stubs::configuration()
+            },
+            &|_| None,
+        );
 
-        let actual = actual.unwrap();
+        let (actual, _) = actual.unwrap();
         let expected = vec![model::Path {
             relative: vec!["assets".into(), "credits.md".into()],
             absolute: path::PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
@@ -35,6 +80,11 @@ mod tests {
                 .into_os_string()
                 .into_string()
                 .unwrap(),
+            contents: None,
+            size: None,
+            hash: None,
+            fingerprinted_path: None,
+            layer: None,
         }];
         assert_eq!(actual, expected);
     }
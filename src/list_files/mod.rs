@@ -1,6 +1,15 @@
+mod check_collisions;
+mod check_expectations;
+mod check_pattern_matches;
+mod drop_shadowed_paths;
+mod filter_metadata;
 mod get_base_folder;
 mod get_paths;
 mod get_raw_paths;
+mod get_relative_path;
+mod get_virtual_paths;
 mod main;
+mod rewrite_paths;
+mod walk_folder;
 
 pub use main::main;
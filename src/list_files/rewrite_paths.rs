@@ -0,0 +1,219 @@
+use crate::filter;
+use crate::model;
+
+pub fn main(
+    configuration: &model::Configuration,
+    mut paths: Vec<model::Path>,
+) -> model::Result<Vec<model::Path>> {
+    for rewrite in &configuration.rewrites {
+        let filter = rewrite
+            .paths
+            .as_ref()
+            .map(|patterns| filter::Filter::new(patterns, &configuration.pattern_syntax))
+            .transpose()?;
+        let regex = rewrite
+            .rename
+            .as_ref()
+            .map(|rename| {
+                regex::Regex::new(&rename.regex).map_err(|error| model::Error::InvalidPattern {
+                    pattern: rename.regex.clone(),
+                    message: error.to_string(),
+                })
+            })
+            .transpose()?;
+
+        for path in &mut paths {
+            let relative_path = path.relative.join(FOLDER_SEPARATOR);
+            if filter
+                .as_ref()
+                .is_none_or(|filter| filter.is_match(&relative_path))
+            {
+                path.relative = rewrite_path(rewrite, regex.as_ref(), &path.relative);
+                if path.relative.is_empty() {
+                    return Err(model::Error::EmptyRelativePath(
+                        path.absolute.clone().into(),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+const FOLDER_SEPARATOR: &str = "/";
+
+// Steps apply in a fixed order, so a rename or an extension mapping sees the
+// path without the stripped prefix but before the added one.
+fn rewrite_path(
+    rewrite: &model::Rewrite,
+    regex: Option<&regex::Regex>,
+    relative: &[String],
+) -> Vec<String> {
+    let mut relative = relative
+        .strip_prefix(&rewrite.strip_prefix[..])
+        .unwrap_or(relative)
+        .to_vec();
+
+    if let (Some(regex), Some(rename)) = (regex, &rewrite.rename) {
+        relative =
+            get_components(&regex.replace(&relative.join(FOLDER_SEPARATOR), &rename.replacement));
+    }
+
+    if let Some(name) = relative.last_mut()
+        && let Some((stem, extension)) = name.rsplit_once('.')
+        && let Some(new_extension) = rewrite.extensions.get(extension)
+    {
+        *name = if new_extension.is_empty() {
+            stem.into()
+        } else {
+            format!("{stem}.{new_extension}")
+        };
+    }
+
+    rewrite.add_prefix.iter().cloned().chain(relative).collect()
+}
+
+fn get_components(path: &str) -> Vec<String> {
+    path.split(FOLDER_SEPARATOR)
+        .filter(|component| !component.is_empty())
+        .map(|component| component.into())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_paths(relative_paths: &[&str]) -> Vec<model::Path> {
+        relative_paths
+            .iter()
+            .map(|relative_path| model::Path {
+                relative: get_components(relative_path),
+                absolute: format!("/{relative_path}"),
+                ..model::stubs::path()
+            })
+            .collect()
+    }
+
+    fn get_relative_paths(paths: &[model::Path]) -> Vec<String> {
+        paths
+            .iter()
+            .map(|path| path.relative.join(FOLDER_SEPARATOR))
+            .collect()
+    }
+
+    #[test]
+    fn handles_no_rewrites() {
+        let paths = get_paths(&["a/b", "c"]);
+
+        let actual = main(&model::stubs::configuration(), paths.clone());
+
+        let actual = actual.unwrap();
+        let expected = paths;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_prefixes() {
+        let actual = main(
+            &model::Configuration {
+                rewrites: vec![model::Rewrite {
+                    paths: None,
+                    strip_prefix: vec!["frontend".into(), "dist".into()],
+                    rename: None,
+                    extensions: [].into(),
+                    add_prefix: vec!["static".into()],
+                }],
+                ..model::stubs::configuration()
+            },
+            get_paths(&["frontend/dist/app.js", "frontend/main.js"]),
+        );
+
+        let actual = get_relative_paths(&actual.unwrap());
+        let expected = ["static/app.js", "static/frontend/main.js"];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_rename_and_extensions() {
+        let actual = main(
+            &model::Configuration {
+                rewrites: vec![model::Rewrite {
+                    paths: Some("*.htm\n*.md".into()),
+                    strip_prefix: vec![],
+                    rename: Some(model::Rename {
+                        regex: "^pages/(.*)$".into(),
+                        replacement: "$1".into(),
+                    }),
+                    extensions: [("htm".into(), "html".into()), ("md".into(), String::new())]
+                        .into(),
+                    add_prefix: vec![],
+                }],
+                ..model::stubs::configuration()
+            },
+            get_paths(&["pages/a/b.htm", "pages/c.md", "pages/d.txt"]),
+        );
+
+        let actual = get_relative_paths(&actual.unwrap());
+        let expected = ["a/b.html", "c", "pages/d.txt"];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_rewrites_in_order() {
+        let rewrite = model::Rewrite {
+            paths: None,
+            strip_prefix: vec![],
+            rename: None,
+            extensions: [].into(),
+            add_prefix: vec![],
+        };
+
+        let actual = main(
+            &model::Configuration {
+                rewrites: vec![
+                    model::Rewrite {
+                        add_prefix: vec!["b".into()],
+                        ..rewrite.clone()
+                    },
+                    model::Rewrite {
+                        paths: Some("/b/**".into()),
+                        add_prefix: vec!["c".into()],
+                        ..rewrite
+                    },
+                ],
+                ..model::stubs::configuration()
+            },
+            get_paths(&["a"]),
+        );
+
+        let actual = get_relative_paths(&actual.unwrap());
+        let expected = ["c/b/a"];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_empty_rewritten_path_it_errs() {
+        let actual = main(
+            &model::Configuration {
+                rewrites: vec![model::Rewrite {
+                    paths: None,
+                    strip_prefix: vec![],
+                    rename: Some(model::Rename {
+                        regex: ".*".into(),
+                        replacement: String::new(),
+                    }),
+                    extensions: [].into(),
+                    add_prefix: vec![],
+                }],
+                ..model::stubs::configuration()
+            },
+            get_paths(&["a"]),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::EmptyRelativePath("/a".into());
+        assert_eq!(actual, expected);
+    }
+}
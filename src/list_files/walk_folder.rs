@@ -0,0 +1,455 @@
+use super::get_relative_path;
+use crate::filter;
+use crate::model;
+use std::cmp;
+use std::fs;
+use std::path;
+use std::sync;
+
+pub fn main(base_folder: &path::Path, options: &Options) -> Vec<Entry> {
+    let entries = sync::Mutex::new(vec![]);
+    let canonical_base_folder = fs::canonicalize(base_folder).ok();
+    let context = Context {
+        symlinks: options.symlinks,
+        canonical_base_folder: canonical_base_folder.as_deref(),
+    };
+    let standard_filters = &options.standard_filters;
+
+    let mut builder = ignore::WalkBuilder::new(base_folder);
+    builder
+        .standard_filters(false)
+        // Ignore files above the base folder apply, too, like a `.gitignore`
+        // at the root of the repository.
+        .parents(
+            standard_filters.gitignore
+                || standard_filters.git_exclude
+                || standard_filters.git_global
+                || standard_filters.ignore,
+        )
+        .git_ignore(standard_filters.gitignore)
+        .git_exclude(standard_filters.git_exclude)
+        .git_global(standard_filters.git_global)
+        .ignore(standard_filters.ignore)
+        .hidden(standard_filters.hidden)
+        .add_custom_ignore_filename(IGNORE_FILENAME)
+        // Packaged crates are no Git repositories, yet should build the same.
+        .require_git(false)
+        // Following links detects loops, which are reported as errors.
+        .follow_links(options.symlinks != model::Symlinks::Keep);
+
+    // The patterns have been checked before, so they are valid here.
+    if let Some(filter) = options
+        .pruning
+        .as_ref()
+        .and_then(|pruning| filter::Filter::new(&pruning.patterns, &pruning.pattern_syntax).ok())
+    {
+        let base_folder = base_folder.to_path_buf();
+        builder.filter_entry(move |entry| !is_pruned(&filter, &base_folder, entry));
+    }
+
+    builder.build_parallel().run(|| {
+        Box::new(|entry| {
+            let (entry, state) = get_entry(&context, entry);
+            if let Some(entry) = entry {
+                entries.lock().unwrap().push(entry);
+            }
+            state
+        })
+    });
+
+    // Threads finish in any order, so sorting keeps the result deterministic.
+    let mut entries = entries.into_inner().unwrap();
+    entries.sort_by(|left, right| left.get_path().cmp(&right.get_path()));
+    entries
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Options {
+    pub standard_filters: model::StandardFilters,
+    pub symlinks: model::Symlinks,
+    pub pruning: Option<Pruning>,
+}
+
+// Path patterns that may exclude folders as a whole, which are then not
+// descended into.
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Pruning {
+    pub patterns: String,
+    pub pattern_syntax: model::PatternSyntax,
+}
+
+const IGNORE_FILENAME: &str = ".iftreeignore";
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub enum Entry {
+    File(path::PathBuf),
+    SpecialFile(path::PathBuf),
+    EscapingSymlink {
+        path: path::PathBuf,
+        target: path::PathBuf,
+        is_folder: bool,
+    },
+    Error {
+        path: Option<path::PathBuf>,
+        message: String,
+    },
+}
+
+impl Entry {
+    pub fn get_path(&self) -> Option<&path::Path> {
+        match self {
+            Entry::File(path) | Entry::SpecialFile(path) => Some(path),
+            Entry::EscapingSymlink { path, .. } => Some(path),
+            Entry::Error { path, .. } => path.as_deref(),
+        }
+    }
+}
+
+struct Context<'a> {
+    symlinks: model::Symlinks,
+    canonical_base_folder: Option<&'a path::Path>,
+}
+
+fn get_entry(
+    context: &Context,
+    entry: Result<ignore::DirEntry, ignore::Error>,
+) -> (Option<Entry>, ignore::WalkState) {
+    let entry = match entry {
+        Err(error) => return (Some(get_error(&error)), ignore::WalkState::Continue),
+        Ok(entry) => entry,
+    };
+
+    if let (model::Symlinks::FollowWithinBaseFolder, Some(base_folder)) =
+        (context.symlinks, context.canonical_base_folder)
+        && entry.path_is_symlink()
+        && let Ok(target) = fs::canonicalize(entry.path())
+        && !target.starts_with(base_folder)
+    {
+        // Not descending into such a folder keeps its files out.
+        let entry = Entry::EscapingSymlink {
+            path: entry.path().into(),
+            is_folder: target.is_dir(),
+            target,
+        };
+        return (Some(entry), ignore::WalkState::Skip);
+    }
+
+    // Unless links are followed, the entry of a link has the metadata of the
+    // link itself, so the target is looked up here. A dangling link keeps its
+    // own metadata, which makes it a special file.
+    let metadata = if entry.path_is_symlink() && context.symlinks == model::Symlinks::Keep {
+        fs::metadata(entry.path())
+            .or_else(|_| fs::symlink_metadata(entry.path()))
+            .map_err(|error| ignore::Error::WithPath {
+                path: entry.path().into(),
+                err: Box::new(ignore::Error::Io(error)),
+            })
+    } else {
+        entry.metadata()
+    };
+
+    let entry = match metadata {
+        Err(error) => Some(get_error(&error)),
+
+        Ok(metadata) => {
+            // Ignore files configure the walk, so they are no assets themselves.
+            if metadata.is_dir() || entry.file_name() == IGNORE_FILENAME {
+                None
+            } else if metadata.is_file() {
+                Some(Entry::File(entry.into_path()))
+            } else {
+                Some(Entry::SpecialFile(entry.into_path()))
+            }
+        }
+    };
+    (entry, ignore::WalkState::Continue)
+}
+
+fn is_pruned(filter: &filter::Filter, base_folder: &path::Path, entry: &ignore::DirEntry) -> bool {
+    entry.depth() > 0
+        && entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir())
+        && get_relative_path::main(base_folder, entry.path())
+            .is_some_and(|relative_path| filter.is_excluded_folder(&relative_path))
+}
+
+fn get_error(error: &ignore::Error) -> Entry {
+    Entry::Error {
+        path: get_error_path(error).map(|path| path.to_path_buf()),
+        message: error.to_string(),
+    }
+}
+
+fn get_error_path(error: &ignore::Error) -> Option<&path::Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            get_error_path(err)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+pub mod stubs {
+    use super::*;
+
+    pub fn options() -> Options {
+        Options {
+            standard_filters: model::StandardFilters::default(),
+            symlinks: model::Symlinks::Keep,
+            pruning: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn handles() {
+        let folder = tempfile::tempdir().unwrap();
+        for file in ["b/c", "a", "b/a", ".d"] {
+            let file = folder.path().join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "").unwrap();
+        }
+
+        let actual = main(folder.path(), &stubs::options());
+
+        let expected = [".d", "a", "b/a", "b/c"]
+            .into_iter()
+            .map(|file| Entry::File(folder.path().join(file)))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_standard_filters_it_handles() {
+        let folder = tempfile::tempdir().unwrap();
+        for (file, contents) in [
+            (".gitignore", "*.log\n"),
+            (".ignore", "*.tmp\n"),
+            (".d", ""),
+            ("a.log", ""),
+            ("b/c.tmp", ""),
+            ("b/e", ""),
+        ] {
+            let file = folder.path().join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, contents).unwrap();
+        }
+
+        let actual = [
+            model::StandardFilters {
+                gitignore: true,
+                ..model::StandardFilters::default()
+            },
+            model::StandardFilters {
+                ignore: true,
+                hidden: true,
+                ..model::StandardFilters::default()
+            },
+        ]
+        .map(|standard_filters| {
+            main(
+                folder.path(),
+                &Options {
+                    standard_filters,
+                    ..stubs::options()
+                },
+            )
+        });
+
+        let expected = [
+            vec![".d", ".gitignore", ".ignore", "b/c.tmp", "b/e"],
+            vec!["a.log", "b/e"],
+        ]
+        .map(|files| {
+            files
+                .into_iter()
+                .map(|file| Entry::File(folder.path().join(file)))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_standard_filters_it_handles_ignore_files_in_parent_folders() {
+        let folder = tempfile::tempdir().unwrap();
+        for (file, contents) in [(".gitignore", "*.log\n"), ("b/a/c.log", ""), ("b/d", "")] {
+            let file = folder.path().join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, contents).unwrap();
+        }
+
+        let actual = main(
+            &folder.path().join("b"),
+            &Options {
+                standard_filters: model::StandardFilters {
+                    gitignore: true,
+                    ..model::StandardFilters::default()
+                },
+                ..stubs::options()
+            },
+        );
+
+        let expected = vec![Entry::File(folder.path().join("b/d"))];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_iftreeignore_files_it_handles_them_per_folder() {
+        let folder = tempfile::tempdir().unwrap();
+        for (file, contents) in [
+            (".iftreeignore", "/a\n"),
+            ("a", ""),
+            ("b/.iftreeignore", "*.tmp\n!/keep.tmp\n"),
+            ("b/a", ""),
+            ("b/c.tmp", ""),
+            ("b/keep.tmp", ""),
+            ("d/c.tmp", ""),
+        ] {
+            let file = folder.path().join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, contents).unwrap();
+        }
+
+        let actual = main(folder.path(), &stubs::options());
+
+        let expected = ["b/a", "b/keep.tmp", "d/c.tmp"]
+            .into_iter()
+            .map(|file| Entry::File(folder.path().join(file)))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_pruning_it_skips_excluded_folders() {
+        let folder = tempfile::tempdir().unwrap();
+        for file in ["a/b", "c/d", "c/e/f", "g"] {
+            let file = folder.path().join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "").unwrap();
+        }
+
+        let actual = main(
+            folder.path(),
+            &Options {
+                pruning: Some(Pruning {
+                    patterns: "/**\n!/c/".into(),
+                    pattern_syntax: model::PatternSyntax::default(),
+                }),
+                ..stubs::options()
+            },
+        );
+
+        let expected = ["a/b", "g"]
+            .into_iter()
+            .map(|file| Entry::File(folder.path().join(file)))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_faithful_pruning_it_skips_excluded_folders() {
+        let folder = tempfile::tempdir().unwrap();
+        for file in ["a/b", "c/d"] {
+            let file = folder.path().join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "").unwrap();
+        }
+
+        let actual = main(
+            folder.path(),
+            &Options {
+                pruning: Some(Pruning {
+                    patterns: "/*\n!/c/".into(),
+                    pattern_syntax: model::PatternSyntax {
+                        language: model::PatternLanguage::GitignoreFaithful,
+                        case_insensitive: false,
+                    },
+                }),
+                ..stubs::options()
+            },
+        );
+
+        let expected = vec![Entry::File(folder.path().join("a/b"))];
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn given_symlinks_it_handles() {
+        let folder = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a"), "").unwrap();
+        fs::write(outside.path().join("c"), "").unwrap();
+        std::os::unix::fs::symlink(folder.path().join("a"), folder.path().join("b")).unwrap();
+        std::os::unix::fs::symlink(outside.path(), folder.path().join("d")).unwrap();
+
+        let actual = [
+            model::Symlinks::Follow,
+            model::Symlinks::FollowWithinBaseFolder,
+            model::Symlinks::Keep,
+        ]
+        .map(|symlinks| {
+            main(
+                folder.path(),
+                &Options {
+                    symlinks,
+                    ..stubs::options()
+                },
+            )
+        });
+
+        let expected = [
+            vec![
+                Entry::File(folder.path().join("a")),
+                Entry::File(folder.path().join("b")),
+                Entry::File(folder.path().join("d/c")),
+            ],
+            vec![
+                Entry::File(folder.path().join("a")),
+                Entry::File(folder.path().join("b")),
+                Entry::EscapingSymlink {
+                    path: folder.path().join("d"),
+                    target: fs::canonicalize(outside.path()).unwrap(),
+                    is_folder: true,
+                },
+            ],
+            vec![
+                Entry::File(folder.path().join("a")),
+                Entry::File(folder.path().join("b")),
+            ],
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn given_special_file_it_keeps_it() {
+        let folder = tempfile::tempdir().unwrap();
+        let _socket = std::os::unix::net::UnixListener::bind(folder.path().join("a")).unwrap();
+
+        let actual = main(folder.path(), &stubs::options());
+
+        let expected = vec![Entry::SpecialFile(folder.path().join("a"))];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_no_such_folder_it_keeps_error() {
+        let actual = main(path::Path::new("/iftree/no_such_folder"), &stubs::options());
+
+        let actual = actual
+            .into_iter()
+            .map(|entry| entry.get_path().map(|path| path.to_path_buf()))
+            .collect::<Vec<_>>();
+        let expected = vec![Some(path::PathBuf::from("/iftree/no_such_folder"))];
+        assert_eq!(actual, expected);
+    }
+}
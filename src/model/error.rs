@@ -1,6 +1,7 @@
 use super::main;
 use std::error;
 use std::fmt;
+use std::io;
 use std::path;
 
 impl PartialEq for main::IgnoreError {
@@ -9,22 +10,149 @@ impl PartialEq for main::IgnoreError {
     }
 }
 
+impl Clone for main::IoError {
+    fn clone(&self) -> Self {
+        main::IoError(io::Error::new(self.0.kind(), self.0.to_string()))
+    }
+}
+
+impl PartialEq for main::IoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.kind() == other.0.kind() && self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl fmt::Display for main::Encoding {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            main::Encoding::Auto => "auto",
+            main::Encoding::Latin1 => "latin-1",
+            main::Encoding::Utf8 => "utf-8",
+            main::Encoding::Utf16Be => "utf-16be",
+            main::Encoding::Utf16Le => "utf-16le",
+        })
+    }
+}
+
 impl fmt::Display for main::Error {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            main::Error::AliasCollision(alias) => write!(
+                formatter,
+                "Alias {alias:?} collides with another file or folder of the same path.",
+            ),
+
+            main::Error::CaptureMismatch {
+                relative_path,
+                regex,
+            } => write!(
+                formatter,
+                "Relative path {relative_path:?} does not match {regex:?} \
+                with a capture for each field. \
+                Exclude the file or adjust the \"captures\" regular expression.",
+            ),
+
+            main::Error::CommandFailed {
+                command,
+                path,
+                message,
+            } => write!(
+                formatter,
+                "Command {command:?} failed for file {path:?}: {message}",
+            ),
+
+            main::Error::CyclicReferences(paths) => write!(
+                formatter,
+                "Unable to fingerprint files with cyclic references: {paths:?}",
+            ),
+
+            main::Error::EmptyRelativePath(path) => write!(
+                formatter,
+                "Rewriting leaves file {path:?} with an empty relative path.",
+            ),
+
             main::Error::EnvironmentVariable { name, source } => write!(
                 formatter,
                 "Unable to get environment variable {name:?}: {source}",
             ),
 
+            main::Error::FileRead { path, source } => {
+                write!(formatter, "Unable to read file {path:?}: {}", source.0)
+            }
+
+            main::Error::FileTooLarge {
+                path,
+                size,
+                max_size,
+            } => write!(
+                formatter,
+                "File {path:?} has {size} bytes, more than the maximum of {max_size}.",
+            ),
+
+            main::Error::FileWrite { path, source } => {
+                write!(formatter, "Unable to write file {path:?}: {}", source.0)
+            }
+
+            main::Error::GroupCollision { names, identifier } => write!(
+                formatter,
+                "Groups {names:?} share the identifier {identifier}. Rename one of them.",
+            ),
+
             main::Error::Ignore(main::IgnoreError(error)) => write!(formatter, "{error}"),
 
+            main::Error::InvalidCapture {
+                relative_path,
+                field,
+                value,
+            } => write!(
+                formatter,
+                "Captured value {value:?} of relative path {relative_path:?} \
+                is not an integer as needed for field {field:?}.",
+            ),
+
+            main::Error::InvalidEncoding { path, encoding } => write!(
+                formatter,
+                "File is not valid in encoding {encoding:?}: {path:?}",
+                encoding = encoding.to_string(),
+            ),
+
+            main::Error::InvalidJson { path, message } => {
+                write!(formatter, "File is not valid JSON ({message}): {path:?}")
+            }
+
+            main::Error::InvalidPattern { pattern, message } => {
+                write!(formatter, "Invalid path pattern {pattern:?}: {message}")
+            }
+
+            main::Error::MissingLockedFile { path, lock_file } => write!(
+                formatter,
+                "File {path:?} of index lock file {lock_file:?} is not among the files. \
+                Remove its line to give up its index, which shifts those of later files.",
+            ),
+
+            main::Error::MissingRequiredFile(path) => write!(
+                formatter,
+                "Required file {path:?} is not among the files. \
+                Check the path patterns or the \"required\" list for typos.",
+            ),
+
+            main::Error::NoFingerprint => formatter.write_str(
+                "Field \"fingerprinted_path\" needs fingerprints. \
+                Configure them with a \"[fingerprint]\" table.",
+            ),
+
             main::Error::NoInitializer => formatter.write_str(
                 "No initializer. \
                 Configure one with \"template.initializer = 'a_macro'\" or \
                 use standard fields to generate a default initializer.",
             ),
 
+            main::Error::NoMainFile(path) => write!(
+                formatter,
+                "Variants of {path:?} have no main file, \
+                which fields like \"contents_str\" need.",
+            ),
+
             main::Error::NonstandardField {
                 field,
                 standard_fields,
@@ -47,6 +175,15 @@ impl fmt::Display for main::Error {
                 )
             }
 
+            main::Error::PathCollision {
+                relative_path,
+                absolute_paths: [first, second],
+            } => write!(
+                formatter,
+                "Relative path {relative_path:?} is taken by both {first:?} and {second:?}. \
+                Consider a different mount for one of their sources.",
+            ),
+
             main::Error::PathInvalidUnicode(path) => write!(
                 formatter,
                 "Path is not valid Unicode, consider renaming it: {path:?}",
@@ -54,6 +191,43 @@ impl fmt::Display for main::Error {
 
             main::Error::PathStripPrefix(error) => write!(formatter, "{error}"),
 
+            main::Error::SpecialFile(path) => write!(
+                formatter,
+                "File is neither a regular file nor a folder: {path:?}. \
+                Exclude it or configure \"special_files = 'skip'\".",
+            ),
+
+            main::Error::SymlinkEscapesBaseFolder { path, target } => write!(
+                formatter,
+                "Symbolic link {path:?} points to {target:?} outside of the base folder.",
+            ),
+
+            main::Error::TooFewFiles { count, min_files } => write!(
+                formatter,
+                "Only {count} files are included, fewer than the minimum of {min_files}.",
+            ),
+
+            main::Error::TooManyCfgPredicates {
+                count,
+                max_predicates,
+            } => write!(
+                formatter,
+                "Sources have {count} distinct `cfg` predicates, more than the maximum of \
+                {max_predicates}, as each combination of them is compiled apart.",
+            ),
+
+            main::Error::TooManyFiles { count, max_files } => write!(
+                formatter,
+                "{count} files are included, more than the maximum of {max_files}.",
+            ),
+
+            main::Error::UndefinedVariable { name, path } => write!(
+                formatter,
+                "Undefined variable {name:?} in file {path:?}. \
+                Use one of \"${{cargo:…}}\", \"${{env:…}}\", \"${{git:commit}}\" or \
+                escape a literal \"${{\" as \"$${{\".",
+            ),
+
             main::Error::UnexpectedEmptyRelativePath { absolute_path } => write!(
                 formatter,
                 "Unexpected empty relative path for absolute path \
@@ -64,6 +238,43 @@ impl fmt::Display for main::Error {
                 formatter,
                 "Unexpected path collision (consider reporting this): {path:?}",
             ),
+
+            main::Error::UnknownAliasTarget { alias, target } => write!(
+                formatter,
+                "Alias {alias:?} refers to {target:?}, which is not among the files.",
+            ),
+
+            main::Error::UnknownOrderCapture(name) => write!(
+                formatter,
+                "Ordering by capture {name:?} needs a capture group of this name \
+                in the \"captures\" regular expression.",
+            ),
+
+            main::Error::UnknownOrderPath(path) => write!(
+                formatter,
+                "File {path:?} of the order list is not among the files.",
+            ),
+
+            main::Error::UnknownOverrideField { paths, field } => write!(
+                formatter,
+                "Override for paths {paths:?} sets field {field:?}, \
+                which the default initializer does not have.",
+            ),
+
+            main::Error::UnlockedFiles { paths, lock_file } => write!(
+                formatter,
+                "Files {paths:?} are missing from index lock file {lock_file:?}. \
+                Append them to it, or build once with the environment variable \
+                IFTREE_UPDATE_LOCKS set to have them appended.",
+            ),
+
+            main::Error::UnmatchedPattern {
+                pattern,
+                base_folder,
+            } => write!(
+                formatter,
+                "Path pattern {pattern:?} matches no files in base folder {base_folder:?}.",
+            ),
         }
     }
 }
@@ -71,14 +282,44 @@ impl fmt::Display for main::Error {
 impl error::Error for main::Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
+            main::Error::AliasCollision(_) => None,
+            main::Error::CaptureMismatch { .. } => None,
+            main::Error::CommandFailed { .. } => None,
+            main::Error::CyclicReferences(_) => None,
+            main::Error::EmptyRelativePath(_) => None,
             main::Error::EnvironmentVariable { source, .. } => Some(source),
+            main::Error::FileRead { source, .. } => Some(&source.0),
+            main::Error::FileTooLarge { .. } => None,
+            main::Error::FileWrite { source, .. } => Some(&source.0),
+            main::Error::GroupCollision { .. } => None,
             main::Error::Ignore(main::IgnoreError(error)) => Some(error),
+            main::Error::InvalidCapture { .. } => None,
+            main::Error::InvalidEncoding { .. } => None,
+            main::Error::InvalidJson { .. } => None,
+            main::Error::InvalidPattern { .. } => None,
+            main::Error::MissingLockedFile { .. } => None,
+            main::Error::MissingRequiredFile(_) => None,
+            main::Error::NoFingerprint => None,
             main::Error::NoInitializer => None,
+            main::Error::NoMainFile(_) => None,
+            main::Error::PathCollision { .. } => None,
             main::Error::NonstandardField { .. } => None,
             main::Error::PathInvalidUnicode(_) => None,
             main::Error::PathStripPrefix(error) => Some(error),
+            main::Error::SpecialFile(_) => None,
+            main::Error::SymlinkEscapesBaseFolder { .. } => None,
+            main::Error::TooFewFiles { .. } => None,
+            main::Error::TooManyCfgPredicates { .. } => None,
+            main::Error::TooManyFiles { .. } => None,
+            main::Error::UndefinedVariable { .. } => None,
             main::Error::UnexpectedEmptyRelativePath { .. } => None,
             main::Error::UnexpectedPathCollision(_) => None,
+            main::Error::UnknownAliasTarget { .. } => None,
+            main::Error::UnknownOrderCapture(_) => None,
+            main::Error::UnknownOrderPath(_) => None,
+            main::Error::UnknownOverrideField { .. } => None,
+            main::Error::UnlockedFiles { .. } => None,
+            main::Error::UnmatchedPattern { .. } => None,
         }
     }
 }
@@ -104,6 +345,59 @@ mod tests {
     mod display {
         use super::*;
 
+        #[test]
+        fn handles_alias_collision() {
+            let actual = main::Error::AliasCollision("a/b".into()).to_string();
+
+            let expected = "Alias \"a/b\" collides with another file or folder of the same path.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_capture_mismatch() {
+            let actual = main::Error::CaptureMismatch {
+                relative_path: "a/b".into(),
+                regex: "^(?<c>[0-9]+)".into(),
+            }
+            .to_string();
+
+            let expected = "Relative path \"a/b\" does not match \"^(?<c>[0-9]+)\" \
+with a capture for each field. \
+Exclude the file or adjust the \"captures\" regular expression.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_command_failed() {
+            let actual = main::Error::CommandFailed {
+                command: vec!["a".into(), "-b".into()],
+                path: "/c".into(),
+                message: "exit status: 1".into(),
+            }
+            .to_string();
+
+            let expected = "Command [\"a\", \"-b\"] failed for file \"/c\": exit status: 1";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_cyclic_references() {
+            let actual =
+                main::Error::CyclicReferences(vec!["/a.css".into(), "/b.css".into()]).to_string();
+
+            let expected = "Unable to fingerprint files with cyclic references: \
+            [\"/a.css\", \"/b.css\"]";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_empty_relative_path() {
+            let actual = main::Error::EmptyRelativePath("/a/b".into()).to_string();
+
+            let expected = "Rewriting leaves file \"/a/b\" with an empty relative path.";
+            assert_eq!(actual, expected);
+        }
+
         #[test]
         fn handles_environment_variable() {
             let actual = main::Error::EnvironmentVariable {
@@ -117,6 +411,59 @@ mod tests {
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn handles_file_read() {
+            let actual = main::Error::FileRead {
+                path: "/a/b".into(),
+                source: main::IoError(io::Error::new(io::ErrorKind::NotFound, "not found")),
+            }
+            .to_string();
+
+            let expected = "Unable to read file \"/a/b\": not found";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_file_too_large() {
+            let actual = main::Error::FileTooLarge {
+                path: "/a/b".into(),
+                size: 3,
+                max_size: 2,
+            }
+            .to_string();
+
+            let expected = "File \"/a/b\" has 3 bytes, more than the maximum of 2.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_file_write() {
+            let actual = main::Error::FileWrite {
+                path: "/a/b".into(),
+                source: main::IoError(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "permission denied",
+                )),
+            }
+            .to_string();
+
+            let expected = "Unable to write file \"/a/b\": permission denied";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_group_collision() {
+            let actual = main::Error::GroupCollision {
+                names: vec!["a-b".into(), "a_b".into()],
+                identifier: "A_B".into(),
+            }
+            .to_string();
+
+            let expected =
+                "Groups [\"a-b\", \"a_b\"] share the identifier A_B. Rename one of them.";
+            assert_eq!(actual, expected);
+        }
+
         #[test]
         fn handles_ignore() {
             let actual = main::Error::Ignore(main::IgnoreError(ignore::Error::Glob {
@@ -129,6 +476,88 @@ mod tests {
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn handles_invalid_capture() {
+            let actual = main::Error::InvalidCapture {
+                relative_path: "V1a__b.sql".into(),
+                field: "version".into(),
+                value: "1a".into(),
+            }
+            .to_string();
+
+            let expected = "Captured value \"1a\" of relative path \"V1a__b.sql\" \
+is not an integer as needed for field \"version\".";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_invalid_encoding() {
+            let actual = main::Error::InvalidEncoding {
+                path: "/a/b".into(),
+                encoding: main::Encoding::Utf16Le,
+            }
+            .to_string();
+
+            let expected = "File is not valid in encoding \"utf-16le\": \"/a/b\"";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_invalid_json() {
+            let actual = main::Error::InvalidJson {
+                path: "/a/b".into(),
+                message: "EOF while parsing a value at line 1 column 0".into(),
+            }
+            .to_string();
+
+            let expected = "File is not valid JSON \
+(EOF while parsing a value at line 1 column 0): \"/a/b\"";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_invalid_pattern() {
+            let actual = main::Error::InvalidPattern {
+                pattern: "a(".into(),
+                message: "unclosed group".into(),
+            }
+            .to_string();
+
+            let expected = "Invalid path pattern \"a(\": unclosed group";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_missing_locked_file() {
+            let actual = main::Error::MissingLockedFile {
+                path: "a/b".into(),
+                lock_file: "/c.lock".into(),
+            }
+            .to_string();
+
+            let expected = "File \"a/b\" of index lock file \"/c.lock\" is not among the files. \
+Remove its line to give up its index, which shifts those of later files.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_missing_required_file() {
+            let actual = main::Error::MissingRequiredFile("a/b".into()).to_string();
+
+            let expected = "Required file \"a/b\" is not among the files. \
+Check the path patterns or the \"required\" list for typos.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_no_fingerprint() {
+            let actual = main::Error::NoFingerprint.to_string();
+
+            let expected = "Field \"fingerprinted_path\" needs fingerprints. \
+            Configure them with a \"[fingerprint]\" table.";
+            assert_eq!(actual, expected);
+        }
+
         #[test]
         fn handles_no_initializer() {
             let actual = main::Error::NoInitializer.to_string();
@@ -139,6 +568,15 @@ use standard fields to generate a default initializer.";
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn handles_no_main_file() {
+            let actual = main::Error::NoMainFile("a/b".into()).to_string();
+
+            let expected = "Variants of \"a/b\" have no main file, \
+which fields like \"contents_str\" need.";
+            assert_eq!(actual, expected);
+        }
+
         #[test]
         fn handles_nonstandard_field() {
             let actual = main::Error::NonstandardField {
@@ -154,6 +592,19 @@ use standard fields only (\"xy\", \"z\").";
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn handles_path_collision() {
+            let actual = main::Error::PathCollision {
+                relative_path: "a/b".into(),
+                absolute_paths: ["/c/b".into(), "/d/a/b".into()],
+            }
+            .to_string();
+
+            let expected = "Relative path \"a/b\" is taken by both \"/c/b\" and \"/d/a/b\". \
+Consider a different mount for one of their sources.";
+            assert_eq!(actual, expected);
+        }
+
         #[test]
         fn handles_path_invalid_unicode() {
             let actual = main::Error::PathInvalidUnicode("a/b".into()).to_string();
@@ -172,6 +623,78 @@ use standard fields only (\"xy\", \"z\").";
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn handles_special_file() {
+            let actual = main::Error::SpecialFile("/a/b".into()).to_string();
+
+            let expected = "File is neither a regular file nor a folder: \"/a/b\". \
+Exclude it or configure \"special_files = 'skip'\".";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_symlink_escapes_base_folder() {
+            let actual = main::Error::SymlinkEscapesBaseFolder {
+                path: "/a/b".into(),
+                target: "/c".into(),
+            }
+            .to_string();
+
+            let expected = "Symbolic link \"/a/b\" points to \"/c\" outside of the base folder.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_too_few_files() {
+            let actual = main::Error::TooFewFiles {
+                count: 1,
+                min_files: 2,
+            }
+            .to_string();
+
+            let expected = "Only 1 files are included, fewer than the minimum of 2.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_too_many_cfg_predicates() {
+            let actual = main::Error::TooManyCfgPredicates {
+                count: 7,
+                max_predicates: 6,
+            }
+            .to_string();
+
+            let expected = "Sources have 7 distinct `cfg` predicates, more than the maximum of 6, \
+as each combination of them is compiled apart.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_too_many_files() {
+            let actual = main::Error::TooManyFiles {
+                count: 3,
+                max_files: 2,
+            }
+            .to_string();
+
+            let expected = "3 files are included, more than the maximum of 2.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_undefined_variable() {
+            let actual = main::Error::UndefinedVariable {
+                name: "env:ABC".into(),
+                path: "/a".into(),
+            }
+            .to_string();
+
+            let expected = "Undefined variable \"env:ABC\" in file \"/a\". \
+Use one of \"${cargo:…}\", \"${env:…}\", \"${git:commit}\" or \
+escape a literal \"${\" as \"$${\".";
+            assert_eq!(actual, expected);
+        }
+
         #[test]
         fn handles_unexpected_empty_relative_path() {
             let actual = main::Error::UnexpectedEmptyRelativePath {
@@ -191,5 +714,73 @@ use standard fields only (\"xy\", \"z\").";
             let expected = "Unexpected path collision (consider reporting this): \"a/b\"";
             assert_eq!(actual, expected);
         }
+
+        #[test]
+        fn handles_unknown_alias_target() {
+            let actual = main::Error::UnknownAliasTarget {
+                alias: "a".into(),
+                target: "b/c".into(),
+            }
+            .to_string();
+
+            let expected = "Alias \"a\" refers to \"b/c\", which is not among the files.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_unknown_order_capture() {
+            let actual = main::Error::UnknownOrderCapture("a".into()).to_string();
+
+            let expected = "Ordering by capture \"a\" needs a capture group of this name \
+in the \"captures\" regular expression.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_unknown_order_path() {
+            let actual = main::Error::UnknownOrderPath("a/b".into()).to_string();
+
+            let expected = "File \"a/b\" of the order list is not among the files.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_unknown_override_field() {
+            let actual = main::Error::UnknownOverrideField {
+                paths: "*.png".into(),
+                field: "abc".into(),
+            }
+            .to_string();
+
+            let expected = "Override for paths \"*.png\" sets field \"abc\", \
+which the default initializer does not have.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_unlocked_files() {
+            let actual = main::Error::UnlockedFiles {
+                paths: vec!["a/b".into()],
+                lock_file: "/c.lock".into(),
+            }
+            .to_string();
+
+            let expected = "Files [\"a/b\"] are missing from index lock file \"/c.lock\". \
+Append them to it, or build once with the environment variable \
+IFTREE_UPDATE_LOCKS set to have them appended.";
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_unmatched_pattern() {
+            let actual = main::Error::UnmatchedPattern {
+                pattern: "/a/*.b".into(),
+                base_folder: "/c".into(),
+            }
+            .to_string();
+
+            let expected = "Path pattern \"/a/*.b\" matches no files in base folder \"/c\".";
+            assert_eq!(actual, expected);
+        }
     }
 }
@@ -1,16 +1,128 @@
 use std::cmp;
 use std::collections;
 use std::env;
+use std::io;
 use std::path;
 use std::result;
+use std::time;
 
 #[derive(Clone, cmp::PartialEq, Debug, Eq)]
 pub struct Configuration {
+    pub sources: Vec<Source>,
+    pub virtual_files: Vec<VirtualFile>,
+    pub pattern_syntax: PatternSyntax,
+    pub standard_filters: StandardFilters,
+    pub metadata_filters: MetadataFilters,
+    pub oversized_files: OversizedFiles,
+    pub symlinks: Symlinks,
+    pub special_files: SpecialFiles,
+    pub unreadable_files: UnreadableFiles,
+    pub required: Vec<String>,
+    pub min_files: Option<usize>,
+    pub max_files: Option<usize>,
+    pub require_pattern_matches: bool,
+    pub rewrites: Vec<Rewrite>,
+    pub aliases: collections::BTreeMap<String, String>,
+    pub captures: Option<String>,
+    pub variants: collections::BTreeMap<String, String>,
+    pub order: Order,
+    pub index_lock: Option<path::PathBuf>,
+    pub groups: collections::BTreeMap<String, String>,
+    pub template: Template,
+    pub transforms: Vec<Transform>,
+    pub overrides: Vec<Override>,
+    pub fingerprint: Option<Fingerprint>,
+    pub deduplicate: bool,
+    pub runtime_layers: bool,
+    pub debug: bool,
+    pub configuration_files: Vec<path::PathBuf>,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Source {
     pub paths: String,
     pub base_folder: path::PathBuf,
     pub root_folder_variable: String,
-    pub template: Template,
-    pub debug: bool,
+    pub mount: Vec<String>,
+    pub layer: Option<String>,
+    pub cfg: Option<syn::Meta>,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct VirtualFile {
+    pub path: Vec<String>,
+    pub contents: String,
+    pub substitute_variables: bool,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Variant {
+    pub condition: Option<syn::Meta>,
+    pub configuration: Configuration,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Default, Eq)]
+pub struct PatternSyntax {
+    pub language: PatternLanguage,
+    pub case_insensitive: bool,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Default, Eq)]
+pub enum PatternLanguage {
+    #[default]
+    Gitignore,
+    GitignoreFaithful,
+    Glob,
+    Regex,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Default, Eq)]
+pub struct StandardFilters {
+    pub gitignore: bool,
+    pub git_exclude: bool,
+    pub git_global: bool,
+    pub ignore: bool,
+    pub hidden: bool,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Default, Eq)]
+pub struct MetadataFilters {
+    pub max_size: Option<u64>,
+    pub max_depth: Option<usize>,
+    pub content: Option<Content>,
+    pub modified_after: Option<time::SystemTime>,
+    pub modified_before: Option<time::SystemTime>,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Eq)]
+pub enum Content {
+    Binary,
+    Text,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Eq)]
+pub enum Symlinks {
+    Follow,
+    FollowWithinBaseFolder,
+    Keep,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Eq)]
+pub enum OversizedFiles {
+    Fail,
+    Skip,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Eq)]
+pub enum SpecialFiles {
+    Reject,
+    Skip,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Eq)]
+pub enum UnreadableFiles {
+    Fail,
+    Skip,
 }
 
 #[derive(Clone, cmp::PartialEq, Debug, Eq)]
@@ -29,6 +141,72 @@ pub struct CustomVisitor {
     pub visit_file: syn::Path,
 }
 
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Rewrite {
+    pub paths: Option<String>,
+    pub strip_prefix: Vec<String>,
+    pub rename: Option<Rename>,
+    pub extensions: collections::BTreeMap<String, String>,
+    pub add_prefix: Vec<String>,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Rename {
+    pub regex: String,
+    pub replacement: String,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Transform {
+    pub paths: String,
+    pub command: Option<Vec<String>>,
+    pub encoding: Option<Encoding>,
+    pub strip_bom: bool,
+    pub substitute_variables: bool,
+    pub line_endings: Option<LineEndings>,
+    pub trim_trailing_whitespace: bool,
+    pub minify_json: bool,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Override {
+    pub paths: String,
+    pub initializer: Option<syn::Path>,
+    pub transform: Option<Transform>,
+    pub fields: collections::BTreeMap<String, syn::Expr>,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Eq)]
+pub enum Encoding {
+    Auto,
+    Latin1,
+    Utf8,
+    Utf16Be,
+    Utf16Le,
+}
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Eq)]
+pub enum LineEndings {
+    Crlf,
+    Lf,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Fingerprint {
+    pub length: usize,
+    pub rewrite_references: Option<String>,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Default, Eq)]
+pub enum Order {
+    #[default]
+    Path,
+    Natural,
+    CaseInsensitive,
+    List(Vec<String>),
+    Capture(String),
+}
+
 #[derive(Clone, cmp::PartialEq, Debug, Eq)]
 pub struct Type<T> {
     pub name: syn::Ident,
@@ -47,6 +225,11 @@ pub enum TypeStructure<T> {
 pub struct Path {
     pub relative: Vec<String>,
     pub absolute: String,
+    pub contents: Option<Vec<u8>>,
+    pub size: Option<usize>,
+    pub hash: Option<String>,
+    pub fingerprinted_path: Option<String>,
+    pub layer: Option<String>,
 }
 
 #[derive(Clone, cmp::PartialEq, Debug)]
@@ -54,6 +237,10 @@ pub struct View {
     pub type_: syn::Ident,
     pub visitors: Vec<Visitor>,
     pub forest: Forest,
+    pub shared_contents: Option<Vec<SharedContents>>,
+    pub layers: Option<Layers>,
+    pub groups: Vec<Group>,
+    pub skipped_files: Option<Vec<SkippedFile>>,
     pub debug: bool,
 }
 
@@ -61,6 +248,7 @@ pub struct View {
 pub enum Visitor {
     Array(Initializer),
     Identifiers,
+    Fingerprints,
     Custom(CustomVisitor),
 }
 
@@ -72,18 +260,21 @@ pub enum Initializer {
 
 #[derive(Clone, cmp::PartialEq, Debug, Eq)]
 pub enum Populator {
+    Capture { name: String, integer: bool },
     ContentsBytes,
     ContentsStr,
+    FingerprintedPath,
     GetBytes,
     GetStr,
     RelativePath,
+    Variant { name: String, str_: bool },
 }
 
 pub type Forest = collections::BTreeMap<String, Tree>;
 
 #[derive(Clone, cmp::PartialEq, Debug)]
 pub enum Tree {
-    File(File),
+    File(Box<File>),
     Folder(Folder),
 }
 
@@ -93,6 +284,22 @@ pub struct File {
     pub index: usize,
     pub relative_path: String,
     pub absolute_path: String,
+    pub contents: Option<Vec<u8>>,
+    pub size: Option<usize>,
+    pub hash: Option<String>,
+    pub fingerprinted_path: Option<String>,
+    pub shared_contents: Option<usize>,
+    pub layer: Option<String>,
+    pub captures: collections::BTreeMap<String, String>,
+    pub variants: collections::BTreeMap<String, FileVariant>,
+    pub override_: Option<Override>,
+    pub alias_of: Option<String>,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct FileVariant {
+    pub absolute_path: String,
+    pub contents: Option<Vec<u8>>,
 }
 
 #[derive(Clone, cmp::PartialEq, Debug)]
@@ -101,42 +308,231 @@ pub struct Folder {
     pub forest: Forest,
 }
 
+// The outcome of an index lock for one variant, whose files the lock may lack
+// or list beyond.
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct IndexLock {
+    pub file: path::PathBuf,
+    pub locked_paths: Vec<String>,
+    pub new_paths: Vec<String>,
+    pub missing_paths: Vec<String>,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Layers {
+    pub names: Vec<String>,
+    pub shadowed_files: Vec<File>,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct Group {
+    pub identifier: syn::Ident,
+    pub indices: Vec<usize>,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Clone, cmp::PartialEq, Debug, Eq)]
+pub struct SharedContents {
+    pub absolute_path: String,
+    pub contents: Option<Vec<u8>>,
+    pub size: usize,
+    pub file_count: usize,
+}
+
 pub type Result<T> = result::Result<T, Error>;
 
 #[derive(Clone, cmp::PartialEq, Debug)]
 pub enum Error {
+    AliasCollision(String),
+    CaptureMismatch {
+        relative_path: String,
+        regex: String,
+    },
+    CommandFailed {
+        command: Vec<String>,
+        path: path::PathBuf,
+        message: String,
+    },
+    CyclicReferences(Vec<path::PathBuf>),
+    EmptyRelativePath(path::PathBuf),
     EnvironmentVariable {
         name: String,
         source: env::VarError,
     },
+    FileRead {
+        path: path::PathBuf,
+        source: IoError,
+    },
+    FileTooLarge {
+        path: path::PathBuf,
+        size: u64,
+        max_size: u64,
+    },
+    FileWrite {
+        path: path::PathBuf,
+        source: IoError,
+    },
+    GroupCollision {
+        names: Vec<String>,
+        identifier: String,
+    },
     Ignore(IgnoreError),
+    InvalidCapture {
+        relative_path: String,
+        field: String,
+        value: String,
+    },
+    InvalidEncoding {
+        path: path::PathBuf,
+        encoding: Encoding,
+    },
+    InvalidJson {
+        path: path::PathBuf,
+        message: String,
+    },
+    InvalidPattern {
+        pattern: String,
+        message: String,
+    },
+    MissingLockedFile {
+        path: String,
+        lock_file: path::PathBuf,
+    },
+    MissingRequiredFile(String),
+    NoFingerprint,
     NoInitializer,
+    NoMainFile(String),
+    PathCollision {
+        relative_path: path::PathBuf,
+        absolute_paths: [path::PathBuf; 2],
+    },
     NonstandardField {
         field: syn::Ident,
         standard_fields: Vec<syn::Ident>,
     },
     PathInvalidUnicode(path::PathBuf),
     PathStripPrefix(path::StripPrefixError),
+    SpecialFile(path::PathBuf),
+    SymlinkEscapesBaseFolder {
+        path: path::PathBuf,
+        target: path::PathBuf,
+    },
+    TooFewFiles {
+        count: usize,
+        min_files: usize,
+    },
+    TooManyCfgPredicates {
+        count: usize,
+        max_predicates: usize,
+    },
+    TooManyFiles {
+        count: usize,
+        max_files: usize,
+    },
+    UndefinedVariable {
+        name: String,
+        path: path::PathBuf,
+    },
     UnexpectedEmptyRelativePath {
         absolute_path: path::PathBuf,
     },
     UnexpectedPathCollision(path::PathBuf),
+    UnknownAliasTarget {
+        alias: String,
+        target: String,
+    },
+    UnknownOrderCapture(String),
+    UnknownOrderPath(String),
+    UnknownOverrideField {
+        paths: String,
+        field: String,
+    },
+    UnlockedFiles {
+        paths: Vec<String>,
+        lock_file: path::PathBuf,
+    },
+    UnmatchedPattern {
+        pattern: String,
+        base_folder: path::PathBuf,
+    },
 }
 
 #[derive(Clone, Debug)]
 pub struct IgnoreError(pub ignore::Error);
 
+#[derive(Debug)]
+pub struct IoError(pub io::Error);
+
 #[cfg(test)]
 pub mod stubs {
     use super::*;
 
     pub fn configuration() -> Configuration {
         Configuration {
+            sources: vec![source()],
+            virtual_files: vec![],
+            pattern_syntax: PatternSyntax::default(),
+            standard_filters: StandardFilters::default(),
+            metadata_filters: MetadataFilters::default(),
+            oversized_files: OversizedFiles::Fail,
+            symlinks: Symlinks::Keep,
+            special_files: SpecialFiles::Reject,
+            unreadable_files: UnreadableFiles::Fail,
+            required: vec![],
+            min_files: None,
+            max_files: None,
+            require_pattern_matches: false,
+            rewrites: vec![],
+            aliases: collections::BTreeMap::new(),
+            captures: None,
+            variants: collections::BTreeMap::new(),
+            order: Order::Path,
+            index_lock: None,
+            groups: collections::BTreeMap::new(),
+            template: Template::Visitors(vec![]),
+            transforms: vec![],
+            overrides: vec![],
+            fingerprint: None,
+            deduplicate: false,
+            runtime_layers: false,
+            debug: false,
+            configuration_files: vec![],
+        }
+    }
+
+    pub fn source() -> Source {
+        Source {
             paths: "!*".into(),
             base_folder: "foo".into(),
             root_folder_variable: "BAR".into(),
-            template: Template::Visitors(vec![]),
-            debug: false,
+            mount: vec![],
+            layer: None,
+            cfg: None,
+        }
+    }
+
+    pub fn transform() -> Transform {
+        Transform {
+            paths: "*".into(),
+            command: None,
+            encoding: None,
+            strip_bom: false,
+            substitute_variables: false,
+            line_endings: None,
+            trim_trailing_whitespace: false,
+            minify_json: false,
+        }
+    }
+
+    pub fn fingerprint() -> Fingerprint {
+        Fingerprint {
+            length: 8,
+            rewrite_references: None,
         }
     }
 
@@ -155,6 +551,11 @@ pub mod stubs {
         Path {
             relative: vec!["bar".into()],
             absolute: "/foo/bar".into(),
+            contents: None,
+            size: None,
+            hash: None,
+            fingerprinted_path: None,
+            layer: None,
         }
     }
 
@@ -163,6 +564,10 @@ pub mod stubs {
             type_: quote::format_ident!("Foo"),
             visitors: vec![],
             forest: Forest::new(),
+            shared_contents: None,
+            layers: None,
+            groups: vec![],
+            skipped_files: None,
             debug: false,
         }
     }
@@ -173,6 +578,25 @@ pub mod stubs {
             index: 123,
             relative_path: "bar".into(),
             absolute_path: "/foo/bar".into(),
+            contents: None,
+            size: None,
+            hash: None,
+            fingerprinted_path: None,
+            shared_contents: None,
+            layer: None,
+            captures: collections::BTreeMap::new(),
+            variants: collections::BTreeMap::new(),
+            override_: None,
+            alias_of: None,
+        }
+    }
+
+    pub fn shared_contents() -> SharedContents {
+        SharedContents {
+            absolute_path: "/foo/bar".into(),
+            contents: None,
+            size: 0,
+            file_count: 2,
         }
     }
 
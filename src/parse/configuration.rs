@@ -1,16 +1,181 @@
 use std::cmp;
+use std::collections;
 use std::path;
+use std::time;
 
 #[derive(serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Configuration {
-    pub paths: String,
+    pub paths: Option<String>,
     pub base_folder: Option<path::PathBuf>,
     pub root_folder_variable: Option<String>,
+    pub source: Option<Vec<Source>>,
+    pub file: Option<Vec<VirtualFile>>,
+    pub pattern_syntax: Option<PatternLanguage>,
+    pub case_insensitive: Option<bool>,
+    pub standard_filters: Option<StandardFilters>,
+    pub metadata_filters: Option<MetadataFilters>,
+    pub oversized_files: Option<OversizedFiles>,
+    pub symlinks: Option<Symlinks>,
+    pub special_files: Option<SpecialFiles>,
+    pub unreadable_files: Option<UnreadableFiles>,
+    pub required: Option<Vec<String>>,
+    pub min_files: Option<usize>,
+    pub max_files: Option<usize>,
+    pub require_pattern_matches: Option<bool>,
+    pub rewrite: Option<Vec<Rewrite>>,
+    pub aliases: Option<collections::BTreeMap<String, String>>,
+    pub captures: Option<String>,
+    pub variants: Option<collections::BTreeMap<String, String>>,
+    pub order: Option<Order>,
+    pub index_lock: Option<path::PathBuf>,
+    pub groups: Option<collections::BTreeMap<String, String>>,
     pub template: Option<Template>,
+    pub transform: Option<Vec<Transform>>,
+    pub r#override: Option<Vec<Override>>,
+    pub fingerprint: Option<Fingerprint>,
+    pub deduplicate: Option<bool>,
+    pub runtime_layers: Option<bool>,
     pub debug: Option<bool>,
 }
 
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Source {
+    pub paths: String,
+    pub base_folder: Option<path::PathBuf>,
+    pub root_folder_variable: Option<String>,
+    pub mount: Option<String>,
+    pub layer: Option<String>,
+    pub cfg: Option<Predicate>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VirtualFile {
+    pub path: String,
+    pub contents: String,
+    pub substitute_variables: Option<bool>,
+}
+
+#[derive(cmp::PartialEq, Debug, Eq)]
+pub struct Predicate(pub syn::Meta);
+
+#[derive(serde::Deserialize)]
+pub enum PatternLanguage {
+    #[serde(rename = "gitignore")]
+    Gitignore,
+    #[serde(rename = "gitignore_faithful")]
+    GitignoreFaithful,
+    #[serde(rename = "glob")]
+    Glob,
+    #[serde(rename = "regex")]
+    Regex,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields, untagged)]
+pub enum StandardFilters {
+    All(bool),
+    Each {
+        gitignore: Option<bool>,
+        git_exclude: Option<bool>,
+        git_global: Option<bool>,
+        ignore: Option<bool>,
+        hidden: Option<bool>,
+    },
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetadataFilters {
+    pub max_size: Option<u64>,
+    pub max_depth: Option<usize>,
+    pub content: Option<Content>,
+    pub modified_after: Option<Timestamp>,
+    pub modified_before: Option<Timestamp>,
+}
+
+#[derive(serde::Deserialize)]
+pub enum Content {
+    #[serde(rename = "binary")]
+    Binary,
+    #[serde(rename = "text")]
+    Text,
+}
+
+#[derive(cmp::PartialEq, Debug, Eq)]
+pub struct Timestamp(pub time::SystemTime);
+
+#[derive(serde::Deserialize)]
+pub enum Symlinks {
+    #[serde(rename = "follow")]
+    Follow,
+    #[serde(rename = "follow_within_base_folder")]
+    FollowWithinBaseFolder,
+    #[serde(rename = "keep")]
+    Keep,
+}
+
+#[derive(serde::Deserialize)]
+pub enum OversizedFiles {
+    #[serde(rename = "fail")]
+    Fail,
+    #[serde(rename = "skip")]
+    Skip,
+}
+
+#[derive(serde::Deserialize)]
+pub enum SpecialFiles {
+    #[serde(rename = "reject")]
+    Reject,
+    #[serde(rename = "skip")]
+    Skip,
+}
+
+#[derive(serde::Deserialize)]
+pub enum UnreadableFiles {
+    #[serde(rename = "fail")]
+    Fail,
+    #[serde(rename = "skip")]
+    Skip,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rewrite {
+    pub paths: Option<String>,
+    pub strip_prefix: Option<String>,
+    pub rename: Option<Rename>,
+    pub extensions: Option<collections::BTreeMap<String, String>>,
+    pub add_prefix: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rename {
+    pub regex: String,
+    pub replacement: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields, untagged)]
+pub enum Order {
+    Name(OrderName),
+    List { list: Vec<String> },
+    Capture { capture: String },
+}
+
+#[derive(serde::Deserialize)]
+pub enum OrderName {
+    #[serde(rename = "case_insensitive")]
+    CaseInsensitive,
+    #[serde(rename = "natural")]
+    Natural,
+    #[serde(rename = "path")]
+    Path,
+}
+
 #[derive(serde::Deserialize)]
 #[serde(deny_unknown_fields, untagged)]
 pub enum Template {
@@ -31,3 +196,69 @@ pub struct CustomVisitor {
     pub visit_folder: Option<Path>,
     pub visit_file: Path,
 }
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Transform {
+    pub paths: String,
+    pub command: Option<Vec<String>>,
+    pub encoding: Option<Encoding>,
+    pub strip_bom: Option<bool>,
+    pub substitute_variables: Option<bool>,
+    pub line_endings: Option<LineEndings>,
+    pub trim_trailing_whitespace: Option<bool>,
+    pub minify_json: Option<bool>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Override {
+    pub paths: String,
+    pub initializer: Option<Path>,
+    pub transform: Option<OverrideTransform>,
+    pub fields: Option<collections::BTreeMap<String, Expression>>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideTransform {
+    pub command: Option<Vec<String>>,
+    pub encoding: Option<Encoding>,
+    pub strip_bom: Option<bool>,
+    pub substitute_variables: Option<bool>,
+    pub line_endings: Option<LineEndings>,
+    pub trim_trailing_whitespace: Option<bool>,
+    pub minify_json: Option<bool>,
+}
+
+#[derive(cmp::PartialEq, Debug, Eq)]
+pub struct Expression(pub syn::Expr);
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fingerprint {
+    pub length: Option<usize>,
+    pub rewrite_references: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub enum Encoding {
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "latin-1")]
+    Latin1,
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "utf-16be")]
+    Utf16Be,
+    #[serde(rename = "utf-16le")]
+    Utf16Le,
+}
+
+#[derive(serde::Deserialize)]
+pub enum LineEndings {
+    #[serde(rename = "crlf")]
+    Crlf,
+    #[serde(rename = "lf")]
+    Lf,
+}
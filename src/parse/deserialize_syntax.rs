@@ -0,0 +1,36 @@
+use serde::de;
+use std::fmt;
+use std::marker;
+
+// Deserializes a string parsed as Rust syntax, like an expression.
+pub fn main<'a, T, D>(deserializer: D, expecting: &'static str) -> Result<T, D::Error>
+where
+    T: syn::parse::Parse,
+    D: serde::Deserializer<'a>,
+{
+    deserializer.deserialize_str(Visitor {
+        expecting,
+        value: marker::PhantomData,
+    })
+}
+
+struct Visitor<T> {
+    expecting: &'static str,
+    value: marker::PhantomData<T>,
+}
+
+impl<T: syn::parse::Parse> de::Visitor<'_> for Visitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, string: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        syn::parse_str(string)
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(string), &self))
+    }
+}
@@ -0,0 +1,47 @@
+use super::configuration;
+use super::deserialize_syntax;
+
+impl<'a> serde::Deserialize<'a> for configuration::Expression {
+    fn deserialize<T>(deserializer: T) -> Result<configuration::Expression, T::Error>
+    where
+        T: serde::Deserializer<'a>,
+    {
+        deserialize_syntax::main(deserializer, "an expression (like `\"\"` or `x::y()`)")
+            .map(configuration::Expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(test)]
+    mod deserialize {
+        use super::*;
+        use std::cmp;
+
+        #[derive(cmp::PartialEq, Debug, serde::Deserialize)]
+        struct Binding {
+            value: configuration::Expression,
+        }
+
+        #[test]
+        fn handles_valid_value() {
+            let actual: Result<Binding, _> = toml::from_str("value = 'a::b(\"c\")'");
+
+            let actual = actual.unwrap();
+            let expected = Binding {
+                value: configuration::Expression(syn::parse_str("a::b(\"c\")").unwrap()),
+            };
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_invalid_value_it_errs() {
+            let actual: Result<Binding, _> = toml::from_str("value = 'a b'");
+
+            let actual = actual.is_err();
+            assert!(actual);
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use crate::transform_files;
+use serde::de::Error as _;
+use std::env;
+use std::io;
+use std::path;
+use toml::de;
+
+// Besides the configuration, the files it has been read from are returned,
+// apart from the manifest, so that changing them rebuilds the crate.
+pub fn main(
+    string: &str,
+    get_environment_variable: &dyn Fn(&str) -> Result<String, env::VarError>,
+    get_variable: &dyn Fn(&str) -> Option<String>,
+    read_file: &dyn Fn(&path::Path) -> io::Result<String>,
+) -> Result<(toml::Table, Vec<path::PathBuf>), de::Error> {
+    let mut inline = toml::from_str::<toml::Table>(string)?;
+    let context = Context {
+        get_environment_variable,
+        get_variable,
+        read_file,
+    };
+    let mut files = vec![];
+
+    // Precedence from low to high: Cargo metadata, configuration file, inline.
+    let mut configuration = get_cargo_metadata(&context)?;
+    if let Some(file) = inline.remove(CONFIGURATION_FILE_KEY) {
+        let file = get_string(&file, CONFIGURATION_FILE_KEY)?;
+        let file = resolve_file(&context, &substitute(&context, file)?);
+        configuration.extend(read_table(&context, &file)?);
+        files.push(file);
+    }
+    configuration.extend(inline);
+
+    resolve_source(&context, &mut configuration, &mut files)?;
+    if let Some(toml::Value::Array(sources)) = configuration.get_mut("source") {
+        for source in sources {
+            if let toml::Value::Table(source) = source {
+                resolve_source(&context, source, &mut files)?;
+            }
+        }
+    }
+
+    Ok((configuration, files))
+}
+
+struct Context<'a> {
+    get_environment_variable: &'a dyn Fn(&str) -> Result<String, env::VarError>,
+    get_variable: &'a dyn Fn(&str) -> Option<String>,
+    read_file: &'a dyn Fn(&path::Path) -> io::Result<String>,
+}
+
+const CONFIGURATION_FILE_KEY: &str = "configuration_file";
+
+fn get_cargo_metadata(context: &Context) -> Result<toml::Table, de::Error> {
+    // Without a manifest folder, say, outside of Cargo, there are no defaults.
+    let Ok(folder) = (context.get_environment_variable)("CARGO_MANIFEST_DIR") else {
+        return Ok(toml::Table::new());
+    };
+    let manifest = read_table(context, &path::Path::new(&folder).join("Cargo.toml"))?;
+
+    match ["package", "metadata", "iftree"]
+        .into_iter()
+        .try_fold(&toml::Value::Table(manifest), |value, key| value.get(key))
+    {
+        None => Ok(toml::Table::new()),
+        Some(toml::Value::Table(metadata)) => Ok(metadata.clone()),
+        Some(_) => Err(de::Error::custom(
+            "Cargo manifest field `package.metadata.iftree` must be a table",
+        )),
+    }
+}
+
+fn resolve_source(
+    context: &Context,
+    source: &mut toml::Table,
+    files: &mut Vec<path::PathBuf>,
+) -> Result<(), de::Error> {
+    for key in ["paths", "base_folder"] {
+        if let Some(value) = source.get(key) {
+            let value = substitute(context, get_string(value, key)?)?;
+            source.insert(key.into(), value.into());
+        }
+    }
+
+    if let Some(file) = source.remove("paths_file") {
+        let file = resolve_file(
+            context,
+            &substitute(context, get_string(&file, "paths_file")?)?,
+        );
+        let patterns = substitute(context, &read_file(context, &file)?)?;
+        let paths = match source.get("paths") {
+            None => patterns,
+            Some(paths) => format!("{}\n{patterns}", get_string(paths, "paths")?),
+        };
+        source.insert("paths".into(), paths.into());
+        files.push(file);
+    }
+
+    Ok(())
+}
+
+fn read_table(context: &Context, file: &path::Path) -> Result<toml::Table, de::Error> {
+    let contents = read_file(context, file)?;
+    toml::from_str(&contents)
+        .map_err(|error| de::Error::custom(format!("In file {file:?}: {error}")))
+}
+
+// Like a base folder, a relative file is resolved from the manifest folder.
+fn resolve_file(context: &Context, file: &str) -> path::PathBuf {
+    match (context.get_environment_variable)("CARGO_MANIFEST_DIR") {
+        Ok(folder) if path::Path::new(file).is_relative() => path::Path::new(&folder).join(file),
+        _ => file.into(),
+    }
+}
+
+fn read_file(context: &Context, file: &path::Path) -> Result<String, de::Error> {
+    (context.read_file)(file)
+        .map_err(|error| de::Error::custom(format!("Unable to read file {file:?}: {error}")))
+}
+
+fn get_string<'a>(value: &'a toml::Value, key: &str) -> Result<&'a str, de::Error> {
+    value
+        .as_str()
+        .ok_or_else(|| de::Error::custom(format!("field `{key}` must be a string")))
+}
+
+fn substitute(context: &Context, string: &str) -> Result<String, de::Error> {
+    let substituted =
+        transform_files::substitute_variables(string.as_bytes(), context.get_variable).map_err(
+            |name| {
+                de::Error::custom(format!(
+                    "Undefined variable {name:?} in {string:?}. \
+                    Escape a literal \"${{\" as \"$${{\".",
+                ))
+            },
+        )?;
+    Ok(String::from_utf8_lossy(&substituted).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_environment_variable(name: &str) -> Result<String, env::VarError> {
+        match name {
+            "CARGO_MANIFEST_DIR" => Ok("/a".into()),
+            "B" => Ok("b".into()),
+            _ => Err(env::VarError::NotPresent),
+        }
+    }
+
+    fn get_variable(name: &str) -> Option<String> {
+        name.strip_prefix("env:")
+            .and_then(|name| get_environment_variable(name).ok())
+    }
+
+    fn read_file(file: &path::Path) -> io::Result<String> {
+        match file.to_str().unwrap() {
+            "/a/Cargo.toml" => Ok("
+[package]
+name = 'c'
+
+[package.metadata.iftree]
+paths = '/metadata/**'
+deduplicate = true
+"
+            .into()),
+            "/a/d.toml" => Ok("paths = '/file/**'\ndebug = true".into()),
+            "/a/b.patterns" => Ok("/${env:B}/*.txt\n!/b/e.txt".into()),
+            _ => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn parse(string: &str) -> toml::Table {
+        toml::from_str(string).unwrap()
+    }
+
+    fn parse_files(files: &[&str]) -> Vec<path::PathBuf> {
+        files.iter().map(path::PathBuf::from).collect()
+    }
+
+    #[test]
+    fn handles_cargo_metadata_as_defaults() {
+        let actual = main(
+            "paths = '/inline/**'",
+            &get_environment_variable,
+            &get_variable,
+            &read_file,
+        );
+
+        let (actual, files) = actual.unwrap();
+        let expected = parse("paths = '/inline/**'\ndeduplicate = true");
+        assert_eq!(files, parse_files(&[]));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_configuration_file() {
+        let actual = main(
+            "configuration_file = 'd.toml'\ndebug = false",
+            &get_environment_variable,
+            &get_variable,
+            &read_file,
+        );
+
+        let (actual, files) = actual.unwrap();
+        let expected = parse("paths = '/file/**'\ndeduplicate = true\ndebug = false");
+        assert_eq!(files, parse_files(&["/a/d.toml"]));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_paths_files_and_environment_variables() {
+        let actual = main(
+            "
+paths = '/${env:B}/a'
+paths_file = '${env:B}.patterns'
+base_folder = 'x/${env:B}'
+
+[[source]]
+paths_file = '/a/b.patterns'
+",
+            &get_environment_variable,
+            &get_variable,
+            &read_file,
+        );
+
+        let (actual, files) = actual.unwrap();
+        assert_eq!(files, parse_files(&["/a/b.patterns", "/a/b.patterns"]));
+        let expected = parse(
+            "
+paths = '''
+/b/a
+/b/*.txt
+!/b/e.txt'''
+base_folder = 'x/b'
+deduplicate = true
+
+[[source]]
+paths = '''
+/b/*.txt
+!/b/e.txt'''
+",
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_undefined_variable_it_errs() {
+        let actual = main(
+            "paths = '/${env:C}'",
+            &get_environment_variable,
+            &get_variable,
+            &read_file,
+        );
+
+        let actual = actual.unwrap_err().to_string();
+        let expected = "Undefined variable \"env:C\" in \"/${env:C}\". \
+Escape a literal \"${\" as \"$${\".\n";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_missing_paths_file_it_errs() {
+        let actual = main(
+            "paths_file = 'e'",
+            &get_environment_variable,
+            &get_variable,
+            &read_file,
+        );
+
+        let actual = actual.unwrap_err().to_string();
+        let expected = "Unable to read file \"/a/e\": entity not found\n";
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,5 +1,12 @@
 mod configuration;
+mod deserialize_syntax;
+mod expression;
+mod load_configuration;
 mod parse_configuration;
-mod parse_configuration_from_string;
+mod parse_configuration_from_table;
 mod parse_type;
 mod path;
+mod predicate;
+mod timestamp;
+
+pub use parse_configuration::main as parse_configuration;
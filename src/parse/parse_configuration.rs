@@ -1,13 +1,26 @@
-use super::parse_configuration_from_string;
+use super::load_configuration;
+use super::parse_configuration_from_table;
 use crate::model;
-use syn::parse;
+use std::env;
+use std::fs;
 
-impl parse::Parse for model::Configuration {
-    fn parse(parameters: parse::ParseStream) -> syn::Result<Self> {
-        let token = parameters.parse::<syn::LitStr>()?;
-        parse_configuration_from_string::main(&token.value())
-            .map_err(|error| syn::Error::new(token.span(), error))
-    }
+pub fn main(
+    token: &syn::LitStr,
+    get_variable: &dyn Fn(&str) -> Option<String>,
+) -> syn::Result<model::Configuration> {
+    load_configuration::main(
+        &token.value(),
+        &|name| env::var(name),
+        get_variable,
+        &|path| fs::read_to_string(path),
+    )
+    .and_then(|(table, configuration_files)| {
+        Ok(model::Configuration {
+            configuration_files,
+            ..parse_configuration_from_table::main(table)?
+        })
+    })
+    .map_err(|error| syn::Error::new(token.span(), error))
 }
 
 #[cfg(test)]
@@ -16,7 +29,9 @@ mod tests {
 
     #[test]
     fn handles_valid_configuration() {
-        let actual = syn::parse_str::<model::Configuration>(r#""paths = '/assets'""#);
+        let actual = main(&syn::parse_str(r#""paths = '/assets'""#).unwrap(), &|_| {
+            None
+        });
 
         let actual = actual.is_ok();
         assert!(actual);
@@ -24,7 +39,7 @@ mod tests {
 
     #[test]
     fn given_invalid_configuration_it_errs() {
-        let actual = syn::parse_str::<model::Configuration>(r#""paths = #""#);
+        let actual = main(&syn::parse_str(r#""paths = #""#).unwrap(), &|_| None);
 
         let actual = actual.unwrap_err().to_string();
         let expected = String::from(
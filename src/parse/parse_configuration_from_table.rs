@@ -0,0 +1,930 @@
+use super::configuration;
+use crate::model;
+use serde::de::Error as _;
+use toml::de;
+
+pub fn main(table: toml::Table) -> Result<model::Configuration, de::Error> {
+    let configuration = table.try_into::<configuration::Configuration>()?;
+    if configuration.paths.is_none()
+        && configuration.source.is_none()
+        && configuration.file.is_none()
+    {
+        return Err(de::Error::custom(
+            "missing field `paths`, `source`, or `file`",
+        ));
+    }
+    if configuration
+        .file
+        .iter()
+        .flatten()
+        .any(|file| get_components(&file.path).is_empty())
+    {
+        return Err(de::Error::custom("empty field `path` of `file`"));
+    }
+    if let Some(length) = configuration
+        .fingerprint
+        .as_ref()
+        .and_then(|fingerprint| fingerprint.length)
+        && !(1..=64).contains(&length)
+    {
+        return Err(de::Error::custom(format!(
+            "field `length` of `fingerprint` must be from 1 to 64, not {length}",
+        )));
+    }
+    Ok(configuration.into())
+}
+
+impl From<configuration::Configuration> for model::Configuration {
+    fn from(configuration: configuration::Configuration) -> Self {
+        let main_source = configuration
+            .paths
+            .map(|paths| configuration::Source {
+                paths,
+                base_folder: None,
+                root_folder_variable: None,
+                mount: None,
+                layer: None,
+                cfg: None,
+            })
+            .into_iter();
+        // Other sources default to the base folder and root folder variable
+        // configured at the top level.
+        let sources = main_source
+            .chain(configuration.source.unwrap_or_default())
+            .map(|source| model::Source {
+                paths: source.paths,
+                base_folder: source
+                    .base_folder
+                    .or_else(|| configuration.base_folder.clone())
+                    .unwrap_or_default(),
+                root_folder_variable: source
+                    .root_folder_variable
+                    .or_else(|| configuration.root_folder_variable.clone())
+                    .unwrap_or_else(|| "CARGO_MANIFEST_DIR".into()),
+                mount: get_components(&source.mount.unwrap_or_default()),
+                layer: source.layer,
+                cfg: source.cfg.map(|value| value.0),
+            })
+            .collect();
+
+        model::Configuration {
+            sources,
+            virtual_files: configuration
+                .file
+                .unwrap_or_default()
+                .into_iter()
+                .map(|file| file.into())
+                .collect(),
+            pattern_syntax: model::PatternSyntax {
+                language: configuration
+                    .pattern_syntax
+                    .map_or_else(model::PatternLanguage::default, |value| value.into()),
+                case_insensitive: configuration.case_insensitive.unwrap_or(false),
+            },
+            standard_filters: configuration
+                .standard_filters
+                .map_or_else(model::StandardFilters::default, |value| value.into()),
+            metadata_filters: configuration
+                .metadata_filters
+                .map_or_else(model::MetadataFilters::default, |value| value.into()),
+            oversized_files: configuration
+                .oversized_files
+                .map_or(model::OversizedFiles::Fail, |value| value.into()),
+            symlinks: configuration
+                .symlinks
+                .map_or(model::Symlinks::Keep, |value| value.into()),
+            special_files: configuration
+                .special_files
+                .map_or(model::SpecialFiles::Reject, |value| value.into()),
+            unreadable_files: configuration
+                .unreadable_files
+                .map_or(model::UnreadableFiles::Fail, |value| value.into()),
+            required: configuration.required.unwrap_or_default(),
+            min_files: configuration.min_files,
+            max_files: configuration.max_files,
+            require_pattern_matches: configuration.require_pattern_matches.unwrap_or(false),
+            rewrites: configuration
+                .rewrite
+                .unwrap_or_default()
+                .into_iter()
+                .map(|rewrite| rewrite.into())
+                .collect(),
+            aliases: configuration.aliases.unwrap_or_default(),
+            captures: configuration.captures,
+            variants: configuration.variants.unwrap_or_default(),
+            order: configuration
+                .order
+                .map_or_else(model::Order::default, |value| value.into()),
+            index_lock: configuration.index_lock,
+            groups: configuration.groups.unwrap_or_default(),
+            template: match configuration.template {
+                None => model::Template::Default {
+                    initializer: None,
+                    identifiers: true,
+                },
+                Some(template) => template.into(),
+            },
+            transforms: configuration
+                .transform
+                .unwrap_or_default()
+                .into_iter()
+                .map(|transform| transform.into())
+                .collect(),
+            overrides: configuration
+                .r#override
+                .unwrap_or_default()
+                .into_iter()
+                .map(|override_| override_.into())
+                .collect(),
+            fingerprint: configuration.fingerprint.map(|value| value.into()),
+            deduplicate: configuration.deduplicate.unwrap_or(false),
+            runtime_layers: configuration.runtime_layers.unwrap_or(false),
+            debug: configuration.debug.unwrap_or(false),
+            configuration_files: vec![],
+        }
+    }
+}
+
+impl From<configuration::PatternLanguage> for model::PatternLanguage {
+    fn from(pattern_language: configuration::PatternLanguage) -> Self {
+        match pattern_language {
+            configuration::PatternLanguage::Gitignore => model::PatternLanguage::Gitignore,
+            configuration::PatternLanguage::GitignoreFaithful => {
+                model::PatternLanguage::GitignoreFaithful
+            }
+            configuration::PatternLanguage::Glob => model::PatternLanguage::Glob,
+            configuration::PatternLanguage::Regex => model::PatternLanguage::Regex,
+        }
+    }
+}
+
+impl From<configuration::Order> for model::Order {
+    fn from(order: configuration::Order) -> Self {
+        match order {
+            configuration::Order::Name(configuration::OrderName::CaseInsensitive) => {
+                model::Order::CaseInsensitive
+            }
+            configuration::Order::Name(configuration::OrderName::Natural) => model::Order::Natural,
+            configuration::Order::Name(configuration::OrderName::Path) => model::Order::Path,
+            configuration::Order::List { list } => model::Order::List(
+                list.iter()
+                    .map(|path| get_components(path).join("/"))
+                    .collect(),
+            ),
+            configuration::Order::Capture { capture } => model::Order::Capture(capture),
+        }
+    }
+}
+
+impl From<configuration::StandardFilters> for model::StandardFilters {
+    fn from(standard_filters: configuration::StandardFilters) -> Self {
+        match standard_filters {
+            configuration::StandardFilters::All(value) => model::StandardFilters {
+                gitignore: value,
+                git_exclude: value,
+                git_global: value,
+                ignore: value,
+                hidden: value,
+            },
+            configuration::StandardFilters::Each {
+                gitignore,
+                git_exclude,
+                git_global,
+                ignore,
+                hidden,
+            } => model::StandardFilters {
+                gitignore: gitignore.unwrap_or(false),
+                git_exclude: git_exclude.unwrap_or(false),
+                git_global: git_global.unwrap_or(false),
+                ignore: ignore.unwrap_or(false),
+                hidden: hidden.unwrap_or(false),
+            },
+        }
+    }
+}
+
+impl From<configuration::MetadataFilters> for model::MetadataFilters {
+    fn from(metadata_filters: configuration::MetadataFilters) -> Self {
+        model::MetadataFilters {
+            max_size: metadata_filters.max_size,
+            max_depth: metadata_filters.max_depth,
+            content: metadata_filters.content.map(|value| value.into()),
+            modified_after: metadata_filters.modified_after.map(|value| value.0),
+            modified_before: metadata_filters.modified_before.map(|value| value.0),
+        }
+    }
+}
+
+impl From<configuration::Content> for model::Content {
+    fn from(content: configuration::Content) -> Self {
+        match content {
+            configuration::Content::Binary => model::Content::Binary,
+            configuration::Content::Text => model::Content::Text,
+        }
+    }
+}
+
+impl From<configuration::Symlinks> for model::Symlinks {
+    fn from(symlinks: configuration::Symlinks) -> Self {
+        match symlinks {
+            configuration::Symlinks::Follow => model::Symlinks::Follow,
+            configuration::Symlinks::FollowWithinBaseFolder => {
+                model::Symlinks::FollowWithinBaseFolder
+            }
+            configuration::Symlinks::Keep => model::Symlinks::Keep,
+        }
+    }
+}
+
+impl From<configuration::OversizedFiles> for model::OversizedFiles {
+    fn from(oversized_files: configuration::OversizedFiles) -> Self {
+        match oversized_files {
+            configuration::OversizedFiles::Fail => model::OversizedFiles::Fail,
+            configuration::OversizedFiles::Skip => model::OversizedFiles::Skip,
+        }
+    }
+}
+
+impl From<configuration::SpecialFiles> for model::SpecialFiles {
+    fn from(special_files: configuration::SpecialFiles) -> Self {
+        match special_files {
+            configuration::SpecialFiles::Reject => model::SpecialFiles::Reject,
+            configuration::SpecialFiles::Skip => model::SpecialFiles::Skip,
+        }
+    }
+}
+
+impl From<configuration::UnreadableFiles> for model::UnreadableFiles {
+    fn from(unreadable_files: configuration::UnreadableFiles) -> Self {
+        match unreadable_files {
+            configuration::UnreadableFiles::Fail => model::UnreadableFiles::Fail,
+            configuration::UnreadableFiles::Skip => model::UnreadableFiles::Skip,
+        }
+    }
+}
+
+impl From<configuration::Template> for model::Template {
+    fn from(template: configuration::Template) -> Self {
+        match template {
+            configuration::Template::Default {
+                initializer,
+                identifiers,
+            } => model::Template::Default {
+                initializer: initializer.map(|value| value.0),
+                identifiers: identifiers.unwrap_or(true),
+            },
+            configuration::Template::Visitors(visitors) => model::Template::Visitors(
+                visitors.into_iter().map(|visitor| visitor.into()).collect(),
+            ),
+        }
+    }
+}
+
+impl From<configuration::CustomVisitor> for model::CustomVisitor {
+    fn from(visitor: configuration::CustomVisitor) -> Self {
+        model::CustomVisitor {
+            visit_base: visitor.visit_base.map(|value| value.0),
+            visit_folder: visitor.visit_folder.map(|value| value.0),
+            visit_file: visitor.visit_file.0,
+        }
+    }
+}
+
+impl From<configuration::VirtualFile> for model::VirtualFile {
+    fn from(file: configuration::VirtualFile) -> Self {
+        model::VirtualFile {
+            path: get_components(&file.path),
+            contents: file.contents,
+            substitute_variables: file.substitute_variables.unwrap_or(false),
+        }
+    }
+}
+
+impl From<configuration::Rewrite> for model::Rewrite {
+    fn from(rewrite: configuration::Rewrite) -> Self {
+        model::Rewrite {
+            paths: rewrite.paths,
+            strip_prefix: get_components(&rewrite.strip_prefix.unwrap_or_default()),
+            rename: rewrite.rename.map(|rename| model::Rename {
+                regex: rename.regex,
+                replacement: rename.replacement,
+            }),
+            extensions: rewrite.extensions.unwrap_or_default(),
+            add_prefix: get_components(&rewrite.add_prefix.unwrap_or_default()),
+        }
+    }
+}
+
+impl From<configuration::Override> for model::Override {
+    fn from(override_: configuration::Override) -> Self {
+        model::Override {
+            transform: override_.transform.map(|transform| {
+                configuration::Transform {
+                    paths: override_.paths.clone(),
+                    command: transform.command,
+                    encoding: transform.encoding,
+                    strip_bom: transform.strip_bom,
+                    substitute_variables: transform.substitute_variables,
+                    line_endings: transform.line_endings,
+                    trim_trailing_whitespace: transform.trim_trailing_whitespace,
+                    minify_json: transform.minify_json,
+                }
+                .into()
+            }),
+            paths: override_.paths,
+            initializer: override_.initializer.map(|value| value.0),
+            fields: override_
+                .fields
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(field, value)| (field, value.0))
+                .collect(),
+        }
+    }
+}
+
+impl From<configuration::Transform> for model::Transform {
+    fn from(transform: configuration::Transform) -> Self {
+        model::Transform {
+            paths: transform.paths,
+            command: transform.command,
+            encoding: transform.encoding.map(|value| value.into()),
+            strip_bom: transform.strip_bom.unwrap_or(false),
+            substitute_variables: transform.substitute_variables.unwrap_or(false),
+            line_endings: transform.line_endings.map(|value| value.into()),
+            trim_trailing_whitespace: transform.trim_trailing_whitespace.unwrap_or(false),
+            minify_json: transform.minify_json.unwrap_or(false),
+        }
+    }
+}
+
+impl From<configuration::Fingerprint> for model::Fingerprint {
+    fn from(fingerprint: configuration::Fingerprint) -> Self {
+        model::Fingerprint {
+            length: fingerprint.length.unwrap_or(8),
+            rewrite_references: fingerprint.rewrite_references,
+        }
+    }
+}
+
+impl From<configuration::Encoding> for model::Encoding {
+    fn from(encoding: configuration::Encoding) -> Self {
+        match encoding {
+            configuration::Encoding::Auto => model::Encoding::Auto,
+            configuration::Encoding::Latin1 => model::Encoding::Latin1,
+            configuration::Encoding::Utf8 => model::Encoding::Utf8,
+            configuration::Encoding::Utf16Be => model::Encoding::Utf16Be,
+            configuration::Encoding::Utf16Le => model::Encoding::Utf16Le,
+        }
+    }
+}
+
+impl From<configuration::LineEndings> for model::LineEndings {
+    fn from(line_endings: configuration::LineEndings) -> Self {
+        match line_endings {
+            configuration::LineEndings::Crlf => model::LineEndings::Crlf,
+            configuration::LineEndings::Lf => model::LineEndings::Lf,
+        }
+    }
+}
+
+fn get_components(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|component| !component.is_empty())
+        .map(|component| component.into())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn main(string: &str) -> Result<model::Configuration, de::Error> {
+        super::main(toml::from_str(string)?)
+    }
+    use std::collections;
+    use std::path;
+    use std::time;
+
+    #[test]
+    fn handles_valid_configuration_with_required_fields_only_using_defaults() {
+        let actual = main("paths = '/a/b/**'");
+
+        let actual = actual.unwrap();
+        let expected = model::Configuration {
+            sources: vec![model::Source {
+                paths: "/a/b/**".into(),
+                base_folder: path::PathBuf::new(),
+                root_folder_variable: "CARGO_MANIFEST_DIR".into(),
+                mount: vec![],
+                layer: None,
+                cfg: None,
+            }],
+            virtual_files: vec![],
+            pattern_syntax: model::PatternSyntax::default(),
+            standard_filters: model::StandardFilters::default(),
+            metadata_filters: model::MetadataFilters::default(),
+            oversized_files: model::OversizedFiles::Fail,
+            symlinks: model::Symlinks::Keep,
+            special_files: model::SpecialFiles::Reject,
+            unreadable_files: model::UnreadableFiles::Fail,
+            required: vec![],
+            min_files: None,
+            max_files: None,
+            require_pattern_matches: false,
+            rewrites: vec![],
+            aliases: collections::BTreeMap::new(),
+            captures: None,
+            variants: collections::BTreeMap::new(),
+            order: model::Order::Path,
+            index_lock: None,
+            groups: collections::BTreeMap::new(),
+            template: model::Template::Default {
+                initializer: None,
+                identifiers: true,
+            },
+            transforms: vec![],
+            overrides: vec![],
+            fingerprint: None,
+            deduplicate: false,
+            runtime_layers: false,
+            debug: false,
+            configuration_files: vec![],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_optional_fields() {
+        let actual = main(
+            "
+paths = '/my/assets/**'
+base_folder = 'my_base'
+root_folder_variable = 'MY_ROOT_FOLDER'
+pattern_syntax = 'glob'
+case_insensitive = true
+standard_filters = true
+metadata_filters = { max_size = 5_000_000, max_depth = 3, content = 'text', modified_after = 2024-01-02T03:04:05Z }
+oversized_files = 'skip'
+symlinks = 'follow_within_base_folder'
+special_files = 'skip'
+unreadable_files = 'skip'
+required = ['index.html', 'favicon.ico']
+min_files = 2
+max_files = 100
+require_pattern_matches = true
+aliases = { 'home.html' = 'index.html' }
+captures = '^(?<name>[^.]+)'
+variants = { gzip = '.gz', map = '.map' }
+order = 'natural'
+index_lock = 'assets.lock'
+groups = { images = '*.png', scripts = '*.js' }
+template.initializer = 'my_macro'
+template.identifiers = false
+fingerprint.length = 12
+fingerprint.rewrite_references = '*.css'
+deduplicate = true
+runtime_layers = true
+debug = true
+",
+        );
+
+        let actual = actual.unwrap();
+        let expected = model::Configuration {
+            sources: vec![model::Source {
+                paths: "/my/assets/**".into(),
+                base_folder: "my_base".into(),
+                root_folder_variable: "MY_ROOT_FOLDER".into(),
+                mount: vec![],
+                layer: None,
+                cfg: None,
+            }],
+            virtual_files: vec![],
+            pattern_syntax: model::PatternSyntax {
+                language: model::PatternLanguage::Glob,
+                case_insensitive: true,
+            },
+            standard_filters: model::StandardFilters {
+                gitignore: true,
+                git_exclude: true,
+                git_global: true,
+                ignore: true,
+                hidden: true,
+            },
+            metadata_filters: model::MetadataFilters {
+                max_size: Some(5_000_000),
+                max_depth: Some(3),
+                content: Some(model::Content::Text),
+                modified_after: Some(time::UNIX_EPOCH + time::Duration::from_secs(1_704_164_645)),
+                modified_before: None,
+            },
+            oversized_files: model::OversizedFiles::Skip,
+            symlinks: model::Symlinks::FollowWithinBaseFolder,
+            special_files: model::SpecialFiles::Skip,
+            unreadable_files: model::UnreadableFiles::Skip,
+            required: vec!["index.html".into(), "favicon.ico".into()],
+            min_files: Some(2),
+            max_files: Some(100),
+            require_pattern_matches: true,
+            rewrites: vec![],
+            aliases: [("home.html".into(), "index.html".into())].into(),
+            captures: Some("^(?<name>[^.]+)".into()),
+            variants: [("gzip".into(), ".gz".into()), ("map".into(), ".map".into())].into(),
+            order: model::Order::Natural,
+            index_lock: Some("assets.lock".into()),
+            groups: [
+                ("images".into(), "*.png".into()),
+                ("scripts".into(), "*.js".into()),
+            ]
+            .into(),
+            template: model::Template::Default {
+                initializer: Some(syn::parse_str("my_macro").unwrap()),
+                identifiers: false,
+            },
+            transforms: vec![],
+            overrides: vec![],
+            fingerprint: Some(model::Fingerprint {
+                length: 12,
+                rewrite_references: Some("*.css".into()),
+            }),
+            deduplicate: true,
+            runtime_layers: true,
+            debug: true,
+            configuration_files: vec![],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_sources() {
+        let actual = main(
+            "
+paths = '/a/**'
+base_folder = 'my_base'
+
+[[source]]
+paths = '/dist/**'
+base_folder = 'frontend'
+mount = 'static/'
+
+[[source]]
+paths = '/build/**'
+root_folder_variable = 'MY_ROOT_FOLDER'
+mount = '/docs/api'
+layer = 'dark'
+cfg = 'feature = \"docs\"'
+",
+        );
+
+        let actual = actual.unwrap().sources;
+        let expected = vec![
+            model::Source {
+                paths: "/a/**".into(),
+                base_folder: "my_base".into(),
+                root_folder_variable: "CARGO_MANIFEST_DIR".into(),
+                mount: vec![],
+                layer: None,
+                cfg: None,
+            },
+            model::Source {
+                paths: "/dist/**".into(),
+                base_folder: "frontend".into(),
+                root_folder_variable: "CARGO_MANIFEST_DIR".into(),
+                mount: vec!["static".into()],
+                layer: None,
+                cfg: None,
+            },
+            model::Source {
+                paths: "/build/**".into(),
+                base_folder: "my_base".into(),
+                root_folder_variable: "MY_ROOT_FOLDER".into(),
+                mount: vec!["docs".into(), "api".into()],
+                layer: Some("dark".into()),
+                cfg: Some(syn::parse_quote! { feature = "docs" }),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_each_standard_filter() {
+        let actual = main(
+            "
+paths = ''
+standard_filters = { gitignore = true, hidden = true }
+",
+        );
+
+        let actual = actual.unwrap().standard_filters;
+        let expected = model::StandardFilters {
+            gitignore: true,
+            git_exclude: false,
+            git_global: false,
+            ignore: false,
+            hidden: true,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_template_visitors() {
+        let actual = main(
+            "
+paths = ''
+template = [
+  { visit_file = 'file' },
+  { visit_base = 'my_base', visit_folder = 'my_folder', visit_file = 'my_file' },
+]
+",
+        );
+
+        let actual = actual.unwrap().template;
+        let expected = model::Template::Visitors(vec![
+            model::CustomVisitor {
+                visit_base: None,
+                visit_folder: None,
+                visit_file: syn::parse_str("file").unwrap(),
+            },
+            model::CustomVisitor {
+                visit_base: Some(syn::parse_str("my_base").unwrap()),
+                visit_folder: Some(syn::parse_str("my_folder").unwrap()),
+                visit_file: syn::parse_str("my_file").unwrap(),
+            },
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_rewrites() {
+        let actual = main(
+            "
+paths = ''
+
+[[rewrite]]
+strip_prefix = 'frontend/dist/'
+add_prefix = '/static'
+
+[[rewrite]]
+paths = '*.htm'
+rename = { regex = '^index', replacement = 'home' }
+extensions = { htm = 'html' }
+",
+        );
+
+        let actual = actual.unwrap().rewrites;
+        let expected = vec![
+            model::Rewrite {
+                paths: None,
+                strip_prefix: vec!["frontend".into(), "dist".into()],
+                rename: None,
+                extensions: collections::BTreeMap::new(),
+                add_prefix: vec!["static".into()],
+            },
+            model::Rewrite {
+                paths: Some("*.htm".into()),
+                strip_prefix: vec![],
+                rename: Some(model::Rename {
+                    regex: "^index".into(),
+                    replacement: "home".into(),
+                }),
+                extensions: [("htm".into(), "html".into())].into(),
+                add_prefix: vec![],
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_virtual_files_only() {
+        let actual = main(
+            "
+[[file]]
+path = '/version.txt'
+contents = '${cargo:pkg_version}'
+substitute_variables = true
+
+[[file]]
+path = 'robots.txt'
+contents = ''
+",
+        );
+
+        let actual = actual.unwrap();
+        let actual = (actual.sources, actual.virtual_files);
+        let expected = (
+            vec![],
+            vec![
+                model::VirtualFile {
+                    path: vec!["version.txt".into()],
+                    contents: "${cargo:pkg_version}".into(),
+                    substitute_variables: true,
+                },
+                model::VirtualFile {
+                    path: vec!["robots.txt".into()],
+                    contents: String::new(),
+                    substitute_variables: false,
+                },
+            ],
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_virtual_file_with_empty_path_it_errs() {
+        let actual = main(
+            "
+[[file]]
+path = '/'
+contents = ''
+",
+        );
+
+        let actual = actual.is_err();
+        assert!(actual);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_transforms() {
+        let actual = main(
+            "
+paths = ''
+
+[[transform]]
+paths = '*.txt'
+encoding = 'utf-16le'
+strip_bom = true
+substitute_variables = true
+line_endings = 'lf'
+trim_trailing_whitespace = true
+
+[[transform]]
+paths = '*.json'
+command = ['minify', '--json']
+minify_json = true
+",
+        );
+
+        let actual = actual.unwrap().transforms;
+        let expected = vec![
+            model::Transform {
+                paths: "*.txt".into(),
+                command: None,
+                encoding: Some(model::Encoding::Utf16Le),
+                strip_bom: true,
+                substitute_variables: true,
+                line_endings: Some(model::LineEndings::Lf),
+                trim_trailing_whitespace: true,
+                minify_json: false,
+            },
+            model::Transform {
+                paths: "*.json".into(),
+                command: Some(vec!["minify".into(), "--json".into()]),
+                encoding: None,
+                strip_bom: false,
+                substitute_variables: false,
+                line_endings: None,
+                trim_trailing_whitespace: false,
+                minify_json: true,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_each_order() {
+        let actual = [
+            "order = 'case_insensitive'",
+            "order = 'path'",
+            "order.list = ['/b/', 'a/c']",
+            "order.capture = 'version'",
+        ]
+        .map(|order| main(&format!("paths = ''\n{order}")).unwrap().order);
+
+        let expected = [
+            model::Order::CaseInsensitive,
+            model::Order::Path,
+            model::Order::List(vec!["b".into(), "a/c".into()]),
+            model::Order::Capture("version".into()),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_overrides() {
+        let actual = main(
+            "
+paths = ''
+
+[[override]]
+paths = '*.json'
+transform.minify_json = true
+
+[[override]]
+paths = '*.svg'
+initializer = 'svg_asset'
+fields = { contents_str = '\"\"' }
+",
+        );
+
+        let actual = actual.unwrap().overrides;
+        let expected = vec![
+            model::Override {
+                paths: "*.json".into(),
+                initializer: None,
+                transform: Some(model::Transform {
+                    paths: "*.json".into(),
+                    command: None,
+                    encoding: None,
+                    strip_bom: false,
+                    substitute_variables: false,
+                    line_endings: None,
+                    trim_trailing_whitespace: false,
+                    minify_json: true,
+                }),
+                fields: collections::BTreeMap::new(),
+            },
+            model::Override {
+                paths: "*.svg".into(),
+                initializer: Some(syn::parse_str("svg_asset").unwrap()),
+                transform: None,
+                fields: [("contents_str".into(), syn::parse_quote! { "" })].into(),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_valid_configuration_with_fingerprint_using_defaults() {
+        let actual = main(
+            "
+paths = ''
+
+[fingerprint]
+",
+        );
+
+        let actual = actual.unwrap().fingerprint;
+        let expected = Some(model::Fingerprint {
+            length: 8,
+            rewrite_references: None,
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_fingerprint_length_out_of_range_it_errs() {
+        let actual = main(
+            "
+paths = ''
+fingerprint.length = 65
+",
+        );
+
+        let actual = actual.unwrap_err().to_string();
+        let expected = "field `length` of `fingerprint` must be from 1 to 64, not 65\n";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_ill_formed_configuration_it_errs() {
+        let actual = main("paths = #");
+
+        let actual = actual.is_err();
+        assert!(actual);
+    }
+
+    #[test]
+    fn given_required_field_is_missing_it_errs() {
+        let actual = main("");
+
+        let actual = actual.is_err();
+        assert!(actual);
+    }
+
+    #[test]
+    fn given_unknown_field_it_errs() {
+        let actual = main(
+            "
+paths = 'abc'
+unknown = ''
+",
+        );
+
+        let actual = actual.is_err();
+        assert!(actual);
+    }
+
+    #[test]
+    fn given_invalid_type_it_errs_with_field() {
+        let actual = main(
+            "
+paths = ''
+deduplicate = 'yes'
+",
+        );
+
+        let actual = actual.unwrap_err().to_string();
+        let expected = "invalid type: string \"yes\", expected a boolean\nin `deduplicate`\n";
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::model;
 use syn::parse;
 
-impl parse::Parse for model::Type<()> {
+impl parse::Parse for model::Type<syn::Type> {
     fn parse(item: parse::ParseStream) -> syn::Result<Self> {
         item.call(syn::Attribute::parse_outer)?;
         item.parse::<syn::Visibility>()?;
@@ -17,7 +17,7 @@ impl parse::Parse for model::Type<()> {
     }
 }
 
-fn parse_structure(item: parse::ParseStream) -> syn::Result<model::Type<()>> {
+fn parse_structure(item: parse::ParseStream) -> syn::Result<model::Type<syn::Type>> {
     let derive_input = item.parse::<syn::DeriveInput>()?;
 
     let raw_structure = match derive_input.data {
@@ -32,13 +32,13 @@ fn parse_structure(item: parse::ParseStream) -> syn::Result<model::Type<()>> {
             named_fields
                 .named
                 .into_iter()
-                .filter_map(|named_field| named_field.ident.map(|field| (field, ())))
+                .filter_map(|named_field| Some((named_field.ident?, named_field.ty)))
                 .collect(),
         ),
 
-        syn::Fields::Unnamed(fields) => {
-            model::TypeStructure::TupleFields(fields.unnamed.iter().map(|_| ()).collect())
-        }
+        syn::Fields::Unnamed(fields) => model::TypeStructure::TupleFields(
+            fields.unnamed.into_iter().map(|field| field.ty).collect(),
+        ),
     };
 
     Ok(model::Type {
@@ -47,16 +47,16 @@ fn parse_structure(item: parse::ParseStream) -> syn::Result<model::Type<()>> {
     })
 }
 
-fn parse_type_alias(item: parse::ParseStream) -> syn::Result<model::Type<()>> {
+fn parse_type_alias(item: parse::ParseStream) -> syn::Result<model::Type<syn::Type>> {
     item.parse::<syn::Token![type]>()?;
     let name = item.parse::<syn::Ident>()?;
     item.parse::<syn::Token![=]>()?;
-    item.parse::<syn::Type>()?;
+    let type_ = item.parse::<syn::Type>()?;
     item.parse::<syn::Token![;]>()?;
 
     Ok(model::Type {
         name,
-        structure: model::TypeStructure::TypeAlias(()),
+        structure: model::TypeStructure::TypeAlias(type_),
     })
 }
 
@@ -66,7 +66,7 @@ mod tests {
 
     #[test]
     fn handles_unit() {
-        let actual = syn::parse_str::<model::Type<()>>("pub struct MyUnit;");
+        let actual = syn::parse_str::<model::Type<syn::Type>>("pub struct MyUnit;");
 
         let actual = actual.unwrap();
         let expected = model::Type {
@@ -78,19 +78,20 @@ mod tests {
 
     #[test]
     fn handles_type_alias() {
-        let actual = syn::parse_str::<model::Type<()>>("pub type MyTypeAlias = &'static str;");
+        let actual =
+            syn::parse_str::<model::Type<syn::Type>>("pub type MyTypeAlias = &'static str;");
 
         let actual = actual.unwrap();
         let expected = model::Type {
             name: quote::format_ident!("MyTypeAlias"),
-            structure: model::TypeStructure::TypeAlias(()),
+            structure: model::TypeStructure::TypeAlias(syn::parse_quote! { &'static str }),
         };
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn handles_named_fields() {
-        let actual = syn::parse_str::<model::Type<()>>(
+        let actual = syn::parse_str::<model::Type<syn::Type>>(
             "pub struct MyNamedFields {
     ab: String,
     bc: &'static str,
@@ -101,8 +102,11 @@ mod tests {
         let expected = model::Type {
             name: quote::format_ident!("MyNamedFields"),
             structure: model::TypeStructure::NamedFields(vec![
-                (quote::format_ident!("ab"), ()),
-                (quote::format_ident!("bc"), ()),
+                (quote::format_ident!("ab"), syn::parse_quote! { String }),
+                (
+                    quote::format_ident!("bc"),
+                    syn::parse_quote! { &'static str },
+                ),
             ]),
         };
         assert_eq!(actual, expected);
@@ -110,20 +114,24 @@ mod tests {
 
     #[test]
     fn handles_tuple_fields() {
-        let actual =
-            syn::parse_str::<model::Type<()>>("pub struct MyTupleFields(usize, &'static str);");
+        let actual = syn::parse_str::<model::Type<syn::Type>>(
+            "pub struct MyTupleFields(usize, &'static str);",
+        );
 
         let actual = actual.unwrap();
         let expected = model::Type {
             name: quote::format_ident!("MyTupleFields"),
-            structure: model::TypeStructure::TupleFields(vec![(), ()]),
+            structure: model::TypeStructure::TupleFields(vec![
+                syn::parse_quote! { usize },
+                syn::parse_quote! { &'static str },
+            ]),
         };
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn given_unexpected_item_it_errs() {
-        let actual = syn::parse_str::<model::Type<()>>("pub fn do_it() {}");
+        let actual = syn::parse_str::<model::Type<syn::Type>>("pub fn do_it() {}");
 
         let actual = actual.unwrap_err().to_string();
         assert_eq!(actual, "expected `struct` or `type`");
@@ -131,7 +139,7 @@ mod tests {
 
     #[test]
     fn given_valid_but_unexpected_derive_input_it_errs() {
-        let actual = syn::parse_str::<model::Type<()>>(
+        let actual = syn::parse_str::<model::Type<syn::Type>>(
             "pub union MyUnion {
     integer: u32,
     floating: f32,
@@ -0,0 +1,75 @@
+use super::configuration;
+use super::deserialize_syntax;
+
+impl<'a> serde::Deserialize<'a> for configuration::Predicate {
+    fn deserialize<T>(deserializer: T) -> Result<configuration::Predicate, T::Error>
+    where
+        T: serde::Deserializer<'a>,
+    {
+        deserialize_syntax::main(
+            deserializer,
+            "a `cfg` predicate (like `unix` or `feature = \"x\"`)",
+        )
+        .map(configuration::Predicate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(test)]
+    mod deserialize {
+        use super::*;
+        use std::cmp;
+
+        #[derive(cmp::PartialEq, Debug, serde::Deserialize)]
+        struct Binding {
+            name: configuration::Predicate,
+        }
+
+        #[test]
+        fn handles_name() {
+            let actual: Result<Binding, _> = toml::from_str("name = 'debug_assertions'");
+
+            let actual = actual.unwrap();
+            let expected = Binding {
+                name: configuration::Predicate(syn::parse_quote! { debug_assertions }),
+            };
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_name_value() {
+            let actual: Result<Binding, _> = toml::from_str(r#"name = 'feature = "full"'"#);
+
+            let actual = actual.unwrap();
+            let expected = Binding {
+                name: configuration::Predicate(syn::parse_quote! { feature = "full" }),
+            };
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_list() {
+            let actual: Result<Binding, _> =
+                toml::from_str("name = 'all(unix, not(target_os = \"macos\"))'");
+
+            let actual = actual.unwrap();
+            let expected = Binding {
+                name: configuration::Predicate(
+                    syn::parse_quote! { all(unix, not(target_os = "macos")) },
+                ),
+            };
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_invalid_value_it_errs() {
+            let actual: Result<Binding, _> = toml::from_str("name = 'a b'");
+
+            let actual = actual.is_err();
+            assert!(actual);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::configuration;
+use serde::de;
+use std::time;
+
+impl<'a> serde::Deserialize<'a> for configuration::Timestamp {
+    fn deserialize<T>(deserializer: T) -> Result<configuration::Timestamp, T::Error>
+    where
+        T: serde::Deserializer<'a>,
+    {
+        let datetime = toml::value::Datetime::deserialize(deserializer)?;
+        match get_system_time(&datetime) {
+            None => Err(de::Error::invalid_value(
+                de::Unexpected::Other("datetime"),
+                &"a date (like `2024-01-31` or `2024-01-31T12:00:00Z`)",
+            )),
+            Some(value) => Ok(configuration::Timestamp(value)),
+        }
+    }
+}
+
+// A local date or time is taken as UTC, so builds agree across time zones.
+fn get_system_time(datetime: &toml::value::Datetime) -> Option<time::SystemTime> {
+    let date = datetime.date?;
+    let (hour, minute, second, nanosecond) = datetime.time.map_or((0, 0, 0, 0), |time| {
+        (
+            time.hour,
+            time.minute,
+            time.second.unwrap_or(0),
+            time.nanosecond.unwrap_or(0),
+        )
+    });
+    let offset_minutes = match datetime.offset {
+        None | Some(toml::value::Offset::Z) => 0,
+        Some(toml::value::Offset::Custom { minutes }) => i64::from(minutes),
+    };
+
+    let days = get_days_since_epoch(
+        i64::from(date.year),
+        i64::from(date.month),
+        i64::from(date.day),
+    );
+    let seconds = days * 86_400 + i64::from(hour) * 3_600 + i64::from(minute) * 60
+        - offset_minutes * 60
+        + i64::from(second);
+    let duration = time::Duration::new(seconds.unsigned_abs(), 0);
+    let time = if seconds < 0 {
+        time::UNIX_EPOCH.checked_sub(duration)
+    } else {
+        time::UNIX_EPOCH.checked_add(duration)
+    }?;
+    time.checked_add(time::Duration::from_nanos(u64::from(nanosecond)))
+}
+
+// Counts days in the proleptic Gregorian calendar, with eras of 400 years that
+// start on March 1 so leap days fall at their end.
+fn get_days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(test)]
+    mod deserialize {
+        use super::*;
+        use std::cmp;
+
+        #[derive(cmp::PartialEq, Debug, serde::Deserialize)]
+        struct Binding {
+            name: configuration::Timestamp,
+        }
+
+        #[test]
+        fn handles_date() {
+            let actual: Result<Binding, _> = toml::from_str("name = 2024-03-01");
+
+            let actual = actual.unwrap();
+            let expected = Binding {
+                name: configuration::Timestamp(
+                    time::UNIX_EPOCH + time::Duration::from_secs(1_709_251_200),
+                ),
+            };
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_offset_datetime() {
+            let actual: Result<Binding, _> = toml::from_str("name = 2000-02-29T12:30:00+02:00");
+
+            let actual = actual.unwrap();
+            let expected = Binding {
+                name: configuration::Timestamp(
+                    time::UNIX_EPOCH + time::Duration::from_secs(951_820_200),
+                ),
+            };
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_time_only_it_errs() {
+            let actual: Result<Binding, _> = toml::from_str("name = 12:30:00");
+
+            let actual = actual.is_err();
+            assert!(actual);
+        }
+
+        #[test]
+        fn given_string_it_errs() {
+            let actual: Result<Binding, _> = toml::from_str("name = '2024-03-01'");
+
+            let actual = actual.is_err();
+            assert!(actual);
+        }
+    }
+}
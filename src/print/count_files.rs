@@ -4,7 +4,7 @@ pub fn main(forest: &model::Forest) -> usize {
     forest
         .values()
         .map(|tree| match tree {
-            model::Tree::File(_) => 1,
+            model::Tree::File(file) => usize::from(file.alias_of.is_none()),
             model::Tree::Folder(model::Folder { forest, .. }) => main(forest),
         })
         .sum()
@@ -18,7 +18,10 @@ mod tests {
     fn handles() {
         let actual = main(
             &[
-                ("0".into(), model::Tree::File(model::stubs::file())),
+                (
+                    "0".into(),
+                    model::Tree::File(Box::new(model::stubs::file())),
+                ),
                 (
                     "1".into(),
                     model::Tree::Folder(model::Folder {
@@ -26,13 +29,19 @@ mod tests {
                             (
                                 "2".into(),
                                 model::Tree::Folder(model::Folder {
-                                    forest: [("3".into(), model::Tree::File(model::stubs::file()))]
-                                        .into_iter()
-                                        .collect(),
+                                    forest: [(
+                                        "3".into(),
+                                        model::Tree::File(Box::new(model::stubs::file())),
+                                    )]
+                                    .into_iter()
+                                    .collect(),
                                     ..model::stubs::folder()
                                 }),
                             ),
-                            ("4".into(), model::Tree::File(model::stubs::file())),
+                            (
+                                "4".into(),
+                                model::Tree::File(Box::new(model::stubs::file())),
+                            ),
                         ]
                         .into_iter()
                         .collect(),
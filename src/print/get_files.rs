@@ -0,0 +1,67 @@
+use crate::forest_files;
+use crate::model;
+
+pub fn main(view: &model::View) -> Vec<&model::File> {
+    // An alias shares the entry of its target.
+    let mut files = forest_files::get_files(&view.forest)
+        .into_iter()
+        .filter(|file| file.alias_of.is_none())
+        .collect::<Vec<_>>();
+    files.extend(view.layers.iter().flat_map(|layers| &layers.shadowed_files));
+    files.sort_by_key(|file| file.index);
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let view = model::View {
+            forest: [
+                (
+                    "a".into(),
+                    model::Tree::File(Box::new(model::File {
+                        index: 1,
+                        ..model::stubs::file()
+                    })),
+                ),
+                (
+                    "b".into(),
+                    model::Tree::Folder(model::Folder {
+                        forest: [(
+                            "c".into(),
+                            model::Tree::File(Box::new(model::File {
+                                index: 0,
+                                ..model::stubs::file()
+                            })),
+                        )]
+                        .into_iter()
+                        .collect(),
+                        ..model::stubs::folder()
+                    }),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            layers: Some(model::Layers {
+                names: vec!["d".into()],
+                shadowed_files: vec![model::File {
+                    index: 2,
+                    ..model::stubs::file()
+                }],
+            }),
+            ..model::stubs::view()
+        };
+
+        let actual = main(&view);
+
+        let actual = actual
+            .into_iter()
+            .map(|file| file.index)
+            .collect::<Vec<_>>();
+        let expected = vec![0, 1, 2];
+        assert_eq!(actual, expected);
+    }
+}
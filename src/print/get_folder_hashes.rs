@@ -0,0 +1,105 @@
+use crate::hash;
+use crate::model;
+
+pub fn main(forest: &model::Forest) -> Vec<(String, String)> {
+    let mut folder_hashes = vec![];
+    let hash = get_hash(&mut folder_hashes, "", forest);
+    folder_hashes.push((String::new(), hash));
+    folder_hashes.sort();
+    folder_hashes
+}
+
+fn get_hash(
+    folder_hashes: &mut Vec<(String, String)>,
+    relative_path: &str,
+    forest: &model::Forest,
+) -> String {
+    let mut listing = vec![];
+
+    for (name, tree) in forest {
+        let hash = match tree {
+            model::Tree::File(file) => file.hash.clone().unwrap_or_default(),
+
+            model::Tree::Folder(folder) => {
+                let relative_path = if relative_path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{relative_path}/{name}")
+                };
+                let hash = get_hash(folder_hashes, &relative_path, &folder.forest);
+                folder_hashes.push((relative_path, hash.clone()));
+                hash
+            }
+        };
+
+        listing.extend_from_slice(name.as_bytes());
+        listing.push(b'\0');
+        listing.extend_from_slice(hash.as_bytes());
+        listing.push(b'\n');
+    }
+
+    hash::main(&listing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_empty_set() {
+        let actual = main(&model::Forest::new());
+
+        let expected = vec![("".into(), hash::main(b""))];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_nested_folders() {
+        let actual = main(
+            &[
+                (
+                    "a".into(),
+                    model::Tree::File(Box::new(model::File {
+                        hash: Some("1".into()),
+                        ..model::stubs::file()
+                    })),
+                ),
+                (
+                    "b".into(),
+                    model::Tree::Folder(model::Folder {
+                        forest: [(
+                            "c".into(),
+                            model::Tree::Folder(model::Folder {
+                                forest: [(
+                                    "d".into(),
+                                    model::Tree::File(Box::new(model::File {
+                                        hash: Some("2".into()),
+                                        ..model::stubs::file()
+                                    })),
+                                )]
+                                .into_iter()
+                                .collect(),
+                                ..model::stubs::folder()
+                            }),
+                        )]
+                        .into_iter()
+                        .collect(),
+                        ..model::stubs::folder()
+                    }),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let c_hash = hash::main(b"d\x002\n");
+        let b_hash = hash::main(format!("c\0{c_hash}\n").as_bytes());
+        let base_hash = hash::main(format!("a\x001\nb\0{b_hash}\n").as_bytes());
+        let expected = vec![
+            ("".into(), base_hash),
+            ("b".into(), b_hash),
+            ("b/c".into(), c_hash),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
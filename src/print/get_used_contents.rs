@@ -0,0 +1,77 @@
+use crate::model;
+use std::cmp;
+
+#[derive(Clone, Copy, cmp::PartialEq, Debug, Default, Eq)]
+pub struct UsedContents {
+    pub bytes: bool,
+    pub str_: bool,
+}
+
+pub fn main(visitors: &[model::Visitor]) -> UsedContents {
+    visitors
+        .iter()
+        .flat_map(|visitor| match visitor {
+            model::Visitor::Array(model::Initializer::Default(populators)) => match populators {
+                model::TypeStructure::Unit => vec![],
+                model::TypeStructure::TypeAlias(populator) => vec![populator],
+                model::TypeStructure::NamedFields(fields) => {
+                    fields.iter().map(|(_, populator)| populator).collect()
+                }
+                model::TypeStructure::TupleFields(populators) => populators.iter().collect(),
+            },
+            _ => vec![],
+        })
+        .fold(UsedContents::default(), |used, populator| match populator {
+            model::Populator::ContentsBytes | model::Populator::GetBytes => UsedContents {
+                bytes: true,
+                ..used
+            },
+            model::Populator::ContentsStr | model::Populator::GetStr => {
+                UsedContents { str_: true, ..used }
+            }
+            model::Populator::Capture { .. }
+            | model::Populator::FingerprintedPath
+            | model::Populator::RelativePath
+            | model::Populator::Variant { .. } => used,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_default_initializer() {
+        let actual = main(&[
+            model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::NamedFields(vec![
+                    (
+                        quote::format_ident!("relative_path"),
+                        model::Populator::RelativePath,
+                    ),
+                    (quote::format_ident!("get_str"), model::Populator::GetStr),
+                ]),
+            )),
+            model::Visitor::Identifiers,
+        ]);
+
+        let expected = UsedContents {
+            bytes: false,
+            str_: true,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_macro_initializer() {
+        let actual = main(&[model::Visitor::Array(model::Initializer::Macro(
+            syn::parse_str("abc").unwrap(),
+        ))]);
+
+        let expected = UsedContents {
+            bytes: false,
+            str_: false,
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,18 +1,34 @@
 use super::print_forest;
+use super::print_groups;
+use super::print_layers;
+use super::print_shared_contents;
+use super::print_skipped_files;
 use super::print_with_debug;
 use crate::model;
 
 pub fn main(item: proc_macro2::TokenStream, view: model::View) -> proc_macro2::TokenStream {
+    let shared_contents = print_shared_contents::main(&view);
     let visits = view
         .visitors
         .iter()
         .map(|visitor| print_forest::main(&view, visitor))
         .collect::<proc_macro2::TokenStream>();
+    let layers = print_layers::main(&view);
+    let groups = print_groups::main(&view);
+    let skipped_files = print_skipped_files::main(&view);
 
     let code = quote::quote! {
         #item
 
+        #shared_contents
+
         #visits
+
+        #layers
+
+        #groups
+
+        #skipped_files
     };
 
     print_with_debug::main(view, code)
@@ -36,15 +52,19 @@ mod tests {
                 ],
                 forest: [(
                     "a.b".into(),
-                    model::Tree::File(model::File {
+                    model::Tree::File(Box::new(model::File {
                         identifier: quote::format_ident!("A_B"),
                         index: 0,
                         absolute_path: "/a.b".into(),
                         ..model::stubs::file()
-                    }),
+                    })),
                 )]
                 .into_iter()
                 .collect(),
+                shared_contents: None,
+                layers: None,
+                groups: vec![],
+                skipped_files: None,
                 debug: false,
             },
         );
@@ -1,8 +1,19 @@
 mod count_files;
+mod get_files;
+mod get_folder_hashes;
+mod get_used_contents;
 mod main;
+mod print_condition;
+mod print_configuration_files;
 mod print_forest;
+mod print_groups;
 mod print_initializer;
+mod print_layers;
 mod print_populator;
+mod print_shared_contents;
+mod print_skipped_files;
 mod print_with_debug;
 
 pub use main::main;
+pub use print_condition::main as print_condition;
+pub use print_configuration_files::main as print_configuration_files;
@@ -0,0 +1,58 @@
+// The code is wrapped in a module rather than taken apart into items, and its
+// public items are brought into scope under the condition.
+pub fn main(
+    condition: &syn::Meta,
+    module: &syn::Ident,
+    code: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote::quote! {
+        #[cfg(#condition)]
+        #[doc(hidden)]
+        mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #code
+        }
+
+        #[cfg(#condition)]
+        pub use #module::*;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let actual = main(
+            &syn::parse_quote! { all(unix, not(feature = "a")) },
+            &quote::format_ident!("__asset_0"),
+            quote::quote! {
+                pub static ASSETS: [&str; 0usize] = [];
+
+                pub mod base {}
+            },
+        );
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {
+            #[cfg(all(unix, not(feature = "a")))]
+            #[doc(hidden)]
+            mod __asset_0 {
+                #[allow(unused_imports)]
+                use super::*;
+
+                pub static ASSETS: [&str; 0usize] = [];
+
+                pub mod base {}
+            }
+
+            #[cfg(all(unix, not(feature = "a")))]
+            pub use __asset_0::*;
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,34 @@
+use std::path;
+
+// Including a file makes the compiler track it, so that changing a file the
+// configuration is read from rebuilds the crate.
+pub fn main(files: &[path::PathBuf]) -> proc_macro2::TokenStream {
+    files
+        .iter()
+        .map(|file| {
+            let file = file.to_string_lossy();
+            quote::quote! {
+                const _: &str = include_str!(#file);
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles() {
+        let actual = main(&["/a.toml".into(), "/b.patterns".into()]);
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {
+            const _: &str = include_str!("/a.toml");
+
+            const _: &str = include_str!("/b.patterns");
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,4 +1,6 @@
 use super::count_files;
+use super::get_files;
+use super::get_folder_hashes;
 use super::print_initializer;
 use crate::model;
 use std::iter;
@@ -14,14 +16,55 @@ pub fn main(view: &model::View, visitor: &model::Visitor) -> proc_macro2::TokenS
     );
 
     match visitor {
-        model::Visitor::Array(_) => {
+        model::Visitor::Array(initializer) => {
             let type_ = &view.type_;
-            let length = count_files::main(&view.forest);
-            quote::quote! { pub static ASSETS: [#type_; #length] = [#contents]; }
+            // Elements follow the file indices, which may differ from the order
+            // of paths. Files shadowed by later layers come last, for lookups by
+            // layer.
+            let files = get_files::main(view);
+            let length = files.len();
+            let contents = files
+                .into_iter()
+                .map(|file| {
+                    let element = print_initializer::main(type_, initializer, file);
+                    quote::quote! { #element, }
+                })
+                .collect::<proc_macro2::TokenStream>();
+            quote::quote! {
+                pub static ASSETS: [#type_; #length] = [#contents];
+            }
         }
 
         model::Visitor::Identifiers => quote::quote! { pub mod base { #contents } },
 
+        model::Visitor::Fingerprints => {
+            let type_ = &view.type_;
+            let folder_hashes = get_folder_hashes::main(&view.forest);
+            let bundle_hash = &folder_hashes[0].1;
+            let folder_cases = folder_hashes
+                .iter()
+                .map(|(relative_path, hash)| quote::quote! { #relative_path => Some(#hash), })
+                .collect::<proc_macro2::TokenStream>();
+
+            quote::quote! {
+                pub const BUNDLE_HASH: &str = #bundle_hash;
+
+                pub fn get_by_fingerprinted_path(fingerprinted_path: &str) -> Option<&'static #type_> {
+                    match fingerprinted_path {
+                        #contents
+                        _ => None,
+                    }
+                }
+
+                pub fn get_folder_hash(relative_path: &str) -> Option<&'static str> {
+                    match relative_path {
+                        #folder_cases
+                        _ => None,
+                    }
+                }
+            }
+        }
+
         model::Visitor::Custom(model::CustomVisitor {
             visit_base: None, ..
         }) => contents,
@@ -54,10 +97,8 @@ fn print_forest(context: &Context, forest: &model::Forest) -> proc_macro2::Token
 
 fn print_file(context: &Context, name: &str, file: &model::File) -> proc_macro2::TokenStream {
     match context.visitor {
-        model::Visitor::Array(initializer) => {
-            let element = print_initializer::main(context.type_, initializer, file);
-            quote::quote! { #element, }
-        }
+        // The array is printed in order of file indices instead.
+        model::Visitor::Array(_) => proc_macro2::TokenStream::new(),
 
         model::Visitor::Identifiers => {
             let identifier = &file.identifier;
@@ -71,6 +112,15 @@ fn print_file(context: &Context, name: &str, file: &model::File) -> proc_macro2:
             }
         }
 
+        // An alias shares the fingerprinted path of its target.
+        model::Visitor::Fingerprints if file.alias_of.is_some() => proc_macro2::TokenStream::new(),
+
+        model::Visitor::Fingerprints => {
+            let fingerprinted_path = file.fingerprinted_path.as_deref().unwrap_or_default();
+            let index = file.index;
+            quote::quote! { #fingerprinted_path => Some(&ASSETS[#index]), }
+        }
+
         model::Visitor::Custom(model::CustomVisitor { visit_file, .. }) => {
             let id = &file.identifier;
             let index = file.index;
@@ -91,7 +141,7 @@ fn print_folder(context: &Context, name: &str, folder: &model::Folder) -> proc_m
     );
 
     match context.visitor {
-        model::Visitor::Array(_) => contents,
+        model::Visitor::Array(_) | model::Visitor::Fingerprints => contents,
 
         model::Visitor::Identifiers => {
             let identifier = &folder.identifier;
@@ -118,6 +168,8 @@ fn print_folder(context: &Context, name: &str, folder: &model::Folder) -> proc_m
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hash;
+    use std::collections;
 
     #[test]
     fn handles_array() {
@@ -127,20 +179,20 @@ mod tests {
                 forest: [
                     (
                         "0".into(),
-                        model::Tree::File(model::File {
+                        model::Tree::File(Box::new(model::File {
                             relative_path: "a".into(),
                             ..model::stubs::file()
-                        }),
+                        })),
                     ),
                     (
                         "1".into(),
                         model::Tree::Folder(model::Folder {
                             forest: [(
                                 "2".into(),
-                                model::Tree::File(model::File {
+                                model::Tree::File(Box::new(model::File {
                                     relative_path: "b/c".into(),
                                     ..model::stubs::file()
-                                }),
+                                })),
                             )]
                             .into_iter()
                             .collect(),
@@ -195,19 +247,19 @@ mod tests {
                     forest: [
                         (
                             "0".into(),
-                            model::Tree::File(model::File {
+                            model::Tree::File(Box::new(model::File {
                                 identifier: quote::format_ident!("A"),
                                 index: 1,
                                 ..model::stubs::file()
-                            }),
+                            })),
                         ),
                         (
                             "1".into(),
-                            model::Tree::File(model::File {
+                            model::Tree::File(Box::new(model::File {
                                 identifier: quote::format_ident!("BC"),
                                 index: 0,
                                 ..model::stubs::file()
-                            }),
+                            })),
                         ),
                     ]
                     .into_iter()
@@ -239,11 +291,11 @@ mod tests {
                     forest: [
                         (
                             "0".into(),
-                            model::Tree::File(model::File {
+                            model::Tree::File(Box::new(model::File {
                                 identifier: quote::format_ident!("A"),
                                 index: 0,
                                 ..model::stubs::file()
-                            }),
+                            })),
                         ),
                         (
                             "1".into(),
@@ -256,11 +308,11 @@ mod tests {
                                             identifier: quote::format_ident!("a"),
                                             forest: [(
                                                 "3".into(),
-                                                model::Tree::File(model::File {
+                                                model::Tree::File(Box::new(model::File {
                                                     identifier: quote::format_ident!("B"),
                                                     index: 2,
                                                     ..model::stubs::file()
-                                                }),
+                                                })),
                                             )]
                                             .into_iter()
                                             .collect(),
@@ -268,11 +320,11 @@ mod tests {
                                     ),
                                     (
                                         "4".into(),
-                                        model::Tree::File(model::File {
+                                        model::Tree::File(Box::new(model::File {
                                             identifier: quote::format_ident!("C"),
                                             index: 1,
                                             ..model::stubs::file()
-                                        }),
+                                        })),
                                     ),
                                 ]
                                 .into_iter()
@@ -312,6 +364,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn handles_fingerprints() {
+        let actual = main(
+            &model::View {
+                type_: quote::format_ident!("Asset"),
+                forest: [(
+                    "a".into(),
+                    model::Tree::Folder(model::Folder {
+                        forest: [(
+                            "b.js".into(),
+                            model::Tree::File(Box::new(model::File {
+                                index: 0,
+                                hash: Some("0123".into()),
+                                fingerprinted_path: Some("a/b.0123.js".into()),
+                                ..model::stubs::file()
+                            })),
+                        )]
+                        .into_iter()
+                        .collect(),
+                        ..model::stubs::folder()
+                    }),
+                )]
+                .into_iter()
+                .collect(),
+                ..model::stubs::view()
+            },
+            &model::Visitor::Fingerprints,
+        );
+
+        let actual = actual.to_string();
+        let folder_hash = hash::main(b"b.js\x000123\n");
+        let bundle_hash = hash::main(format!("a\0{folder_hash}\n").as_bytes());
+        let expected = quote::quote! {
+            pub const BUNDLE_HASH: &str = #bundle_hash;
+
+            pub fn get_by_fingerprinted_path(fingerprinted_path: &str) -> Option<&'static Asset> {
+                match fingerprinted_path {
+                    "a/b.0123.js" => Some(&ASSETS[0usize]),
+                    _ => None,
+                }
+            }
+
+            pub fn get_folder_hash(relative_path: &str) -> Option<&'static str> {
+                match relative_path {
+                    "" => Some(#bundle_hash),
+                    "a" => Some(#folder_hash),
+                    _ => None,
+                }
+            }
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
     #[cfg(test)]
     mod handles_custom {
         use super::*;
@@ -323,12 +429,22 @@ mod tests {
                     forest: [
                         (
                             "0".into(),
-                            model::Tree::File(model::File {
+                            model::Tree::File(Box::new(model::File {
                                 identifier: quote::format_ident!("A"),
                                 index: 0,
                                 relative_path: "a".into(),
                                 absolute_path: "/a".into(),
-                            }),
+                                contents: None,
+                                size: None,
+                                hash: None,
+                                fingerprinted_path: None,
+                                shared_contents: None,
+                                layer: None,
+                                captures: collections::BTreeMap::new(),
+                                variants: collections::BTreeMap::new(),
+                                override_: None,
+                                alias_of: None,
+                            })),
                         ),
                         (
                             "1".into(),
@@ -341,12 +457,22 @@ mod tests {
                                             identifier: quote::format_ident!("a"),
                                             forest: [(
                                                 "3".into(),
-                                                model::Tree::File(model::File {
+                                                model::Tree::File(Box::new(model::File {
                                                     identifier: quote::format_ident!("B"),
                                                     index: 2,
                                                     relative_path: "b/a/b".into(),
                                                     absolute_path: "/b/a/b".into(),
-                                                }),
+                                                    contents: None,
+                                                    size: None,
+                                                    hash: None,
+                                                    fingerprinted_path: None,
+                                                    shared_contents: None,
+                                                    layer: None,
+                                                    captures: collections::BTreeMap::new(),
+                                                    variants: collections::BTreeMap::new(),
+                                                    override_: None,
+                                                    alias_of: None,
+                                                })),
                                             )]
                                             .into_iter()
                                             .collect(),
@@ -354,12 +480,22 @@ mod tests {
                                     ),
                                     (
                                         "4".into(),
-                                        model::Tree::File(model::File {
+                                        model::Tree::File(Box::new(model::File {
                                             identifier: quote::format_ident!("C"),
                                             index: 1,
                                             relative_path: "b/c".into(),
                                             absolute_path: "/b/c".into(),
-                                        }),
+                                            contents: None,
+                                            size: None,
+                                            hash: None,
+                                            fingerprinted_path: None,
+                                            shared_contents: None,
+                                            layer: None,
+                                            captures: collections::BTreeMap::new(),
+                                            variants: collections::BTreeMap::new(),
+                                            override_: None,
+                                            alias_of: None,
+                                        })),
                                     ),
                                 ]
                                 .into_iter()
@@ -409,12 +545,22 @@ mod tests {
                             identifier: quote::format_ident!("a"),
                             forest: [(
                                 "1".into(),
-                                model::Tree::File(model::File {
+                                model::Tree::File(Box::new(model::File {
                                     identifier: quote::format_ident!("B"),
                                     index: 0,
                                     relative_path: "a/b".into(),
                                     absolute_path: "/a/b".into(),
-                                }),
+                                    contents: None,
+                                    size: None,
+                                    hash: None,
+                                    fingerprinted_path: None,
+                                    shared_contents: None,
+                                    layer: None,
+                                    captures: collections::BTreeMap::new(),
+                                    variants: collections::BTreeMap::new(),
+                                    override_: None,
+                                    alias_of: None,
+                                })),
                             )]
                             .into_iter()
                             .collect(),
@@ -453,12 +599,22 @@ mod tests {
                             identifier: quote::format_ident!("a"),
                             forest: [(
                                 "1".into(),
-                                model::Tree::File(model::File {
+                                model::Tree::File(Box::new(model::File {
                                     identifier: quote::format_ident!("B"),
                                     index: 0,
                                     relative_path: "a/b".into(),
                                     absolute_path: "/a/b".into(),
-                                }),
+                                    contents: None,
+                                    size: None,
+                                    hash: None,
+                                    fingerprinted_path: None,
+                                    shared_contents: None,
+                                    layer: None,
+                                    captures: collections::BTreeMap::new(),
+                                    variants: collections::BTreeMap::new(),
+                                    override_: None,
+                                    alias_of: None,
+                                })),
                             )]
                             .into_iter()
                             .collect(),
@@ -0,0 +1,106 @@
+use crate::model;
+
+pub fn main(view: &model::View) -> proc_macro2::TokenStream {
+    let has_array = view
+        .visitors
+        .iter()
+        .any(|visitor| matches!(visitor, model::Visitor::Array(_)));
+
+    if !has_array || view.groups.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+
+    // Like files in module `base`, groups are apart from `ASSETS` and other
+    // items next to it.
+    let type_ = &view.type_;
+    let groups = view
+        .groups
+        .iter()
+        .map(|group| {
+            let identifier = &group.identifier;
+            let references = group
+                .indices
+                .iter()
+                .map(|index| quote::quote! { &super::ASSETS[#index], })
+                .collect::<proc_macro2::TokenStream>();
+
+            quote::quote! {
+                pub static #identifier: &[&super::#type_] = &[
+                    #references
+                ];
+            }
+        })
+        .collect::<proc_macro2::TokenStream>();
+
+    quote::quote! { pub mod groups { #groups } }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_no_array() {
+        let actual = main(&model::View {
+            visitors: vec![model::Visitor::Identifiers],
+            groups: vec![model::Group {
+                identifier: quote::format_ident!("IMAGES"),
+                indices: vec![0],
+            }],
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {}.to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_no_groups() {
+        let actual = main(&model::View {
+            visitors: vec![model::Visitor::Array(model::Initializer::Macro(
+                syn::parse_str("abc").unwrap(),
+            ))],
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {}.to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_groups() {
+        let actual = main(&model::View {
+            type_: quote::format_ident!("Asset"),
+            visitors: vec![model::Visitor::Array(model::Initializer::Macro(
+                syn::parse_str("abc").unwrap(),
+            ))],
+            groups: vec![
+                model::Group {
+                    identifier: quote::format_ident!("FONTS"),
+                    indices: vec![],
+                },
+                model::Group {
+                    identifier: quote::format_ident!("IMAGES"),
+                    indices: vec![0, 2],
+                },
+            ],
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {
+            pub mod groups {
+                pub static FONTS: &[&super::Asset] = &[];
+
+                pub static IMAGES: &[&super::Asset] = &[
+                    &super::ASSETS[0usize],
+                    &super::ASSETS[2usize],
+                ];
+            }
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,14 +1,19 @@
 use super::print_populator;
 use crate::model;
+use syn::ext::IdentExt;
 
 pub fn main(
     type_: &syn::Ident,
     initializer: &model::Initializer,
     file: &model::File,
 ) -> proc_macro2::TokenStream {
-    match initializer {
-        model::Initializer::Default(populators) => print_default(type_, populators, file),
-        model::Initializer::Macro(name) => print_macro(name, file),
+    let override_ = file.override_.as_ref();
+    match (
+        override_.and_then(|override_| override_.initializer.as_ref()),
+        initializer,
+    ) {
+        (Some(name), _) | (None, model::Initializer::Macro(name)) => print_macro(name, file),
+        (None, model::Initializer::Default(populators)) => print_default(type_, populators, file),
     }
 }
 
@@ -20,6 +25,11 @@ fn print_default(
     let context = print_populator::Context {
         relative_path: &file.relative_path,
         absolute_path: &file.absolute_path,
+        contents: file.contents.as_deref(),
+        fingerprinted_path: file.fingerprinted_path.as_deref().unwrap_or_default(),
+        shared_contents: file.shared_contents,
+        captures: &file.captures,
+        variants: &file.variants,
     };
 
     match populators {
@@ -31,7 +41,7 @@ fn print_default(
             let contents: proc_macro2::TokenStream = field_populators
                 .iter()
                 .map(|(field, populator)| {
-                    let term = print_populator::main(populator, &context);
+                    let term = print_term(populator, &context, file, &field.unraw().to_string());
                     quote::quote! { #field: #term, }
                 })
                 .collect();
@@ -42,8 +52,9 @@ fn print_default(
         model::TypeStructure::TupleFields(populators) => {
             let contents: proc_macro2::TokenStream = populators
                 .iter()
-                .map(|populator| {
-                    let term = print_populator::main(populator, &context);
+                .enumerate()
+                .map(|(index, populator)| {
+                    let term = print_term(populator, &context, file, &index.to_string());
                     quote::quote! { #term, }
                 })
                 .collect();
@@ -53,6 +64,23 @@ fn print_default(
     }
 }
 
+// A field set by an override takes its expression instead of the populator.
+fn print_term(
+    populator: &model::Populator,
+    context: &print_populator::Context,
+    file: &model::File,
+    field: &str,
+) -> proc_macro2::TokenStream {
+    match file
+        .override_
+        .as_ref()
+        .and_then(|override_| override_.fields.get(field))
+    {
+        None => print_populator::main(populator, context),
+        Some(expression) => quote::quote! { #expression },
+    }
+}
+
 fn print_macro(macro_: &syn::Path, file: &model::File) -> proc_macro2::TokenStream {
     let relative_path = &file.relative_path;
     let absolute_path = &file.absolute_path;
@@ -178,6 +206,82 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod override_ {
+        use super::*;
+        use std::collections;
+
+        #[test]
+        fn handles_initializer() {
+            let actual = main(
+                &quote::format_ident!("Foo"),
+                &model::Initializer::Default(model::TypeStructure::Unit),
+                &model::File {
+                    relative_path: "b".into(),
+                    absolute_path: "/a/b".into(),
+                    override_: Some(model::Override {
+                        paths: "*".into(),
+                        initializer: Some(syn::parse_str("abc").unwrap()),
+                        transform: None,
+                        fields: collections::BTreeMap::new(),
+                    }),
+                    ..model::stubs::file()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! { abc!("b", "/a/b") }.to_string();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_fields() {
+            let override_ = Some(model::Override {
+                paths: "*".into(),
+                initializer: None,
+                transform: None,
+                fields: [
+                    ("0".into(), syn::parse_quote! { "x" }),
+                    ("type".into(), syn::parse_quote! { "y" }),
+                ]
+                .into(),
+            });
+
+            let actual = [
+                model::TypeStructure::NamedFields(vec![
+                    (
+                        quote::format_ident!("relative_path"),
+                        model::Populator::RelativePath,
+                    ),
+                    (syn::parse_quote! { r#type }, model::Populator::ContentsStr),
+                ]),
+                model::TypeStructure::TupleFields(vec![
+                    model::Populator::ContentsStr,
+                    model::Populator::RelativePath,
+                ]),
+            ]
+            .map(|populators| {
+                main(
+                    &quote::format_ident!("Foo"),
+                    &model::Initializer::Default(populators),
+                    &model::File {
+                        relative_path: "b".into(),
+                        override_: override_.clone(),
+                        ..model::stubs::file()
+                    },
+                )
+                .to_string()
+            });
+
+            let expected = [
+                quote::quote! { Foo { relative_path: "b", r#type: "y", } },
+                quote::quote! { Foo("x", "b",) },
+            ]
+            .map(|expected| expected.to_string());
+            assert_eq!(actual, expected);
+        }
+    }
+
     #[test]
     fn macro_() {
         let actual = main(
@@ -0,0 +1,177 @@
+use super::get_files;
+use crate::layers;
+use crate::model;
+use std::collections;
+
+pub fn main(view: &model::View) -> proc_macro2::TokenStream {
+    let has_array = view
+        .visitors
+        .iter()
+        .any(|visitor| matches!(visitor, model::Visitor::Array(_)));
+
+    match &view.layers {
+        Some(layers) if has_array => {
+            let type_ = &view.type_;
+            let files = get_files::main(view);
+            let cases = (1..=layers.names.len())
+                .map(|rank| {
+                    let name = &layers.names[rank - 1];
+                    let lookups = get_selection(&layers.names, &files, rank)
+                        .into_iter()
+                        .map(|(relative_path, index)| {
+                            quote::quote! { #relative_path => Some(&ASSETS[#index]), }
+                        })
+                        .collect::<proc_macro2::TokenStream>();
+
+                    quote::quote! {
+                        #name => Some({
+                            fn get(relative_path: &str) -> Option<&'static #type_> {
+                                match relative_path {
+                                    #lookups
+                                    _ => None,
+                                }
+                            }
+
+                            get
+                        }),
+                    }
+                })
+                .collect::<proc_macro2::TokenStream>();
+
+            quote::quote! {
+                pub fn select_layer(name: &str) -> Option<fn(&str) -> Option<&'static #type_>> {
+                    match name {
+                        #cases
+                        _ => None,
+                    }
+                }
+            }
+        }
+
+        _ => proc_macro2::TokenStream::new(),
+    }
+}
+
+// A lookup in a layer falls through to the earlier layers, with files outside
+// of any layer at the bottom.
+fn get_selection<'a>(
+    names: &[String],
+    files: &[&'a model::File],
+    rank: usize,
+) -> collections::BTreeMap<&'a str, usize> {
+    let mut selection = collections::BTreeMap::new();
+
+    for file in files {
+        let file_rank = layers::get_rank(names, file.layer.as_deref());
+
+        if file_rank <= rank {
+            selection
+                .entry(file.relative_path.as_str())
+                .and_modify(|selected: &mut (usize, usize)| {
+                    if file_rank > selected.0 {
+                        *selected = (file_rank, file.index);
+                    }
+                })
+                .or_insert((file_rank, file.index));
+        }
+    }
+
+    selection
+        .into_iter()
+        .map(|(relative_path, (_, index))| (relative_path, index))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_no_layers() {
+        let actual = main(&model::View {
+            visitors: vec![model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::TypeAlias(model::Populator::ContentsStr),
+            ))],
+            layers: None,
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {}.to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_layers() {
+        let actual = main(&model::View {
+            type_: quote::format_ident!("Asset"),
+            visitors: vec![model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::TypeAlias(model::Populator::ContentsStr),
+            ))],
+            forest: [
+                (
+                    "a".into(),
+                    model::Tree::File(Box::new(model::File {
+                        index: 0,
+                        relative_path: "a".into(),
+                        layer: Some("c".into()),
+                        ..model::stubs::file()
+                    })),
+                ),
+                (
+                    "b".into(),
+                    model::Tree::File(Box::new(model::File {
+                        index: 1,
+                        relative_path: "b".into(),
+                        layer: Some("d".into()),
+                        ..model::stubs::file()
+                    })),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            layers: Some(model::Layers {
+                names: vec!["c".into(), "d".into()],
+                shadowed_files: vec![model::File {
+                    index: 2,
+                    relative_path: "a".into(),
+                    layer: None,
+                    ..model::stubs::file()
+                }],
+            }),
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {
+            pub fn select_layer(name: &str) -> Option<fn(&str) -> Option<&'static Asset>> {
+                match name {
+                    "c" => Some({
+                        fn get(relative_path: &str) -> Option<&'static Asset> {
+                            match relative_path {
+                                "a" => Some(&ASSETS[0usize]),
+                                _ => None,
+                            }
+                        }
+
+                        get
+                    }),
+                    "d" => Some({
+                        fn get(relative_path: &str) -> Option<&'static Asset> {
+                            match relative_path {
+                                "a" => Some(&ASSETS[0usize]),
+                                "b" => Some(&ASSETS[1usize]),
+                                _ => None,
+                            }
+                        }
+
+                        get
+                    }),
+                    _ => None,
+                }
+            }
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,47 +1,215 @@
+use super::print_shared_contents;
 use crate::model;
 use std::cmp;
+use std::collections;
+use std::str;
 
 pub fn main(populator: &model::Populator, context: &Context) -> proc_macro2::TokenStream {
     let relative_path = context.relative_path;
     let absolute_path = context.absolute_path;
+    let Source {
+        dependency,
+        bytes,
+        str_,
+        is_original,
+    } = get_source(context);
 
     match populator {
-        model::Populator::ContentsBytes => quote::quote! { include_bytes!(#absolute_path) },
+        model::Populator::Capture { name, integer } => match context.captures.get(name) {
+            None => print_missing_capture(relative_path, name),
+            Some(value) if *integer => print_integer(value),
+            Some(value) => quote::quote! { #value },
+        },
 
-        model::Populator::ContentsStr => quote::quote! { include_str!(#absolute_path) },
+        model::Populator::ContentsBytes => print_with_dependency(&dependency, bytes),
 
-        model::Populator::GetBytes => quote::quote! {{
-            fn get() -> std::borrow::Cow<'static, [u8]> {
-                if cfg!(debug_assertions) {
-                    std::fs::read(#absolute_path).unwrap().into()
-                } else {
-                    (&include_bytes!(#absolute_path)[..]).into()
+        model::Populator::ContentsStr => match str_ {
+            None => print_invalid_utf_8(absolute_path),
+            Some(str_) => print_with_dependency(&dependency, str_),
+        },
+
+        model::Populator::FingerprintedPath => {
+            let fingerprinted_path = context.fingerprinted_path;
+            quote::quote! { #fingerprinted_path }
+        }
+
+        model::Populator::GetBytes => {
+            let value = if is_original {
+                quote::quote! {
+                    if cfg!(debug_assertions) {
+                        std::fs::read(#absolute_path).unwrap().into()
+                    } else {
+                        (&#bytes[..]).into()
+                    }
                 }
-            }
+            } else {
+                quote::quote! { (&#bytes[..]).into() }
+            };
 
-            get
-        }},
+            quote::quote! {{
+                fn get() -> std::borrow::Cow<'static, [u8]> {
+                    #dependency
+                    #value
+                }
 
-        model::Populator::GetStr => quote::quote! {{
-            fn get() -> std::borrow::Cow<'static, str> {
-                if cfg!(debug_assertions) {
-                    std::fs::read_to_string(#absolute_path).unwrap().into()
+                get
+            }}
+        }
+
+        model::Populator::GetStr => match str_ {
+            None => print_invalid_utf_8(absolute_path),
+            Some(str_) => {
+                let value = if is_original {
+                    quote::quote! {
+                        if cfg!(debug_assertions) {
+                            std::fs::read_to_string(#absolute_path).unwrap().into()
+                        } else {
+                            #str_.into()
+                        }
+                    }
                 } else {
-                    include_str!(#absolute_path).into()
-                }
-            }
+                    quote::quote! { #str_.into() }
+                };
 
-            get
-        }},
+                quote::quote! {{
+                    fn get() -> std::borrow::Cow<'static, str> {
+                        #dependency
+                        #value
+                    }
+
+                    get
+                }}
+            }
+        },
 
         model::Populator::RelativePath => quote::quote! { #relative_path },
+
+        model::Populator::Variant { name, str_ } => match context.variants.get(name) {
+            None => quote::quote! { None },
+            Some(variant) => print_variant(variant, *str_),
+        },
+    }
+}
+
+// Variants are included on their own rather than shared.
+fn print_variant(variant: &model::FileVariant, str_: bool) -> proc_macro2::TokenStream {
+    let no_captures = collections::BTreeMap::new();
+    let no_variants = collections::BTreeMap::new();
+    let source = get_source(&Context {
+        relative_path: "",
+        absolute_path: &variant.absolute_path,
+        contents: variant.contents.as_deref(),
+        fingerprinted_path: "",
+        shared_contents: None,
+        captures: &no_captures,
+        variants: &no_variants,
+    });
+
+    let value = if str_ {
+        match source.str_ {
+            None => return print_invalid_utf_8(&variant.absolute_path),
+            Some(value) => value,
+        }
+    } else {
+        source.bytes
+    };
+    let value = print_with_dependency(&source.dependency, value);
+    quote::quote! { Some(#value) }
+}
+
+struct Source {
+    dependency: Option<proc_macro2::TokenStream>,
+    bytes: proc_macro2::TokenStream,
+    str_: Option<proc_macro2::TokenStream>,
+    // Whether the contents are those of the file as is, which can be read
+    // afresh in debug builds.
+    is_original: bool,
+}
+
+fn get_source(context: &Context) -> Source {
+    let absolute_path = context.absolute_path;
+    let is_original = context.contents.is_none();
+
+    match (context.shared_contents, context.contents) {
+        (Some(index), _) => {
+            let (bytes, str_) = print_shared_contents::get_identifiers(index);
+            Source {
+                dependency: print_dependency(absolute_path),
+                bytes: quote::quote! { #bytes },
+                str_: Some(quote::quote! { #str_ }),
+                is_original,
+            }
+        }
+
+        (None, None) => Source {
+            dependency: None,
+            bytes: quote::quote! { include_bytes!(#absolute_path) },
+            str_: Some(quote::quote! { include_str!(#absolute_path) }),
+            is_original,
+        },
+
+        (None, Some(contents)) => {
+            let bytes = proc_macro2::Literal::byte_string(contents);
+            Source {
+                dependency: print_dependency(absolute_path),
+                bytes: quote::quote! { #bytes },
+                str_: str::from_utf8(contents)
+                    .ok()
+                    .map(|str_| quote::quote! { #str_ }),
+                is_original,
+            }
+        }
+    }
+}
+
+fn print_with_dependency(
+    dependency: &Option<proc_macro2::TokenStream>,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match dependency {
+        None => value,
+        Some(dependency) => quote::quote! {{ #dependency #value }},
     }
 }
 
+// Keeps the file as a build dependency even though its contents are not
+// included from it directly. A virtual file has no such file.
+fn print_dependency(absolute_path: &str) -> Option<proc_macro2::TokenStream> {
+    (!absolute_path.is_empty())
+        .then(|| quote::quote! { const _: &[u8] = include_bytes!(#absolute_path); })
+}
+
+fn print_integer(value: &str) -> proc_macro2::TokenStream {
+    let literal = match (value.parse::<i128>(), value.parse::<u128>()) {
+        (Ok(value), _) => proc_macro2::Literal::i128_unsuffixed(value),
+        (_, Ok(value)) => proc_macro2::Literal::u128_unsuffixed(value),
+        _ => {
+            let message = format!("Captured value is not an integer: {value:?}");
+            return quote::quote! { compile_error!(#message) };
+        }
+    };
+    quote::quote! { #literal }
+}
+
+fn print_missing_capture(relative_path: &str, name: &str) -> proc_macro2::TokenStream {
+    let message = format!("No capture {name:?} for relative path {relative_path:?}");
+    quote::quote! { compile_error!(#message) }
+}
+
+pub fn print_invalid_utf_8(absolute_path: &str) -> proc_macro2::TokenStream {
+    let message = format!("File is not valid UTF-8: {absolute_path:?}");
+    quote::quote! { compile_error!(#message) }
+}
+
 #[derive(Clone, cmp::PartialEq, Debug, Eq)]
 pub struct Context<'a> {
     pub relative_path: &'a str,
     pub absolute_path: &'a str,
+    pub contents: Option<&'a [u8]>,
+    pub fingerprinted_path: &'a str,
+    pub shared_contents: Option<usize>,
+    pub captures: &'a collections::BTreeMap<String, String>,
+    pub variants: &'a collections::BTreeMap<String, model::FileVariant>,
 }
 
 #[cfg(test)]
@@ -52,14 +220,101 @@ pub mod stubs {
         Context {
             relative_path: "bar",
             absolute_path: "/foo/bar",
+            contents: None,
+            fingerprinted_path: "bar.0123abcd",
+            shared_contents: None,
+            captures: &NO_CAPTURES,
+            variants: &NO_VARIANTS,
         }
     }
+
+    static NO_CAPTURES: collections::BTreeMap<String, String> = collections::BTreeMap::new();
+
+    static NO_VARIANTS: collections::BTreeMap<String, model::FileVariant> =
+        collections::BTreeMap::new();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn handles_capture() {
+        let captures = [
+            ("name".into(), "add_users".into()),
+            ("version".into(), "12".into()),
+        ]
+        .into();
+        let context = Context {
+            captures: &captures,
+            ..stubs::context()
+        };
+
+        let actual = [("name", false), ("version", true)].map(|(name, integer)| {
+            main(
+                &model::Populator::Capture {
+                    name: name.into(),
+                    integer,
+                },
+                &context,
+            )
+            .to_string()
+        });
+
+        let expected = [quote::quote! { "add_users" }, quote::quote! { 12 }]
+            .map(|expected| expected.to_string());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_variant() {
+        let variants = [
+            (
+                "map".into(),
+                model::FileVariant {
+                    absolute_path: "/a.map".into(),
+                    contents: None,
+                },
+            ),
+            (
+                "gzip".into(),
+                model::FileVariant {
+                    absolute_path: "/a.gz".into(),
+                    contents: Some(vec![1]),
+                },
+            ),
+        ]
+        .into();
+        let context = Context {
+            variants: &variants,
+            ..stubs::context()
+        };
+
+        let actual = [("map", true), ("gzip", false), ("brotli", false)].map(|(name, str_)| {
+            main(
+                &model::Populator::Variant {
+                    name: name.into(),
+                    str_,
+                },
+                &context,
+            )
+            .to_string()
+        });
+
+        let expected = [
+            quote::quote! { Some(include_str!("/a.map")) },
+            quote::quote! {
+                Some({
+                    const _: &[u8] = include_bytes!("/a.gz");
+                    b"\x01"
+                })
+            },
+            quote::quote! { None },
+        ]
+        .map(|expected| expected.to_string());
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn handles_contents_bytes() {
         let actual = main(
@@ -90,6 +345,21 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn handles_fingerprinted_path() {
+        let actual = main(
+            &model::Populator::FingerprintedPath,
+            &Context {
+                fingerprinted_path: "a/b.0123abcd.c",
+                ..stubs::context()
+            },
+        );
+
+        let actual = actual.to_string();
+        let expected = quote::quote! { "a/b.0123abcd.c" }.to_string();
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn handles_get_bytes() {
         let actual = main(
@@ -156,4 +426,206 @@ mod tests {
         let expected = quote::quote! { "a/b" }.to_string();
         assert_eq!(actual, expected);
     }
+
+    #[cfg(test)]
+    mod given_contents {
+        use super::*;
+
+        #[test]
+        fn handles_contents_bytes() {
+            let actual = main(
+                &model::Populator::ContentsBytes,
+                &Context {
+                    absolute_path: "/a/b",
+                    contents: Some(b"c"),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! {{
+                const _: &[u8] = include_bytes!("/a/b");
+                b"c"
+            }}
+            .to_string();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_contents_str() {
+            let actual = main(
+                &model::Populator::ContentsStr,
+                &Context {
+                    absolute_path: "/a/b",
+                    contents: Some(b"c"),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! {{
+                const _: &[u8] = include_bytes!("/a/b");
+                "c"
+            }}
+            .to_string();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_get_bytes() {
+            let actual = main(
+                &model::Populator::GetBytes,
+                &Context {
+                    absolute_path: "/a/b",
+                    contents: Some(b"c"),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! {{
+                fn get() -> std::borrow::Cow<'static, [u8]> {
+                    const _: &[u8] = include_bytes!("/a/b");
+                    (&b"c"[..]).into()
+                }
+
+                get
+            }}
+            .to_string();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_get_str() {
+            let actual = main(
+                &model::Populator::GetStr,
+                &Context {
+                    absolute_path: "/a/b",
+                    contents: Some(b"c"),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! {{
+                fn get() -> std::borrow::Cow<'static, str> {
+                    const _: &[u8] = include_bytes!("/a/b");
+                    "c".into()
+                }
+
+                get
+            }}
+            .to_string();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_relative_path() {
+            let actual = main(
+                &model::Populator::RelativePath,
+                &Context {
+                    relative_path: "a/b",
+                    contents: Some(b"c"),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! { "a/b" }.to_string();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_invalid_utf_8_it_errs_for_contents_str() {
+            let actual = main(
+                &model::Populator::ContentsStr,
+                &Context {
+                    absolute_path: "/a/b",
+                    contents: Some(b"\xff"),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! {
+                compile_error!("File is not valid UTF-8: \"/a/b\"")
+            }
+            .to_string();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[cfg(test)]
+    mod given_shared_contents {
+        use super::*;
+
+        #[test]
+        fn handles_contents_bytes() {
+            let actual = main(
+                &model::Populator::ContentsBytes,
+                &Context {
+                    absolute_path: "/a/b",
+                    shared_contents: Some(1),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! {{
+                const _: &[u8] = include_bytes!("/a/b");
+                SHARED_CONTENTS_BYTES_1
+            }}
+            .to_string();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_get_str() {
+            let actual = main(
+                &model::Populator::GetStr,
+                &Context {
+                    absolute_path: "/a/b",
+                    shared_contents: Some(1),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! {{
+                fn get() -> std::borrow::Cow<'static, str> {
+                    const _: &[u8] = include_bytes!("/a/b");
+                    if cfg!(debug_assertions) {
+                        std::fs::read_to_string("/a/b").unwrap().into()
+                    } else {
+                        SHARED_CONTENTS_STR_1.into()
+                    }
+                }
+
+                get
+            }}
+            .to_string();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn handles_contents_str_given_contents() {
+            let actual = main(
+                &model::Populator::ContentsStr,
+                &Context {
+                    absolute_path: "/a/b",
+                    contents: Some(b"\xff"),
+                    shared_contents: Some(1),
+                    ..stubs::context()
+                },
+            );
+
+            let actual = actual.to_string();
+            let expected = quote::quote! {{
+                const _: &[u8] = include_bytes!("/a/b");
+                SHARED_CONTENTS_STR_1
+            }}
+            .to_string();
+            assert_eq!(actual, expected);
+        }
+    }
 }
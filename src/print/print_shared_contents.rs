@@ -0,0 +1,150 @@
+use super::get_used_contents;
+use super::print_populator;
+use crate::model;
+use std::str;
+
+pub fn main(view: &model::View) -> proc_macro2::TokenStream {
+    let used_contents = get_used_contents::main(&view.visitors);
+
+    view.shared_contents
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, shared_contents)| {
+            let (bytes_identifier, str_identifier) = get_identifiers(index);
+            let absolute_path = &shared_contents.absolute_path;
+
+            let bytes = used_contents.bytes.then(|| {
+                let value = match &shared_contents.contents {
+                    None => quote::quote! { include_bytes!(#absolute_path) },
+                    Some(contents) => {
+                        let contents = proc_macro2::Literal::byte_string(contents);
+                        quote::quote! { #contents }
+                    }
+                };
+                quote::quote! { static #bytes_identifier: &[u8] = #value; }
+            });
+
+            let str_ = used_contents.str_.then(|| {
+                let value = match &shared_contents.contents {
+                    None => quote::quote! { include_str!(#absolute_path) },
+                    Some(contents) => match str::from_utf8(contents) {
+                        Err(_) => print_populator::print_invalid_utf_8(absolute_path),
+                        Ok(contents) => quote::quote! { #contents },
+                    },
+                };
+                quote::quote! { static #str_identifier: &str = #value; }
+            });
+
+            quote::quote! { #bytes #str_ }
+        })
+        .collect()
+}
+
+pub fn get_identifiers(index: usize) -> (syn::Ident, syn::Ident) {
+    (
+        quote::format_ident!("SHARED_CONTENTS_BYTES_{index}"),
+        quote::format_ident!("SHARED_CONTENTS_STR_{index}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_no_shared_contents() {
+        let actual = main(&model::View {
+            visitors: vec![model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::TypeAlias(model::Populator::ContentsBytes),
+            ))],
+            shared_contents: None,
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {}.to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_used_populators_only() {
+        let actual = main(&model::View {
+            visitors: vec![model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::NamedFields(vec![
+                    (
+                        quote::format_ident!("relative_path"),
+                        model::Populator::RelativePath,
+                    ),
+                    (
+                        quote::format_ident!("get_bytes"),
+                        model::Populator::GetBytes,
+                    ),
+                ]),
+            ))],
+            shared_contents: Some(vec![
+                model::SharedContents {
+                    absolute_path: "/a".into(),
+                    contents: None,
+                    size: 1,
+                    file_count: 2,
+                },
+                model::SharedContents {
+                    absolute_path: "/b".into(),
+                    contents: Some(b"c".into()),
+                    size: 1,
+                    file_count: 2,
+                },
+            ]),
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {
+            static SHARED_CONTENTS_BYTES_0: &[u8] = include_bytes!("/a");
+            static SHARED_CONTENTS_BYTES_1: &[u8] = b"c";
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_str() {
+        let actual = main(&model::View {
+            visitors: vec![model::Visitor::Array(model::Initializer::Default(
+                model::TypeStructure::TypeAlias(model::Populator::ContentsStr),
+            ))],
+            shared_contents: Some(vec![
+                model::SharedContents {
+                    absolute_path: "/a".into(),
+                    contents: None,
+                    size: 1,
+                    file_count: 2,
+                },
+                model::SharedContents {
+                    absolute_path: "/b".into(),
+                    contents: Some(b"c".into()),
+                    size: 1,
+                    file_count: 2,
+                },
+                model::SharedContents {
+                    absolute_path: "/d".into(),
+                    contents: Some(b"\xff".into()),
+                    size: 1,
+                    file_count: 2,
+                },
+            ]),
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {
+            static SHARED_CONTENTS_STR_0: &str = include_str!("/a");
+            static SHARED_CONTENTS_STR_1: &str = "c";
+            static SHARED_CONTENTS_STR_2: &str =
+                compile_error!("File is not valid UTF-8: \"/d\"");
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::model;
+
+pub fn main(view: &model::View) -> proc_macro2::TokenStream {
+    match &view.skipped_files {
+        None => proc_macro2::TokenStream::new(),
+
+        Some(skipped_files) => {
+            let length = skipped_files.len();
+            let entries = skipped_files
+                .iter()
+                .map(|skipped_file| {
+                    let path = &skipped_file.path;
+                    let reason = &skipped_file.reason;
+                    quote::quote! { (#path, #reason), }
+                })
+                .collect::<proc_macro2::TokenStream>();
+
+            quote::quote! {
+                pub static SKIPPED_FILES: [(&str, &str); #length] = [
+                    #entries
+                ];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_no_skipping() {
+        let actual = main(&model::View {
+            skipped_files: None,
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {}.to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_skipped_files() {
+        let actual = main(&model::View {
+            skipped_files: Some(vec![
+                model::SkippedFile {
+                    path: "/a/b".into(),
+                    reason: "special file".into(),
+                },
+                model::SkippedFile {
+                    path: "/a/c".into(),
+                    reason: "Permission denied".into(),
+                },
+            ]),
+            ..model::stubs::view()
+        });
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {
+            pub static SKIPPED_FILES: [(&str, &str); 2usize] = [
+                ("/a/b", "special file"),
+                ("/a/c", "Permission denied"),
+            ];
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
+}
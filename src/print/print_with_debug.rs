@@ -1,11 +1,14 @@
 use crate::model;
 
 pub fn main(view: model::View, code: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
-    if view.debug { go(code) } else { code }
+    if view.debug { go(&view, code) } else { code }
 }
 
-fn go(code: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
-    let value = code.to_string();
+fn go(view: &model::View, code: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut value = code.to_string();
+    if let Some(shared_contents) = &view.shared_contents {
+        value.push_str(&print_deduplication(shared_contents));
+    }
 
     quote::quote! {
         #code
@@ -14,6 +17,22 @@ fn go(code: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
     }
 }
 
+fn print_deduplication(shared_contents: &[model::SharedContents]) -> String {
+    let file_count = shared_contents
+        .iter()
+        .map(|shared_contents| shared_contents.file_count)
+        .sum::<usize>();
+    let saved_bytes = shared_contents
+        .iter()
+        .map(|shared_contents| (shared_contents.file_count - 1) * shared_contents.size)
+        .sum::<usize>();
+
+    format!(
+        "\n\n// Deduplication: {file_count} files share {} contents, saving {saved_bytes} bytes.",
+        shared_contents.len(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,4 +71,37 @@ mod tests {
         .to_string();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn handles_debug_with_deduplication() {
+        let actual = main(
+            model::View {
+                shared_contents: Some(vec![
+                    model::SharedContents {
+                        size: 10,
+                        file_count: 3,
+                        ..model::stubs::shared_contents()
+                    },
+                    model::SharedContents {
+                        size: 1,
+                        file_count: 2,
+                        ..model::stubs::shared_contents()
+                    },
+                ]),
+                debug: true,
+                ..model::stubs::view()
+            },
+            quote::quote! { mod abc {} },
+        );
+
+        let actual = actual.to_string();
+        let expected = quote::quote! {
+            mod abc {}
+
+            pub const DEBUG: &str =
+                "mod abc { }\n\n// Deduplication: 5 files share 2 contents, saving 21 bytes.";
+        }
+        .to_string();
+        assert_eq!(actual, expected);
+    }
 }
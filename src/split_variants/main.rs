@@ -0,0 +1,164 @@
+use crate::model;
+
+pub fn main(configuration: model::Configuration) -> model::Result<Vec<model::Variant>> {
+    let mut predicates = vec![];
+    for predicate in configuration
+        .sources
+        .iter()
+        .filter_map(|source| source.cfg.as_ref())
+    {
+        if !predicates.contains(predicate) {
+            predicates.push(predicate.clone());
+        }
+    }
+
+    if predicates.is_empty() {
+        return Ok(vec![model::Variant {
+            condition: None,
+            configuration,
+        }]);
+    }
+    // The number of variants doubles with each predicate.
+    if predicates.len() > MAX_PREDICATES {
+        return Err(model::Error::TooManyCfgPredicates {
+            count: predicates.len(),
+            max_predicates: MAX_PREDICATES,
+        });
+    }
+
+    // Each combination of predicates gets its own variant, so the generated
+    // code is complete and consistent whichever combination holds.
+    Ok((0..1_usize << predicates.len())
+        .map(|combination| {
+            let active_predicates = predicates
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| combination & (1 << index) != 0)
+                .map(|(_, predicate)| predicate)
+                .collect::<Vec<_>>();
+            let is_active = |predicate| active_predicates.contains(&predicate);
+            let terms = predicates.iter().map(|predicate| {
+                if is_active(predicate) {
+                    quote::quote! { #predicate }
+                } else {
+                    quote::quote! { not(#predicate) }
+                }
+            });
+
+            model::Variant {
+                condition: Some(syn::parse_quote! { all(#(#terms),*) }),
+                configuration: model::Configuration {
+                    sources: configuration
+                        .sources
+                        .iter()
+                        .filter(|source| source.cfg.as_ref().is_none_or(is_active))
+                        .cloned()
+                        .collect(),
+                    ..configuration.clone()
+                },
+            }
+        })
+        .collect())
+}
+
+const MAX_PREDICATES: usize = 6;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_no_predicates() {
+        let configuration = model::stubs::configuration();
+
+        let actual = main(configuration.clone());
+
+        let actual = actual.unwrap();
+        let expected = vec![model::Variant {
+            condition: None,
+            configuration,
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_predicates() {
+        let a = model::Source {
+            paths: "/a".into(),
+            ..model::stubs::source()
+        };
+        let b = model::Source {
+            paths: "/b".into(),
+            cfg: Some(syn::parse_quote! { unix }),
+            ..model::stubs::source()
+        };
+        let c = model::Source {
+            paths: "/c".into(),
+            cfg: Some(syn::parse_quote! { feature = "c" }),
+            ..model::stubs::source()
+        };
+        let d = model::Source {
+            paths: "/d".into(),
+            cfg: Some(syn::parse_quote! { unix }),
+            ..model::stubs::source()
+        };
+
+        let actual = main(model::Configuration {
+            sources: vec![a.clone(), b.clone(), c.clone(), d.clone()],
+            ..model::stubs::configuration()
+        });
+
+        let actual = actual.unwrap();
+        let expected = vec![
+            model::Variant {
+                condition: Some(syn::parse_quote! { all(not(unix), not(feature = "c")) }),
+                configuration: model::Configuration {
+                    sources: vec![a.clone()],
+                    ..model::stubs::configuration()
+                },
+            },
+            model::Variant {
+                condition: Some(syn::parse_quote! { all(unix, not(feature = "c")) }),
+                configuration: model::Configuration {
+                    sources: vec![a.clone(), b.clone(), d.clone()],
+                    ..model::stubs::configuration()
+                },
+            },
+            model::Variant {
+                condition: Some(syn::parse_quote! { all(not(unix), feature = "c") }),
+                configuration: model::Configuration {
+                    sources: vec![a.clone(), c.clone()],
+                    ..model::stubs::configuration()
+                },
+            },
+            model::Variant {
+                condition: Some(syn::parse_quote! { all(unix, feature = "c") }),
+                configuration: model::Configuration {
+                    sources: vec![a, b, c, d],
+                    ..model::stubs::configuration()
+                },
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_too_many_predicates_it_errs() {
+        let actual = main(model::Configuration {
+            sources: (0..7)
+                .map(|index| model::Source {
+                    cfg: Some(syn::parse_str(&format!("feature = \"{index}\"")).unwrap()),
+                    ..model::stubs::source()
+                })
+                .collect(),
+            ..model::stubs::configuration()
+        });
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::TooManyCfgPredicates {
+            count: 7,
+            max_predicates: 6,
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,186 @@
+use super::minify_json;
+use super::normalize_line_endings;
+use super::strip_bom;
+use super::substitute_variables;
+use super::transcode;
+use super::trim_trailing_whitespace;
+use crate::model;
+
+pub fn main(
+    transform: &model::Transform,
+    absolute_path: &str,
+    contents: Vec<u8>,
+    run_command: &RunCommand,
+    get_variable: &GetVariable,
+) -> model::Result<Vec<u8>> {
+    let contents = match &transform.command {
+        None => contents,
+        Some(command) => run_command(command, absolute_path, &contents)?,
+    };
+
+    let contents = match transform.encoding {
+        None => contents,
+        Some(encoding) => {
+            transcode::main(contents, encoding).ok_or_else(|| model::Error::InvalidEncoding {
+                path: absolute_path.into(),
+                encoding,
+            })?
+        }
+    };
+
+    let contents = if transform.strip_bom {
+        strip_bom::main(contents)
+    } else {
+        contents
+    };
+
+    let contents = if transform.substitute_variables {
+        substitute_variables::main(&contents, get_variable).map_err(|name| {
+            model::Error::UndefinedVariable {
+                name,
+                path: absolute_path.into(),
+            }
+        })?
+    } else {
+        contents
+    };
+
+    let contents = match transform.line_endings {
+        None => contents,
+        Some(line_endings) => normalize_line_endings::main(&contents, line_endings),
+    };
+
+    let contents = if transform.trim_trailing_whitespace {
+        trim_trailing_whitespace::main(&contents)
+    } else {
+        contents
+    };
+
+    if transform.minify_json {
+        minify_json::main(&contents).map_err(|message| model::Error::InvalidJson {
+            path: absolute_path.into(),
+            message,
+        })
+    } else {
+        Ok(contents)
+    }
+}
+
+pub type RunCommand = dyn Fn(&[String], &str, &[u8]) -> model::Result<Vec<u8>>;
+
+pub type GetVariable<'a> = dyn Fn(&str) -> Option<String> + 'a;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_no_steps() {
+        let actual = main(
+            &model::stubs::transform(),
+            "/a",
+            b" \r\n".to_vec(),
+            &|_, _, _| unreachable!(),
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap();
+        let expected = b" \r\n";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_all_steps_in_order() {
+        let actual = main(
+            &model::Transform {
+                command: Some(vec!["x".into()]),
+                encoding: Some(model::Encoding::Utf16Le),
+                strip_bom: true,
+                substitute_variables: true,
+                line_endings: Some(model::LineEndings::Lf),
+                trim_trailing_whitespace: true,
+                minify_json: true,
+                ..model::stubs::transform()
+            },
+            "/a",
+            b"x".to_vec(),
+            &|command, absolute_path, input| {
+                assert_eq!(command, ["x"]);
+                assert_eq!(absolute_path, "/a");
+                assert_eq!(input, b"x");
+                Ok(b"\xff\xfe[\x00$\x00{\x00x\x00}\x00\r\x00\n\x00]\x00".to_vec())
+            },
+            &|name| {
+                assert_eq!(name, "x");
+                Some(" 1 ".into())
+            },
+        );
+
+        let actual = actual.unwrap();
+        let expected = b"[1]";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_invalid_encoding_it_errs() {
+        let actual = main(
+            &model::Transform {
+                encoding: Some(model::Encoding::Utf8),
+                ..model::stubs::transform()
+            },
+            "/a",
+            b"\xff".to_vec(),
+            &|_, _, _| unreachable!(),
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::InvalidEncoding {
+            path: "/a".into(),
+            encoding: model::Encoding::Utf8,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_undefined_variable_it_errs() {
+        let actual = main(
+            &model::Transform {
+                substitute_variables: true,
+                ..model::stubs::transform()
+            },
+            "/a",
+            b"${x}".to_vec(),
+            &|_, _, _| unreachable!(),
+            &|_| None,
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::UndefinedVariable {
+            name: "x".into(),
+            path: "/a".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_invalid_json_it_errs() {
+        let actual = main(
+            &model::Transform {
+                minify_json: true,
+                ..model::stubs::transform()
+            },
+            "/a",
+            b"{".to_vec(),
+            &|_, _, _| unreachable!(),
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::InvalidJson {
+            path: "/a".into(),
+            message: "EOF while parsing an object at line 1 column 1".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
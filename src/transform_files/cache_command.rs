@@ -0,0 +1,119 @@
+use crate::cache;
+use crate::hash;
+use crate::model;
+use std::fs;
+use std::path;
+
+pub fn main(
+    cache_folder: &path::Path,
+    command: &[String],
+    working_folder: &path::Path,
+    input: &[u8],
+    run_command: &dyn Fn() -> model::Result<Vec<u8>>,
+) -> model::Result<Vec<u8>> {
+    let cache_file = cache_folder.join(get_key(command, working_folder, input));
+
+    match fs::read(&cache_file) {
+        Ok(output) => Ok(output),
+        Err(_) => {
+            let output = run_command()?;
+            // Caching is best effort, so a failure only costs a rerun later.
+            let _ = cache::write_file(&cache_file, &output);
+            Ok(output)
+        }
+    }
+}
+
+fn get_key(command: &[String], working_folder: &path::Path, input: &[u8]) -> String {
+    let mut key = Vec::new();
+    for argument in command
+        .iter()
+        .map(|argument| argument.as_bytes())
+        .chain([working_folder.as_os_str().as_encoded_bytes()])
+    {
+        key.extend_from_slice(&(argument.len() as u64).to_le_bytes());
+        key.extend_from_slice(argument);
+    }
+    key.extend_from_slice(input);
+    hash::main(&key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell;
+
+    #[test]
+    fn given_cache_miss_it_runs_command_and_caches_output() {
+        let cache_folder = tempfile::tempdir().unwrap();
+        let folder = path::Path::new("/y");
+        let runs = cell::Cell::new(0);
+        let run_command = || {
+            runs.set(runs.get() + 1);
+            Ok(b"b".to_vec())
+        };
+
+        let actual = [
+            main(
+                cache_folder.path(),
+                &["x".into()],
+                folder,
+                b"a",
+                &run_command,
+            )
+            .unwrap(),
+            main(
+                cache_folder.path(),
+                &["x".into()],
+                folder,
+                b"a",
+                &run_command,
+            )
+            .unwrap(),
+        ];
+
+        let expected = [b"b".to_vec(), b"b".to_vec()];
+        assert_eq!(actual, expected);
+        assert_eq!(runs.get(), 1);
+    }
+
+    #[test]
+    fn handles_key_by_command_working_folder_and_input() {
+        let folder = path::Path::new("/d");
+        let actual = [
+            get_key(&["a".into()], folder, b"b"),
+            get_key(&["a".into()], folder, b"c"),
+            get_key(&["a".into(), "b".into()], folder, b""),
+            get_key(&["ab".into()], folder, b""),
+            get_key(&["ab".into()], path::Path::new("/e"), b""),
+            get_key(&["a".into()], path::Path::new("b"), b""),
+        ];
+
+        assert_eq!(actual[0].len(), 64);
+        for (index, key) in actual.iter().enumerate() {
+            assert!(!actual[..index].contains(key));
+        }
+    }
+
+    #[test]
+    fn given_failing_command_it_errs_without_caching() {
+        let cache_folder = tempfile::tempdir().unwrap();
+        let error = model::Error::CommandFailed {
+            command: vec!["x".into()],
+            path: "/a".into(),
+            message: "exit status: 1".into(),
+        };
+
+        let actual = main(
+            cache_folder.path(),
+            &["x".into()],
+            path::Path::new("/y"),
+            b"a",
+            &|| Err(error.clone()),
+        );
+
+        let actual = actual.unwrap_err();
+        assert_eq!(actual, error);
+        assert_eq!(fs::read_dir(cache_folder.path()).unwrap().count(), 0);
+    }
+}
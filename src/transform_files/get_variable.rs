@@ -0,0 +1,93 @@
+pub fn main(
+    name: &str,
+    get_environment_variable: &dyn Fn(&str) -> Option<String>,
+    get_git_commit: &dyn Fn() -> Option<String>,
+) -> Option<String> {
+    match name.split_once(':') {
+        Some(("cargo", name)) => {
+            let name = name.to_uppercase();
+            get_environment_variable(&format!("CARGO_{name}"))
+        }
+        Some(("env", name)) => get_environment_variable(name),
+        Some(("git", "commit")) => get_git_commit(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_cargo() {
+        let actual = main(
+            "cargo:pkg_version",
+            &|name| {
+                Some(
+                    (if name == "CARGO_PKG_VERSION" {
+                        "1.2.3"
+                    } else {
+                        unreachable!()
+                    })
+                    .into(),
+                )
+            },
+            &|| unreachable!(),
+        );
+
+        let expected = Some("1.2.3".into());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_env() {
+        let actual = main(
+            "env:MY_VARIABLE",
+            &|name| {
+                Some(
+                    (if name == "MY_VARIABLE" {
+                        "abc"
+                    } else {
+                        unreachable!()
+                    })
+                    .into(),
+                )
+            },
+            &|| unreachable!(),
+        );
+
+        let expected = Some("abc".into());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_git_commit() {
+        let actual = main("git:commit", &|_| unreachable!(), &|| {
+            Some("0123abc".into())
+        });
+
+        let expected = Some("0123abc".into());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_undefined_environment_variable_it_returns_none() {
+        let actual = main("env:MY_VARIABLE", &|_| None, &|| unreachable!());
+
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn given_unknown_namespace_it_returns_none() {
+        let actual = main("abc:def", &|_| unreachable!(), &|| unreachable!());
+
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn given_no_namespace_it_returns_none() {
+        let actual = main("abc", &|_| unreachable!(), &|| unreachable!());
+
+        assert_eq!(actual, None);
+    }
+}
@@ -0,0 +1,327 @@
+use super::apply_transform;
+use super::cache_command;
+use super::get_variable;
+use super::run_command;
+use crate::cache;
+use crate::filter;
+use crate::model;
+use std::cell;
+use std::env;
+use std::fs;
+use std::io;
+use std::path;
+
+pub fn main(
+    configuration: &model::Configuration,
+    paths: Vec<model::Path>,
+    get_variable: &apply_transform::GetVariable,
+) -> model::Result<Vec<model::Path>> {
+    transform_paths(
+        &configuration.transforms,
+        &configuration.overrides,
+        &configuration.pattern_syntax,
+        paths,
+        &|path| fs::read(path),
+        &|command, absolute_path, input| {
+            let cache_folder =
+                cache::get_folder("commands", &|name| env::var(name), &path::Path::is_file)?;
+            // A command runs in the folder of its file, which may change its
+            // output as much as the input does.
+            let working_folder = path::Path::new(absolute_path)
+                .parent()
+                .unwrap_or(path::Path::new("."));
+            cache_command::main(&cache_folder, command, working_folder, input, &|| {
+                run_command::main(command, working_folder, absolute_path, input)
+            })
+        },
+        get_variable,
+    )
+}
+
+// The Git commit is looked up at most once per macro invocation, in the current
+// working directory.
+pub fn resolve_variable(name: &str, git_commit: &cell::OnceCell<Option<String>>) -> Option<String> {
+    get_variable::main(name, &|name| env::var(name).ok(), &|| {
+        git_commit
+            .get_or_init(|| {
+                let command = GIT_COMMIT_COMMAND.map(String::from);
+                let output = run_command::main(&command, path::Path::new("."), "", &[]).ok()?;
+                Some(String::from_utf8(output).ok()?.trim().into())
+            })
+            .clone()
+    })
+}
+
+const GIT_COMMIT_COMMAND: [&str; 3] = ["git", "rev-parse", "HEAD"];
+
+fn transform_paths(
+    transforms: &[model::Transform],
+    overrides: &[model::Override],
+    pattern_syntax: &model::PatternSyntax,
+    paths: Vec<model::Path>,
+    read_file: &dyn Fn(&str) -> io::Result<Vec<u8>>,
+    run_command: &apply_transform::RunCommand,
+    get_variable: &apply_transform::GetVariable,
+) -> model::Result<Vec<model::Path>> {
+    let filters = transforms
+        .iter()
+        .map(|transform| filter::Filter::new(&transform.paths, pattern_syntax))
+        .collect::<model::Result<Vec<_>>>()?;
+    let override_filters = overrides
+        .iter()
+        .map(|override_| filter::Filter::new(&override_.paths, pattern_syntax))
+        .collect::<model::Result<Vec<_>>>()?;
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let relative_path = path.relative.join(NORMALIZED_FOLDER_SEPARATOR);
+            // The transform of the last matching override, if any, comes after
+            // the others.
+            let override_transform = overrides
+                .iter()
+                .zip(&override_filters)
+                .rfind(|(_, filter)| filter.is_match(&relative_path))
+                .and_then(|(override_, _)| override_.transform.as_ref());
+            let transforms = transforms
+                .iter()
+                .zip(&filters)
+                .filter(|(_, filter)| filter.is_match(&relative_path))
+                .map(|(transform, _)| transform)
+                .chain(override_transform)
+                .collect::<Vec<_>>();
+
+            transform_path(&transforms, path, read_file, run_command, get_variable)
+        })
+        .collect()
+}
+
+const NORMALIZED_FOLDER_SEPARATOR: &str = "/";
+
+fn transform_path(
+    transforms: &[&model::Transform],
+    path: model::Path,
+    read_file: &dyn Fn(&str) -> io::Result<Vec<u8>>,
+    run_command: &apply_transform::RunCommand,
+    get_variable: &apply_transform::GetVariable,
+) -> model::Result<model::Path> {
+    if transforms.is_empty() {
+        return Ok(path);
+    }
+
+    let mut contents = match path.contents {
+        None => read_file(&path.absolute).map_err(|source| model::Error::FileRead {
+            path: path.absolute.clone().into(),
+            source: model::IoError(source),
+        })?,
+        Some(contents) => contents,
+    };
+
+    for transform in transforms {
+        contents = apply_transform::main(
+            transform,
+            &path.absolute,
+            contents,
+            run_command,
+            get_variable,
+        )?;
+    }
+
+    Ok(model::Path {
+        contents: Some(contents),
+        ..path
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections;
+
+    #[test]
+    fn handles_no_transforms() {
+        let actual = transform_paths(
+            &[],
+            &[],
+            &model::PatternSyntax::default(),
+            vec![model::stubs::path()],
+            &|_| unreachable!(),
+            &|_, _, _| unreachable!(),
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap();
+        let expected = vec![model::stubs::path()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_transform_of_last_matching_override() {
+        let override_ = model::Override {
+            paths: "*.txt".into(),
+            initializer: None,
+            transform: None,
+            fields: collections::BTreeMap::new(),
+        };
+
+        let actual = transform_paths(
+            &[model::Transform {
+                paths: "*".into(),
+                line_endings: Some(model::LineEndings::Lf),
+                ..model::stubs::transform()
+            }],
+            &[
+                model::Override {
+                    transform: Some(model::Transform {
+                        minify_json: true,
+                        ..model::stubs::transform()
+                    }),
+                    ..override_.clone()
+                },
+                model::Override {
+                    paths: "/a/*".into(),
+                    transform: Some(model::Transform {
+                        trim_trailing_whitespace: true,
+                        ..model::stubs::transform()
+                    }),
+                    ..override_.clone()
+                },
+                model::Override {
+                    paths: "c.txt".into(),
+                    ..override_
+                },
+            ],
+            &model::PatternSyntax::default(),
+            vec![
+                model::Path {
+                    relative: vec!["a".into(), "b.txt".into()],
+                    absolute: "/a/b.txt".into(),
+                    ..model::stubs::path()
+                },
+                model::Path {
+                    relative: vec!["c.txt".into()],
+                    absolute: "/c.txt".into(),
+                    ..model::stubs::path()
+                },
+            ],
+            &|path| {
+                Ok(match path {
+                    "/a/b.txt" => "x \r\ny".into(),
+                    "/c.txt" => "z \r\n".into(),
+                    _ => unreachable!(),
+                })
+            },
+            &|_, _, _| unreachable!(),
+            &|_| unreachable!(),
+        );
+
+        let actual = actual
+            .unwrap()
+            .into_iter()
+            .map(|path| path.contents.unwrap())
+            .collect::<Vec<_>>();
+        let expected = vec![b"x\ny".to_vec(), b"z \n".to_vec()];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_matching_transforms_in_order() {
+        let actual = transform_paths(
+            &[
+                model::Transform {
+                    paths: "*.txt".into(),
+                    line_endings: Some(model::LineEndings::Lf),
+                    ..model::stubs::transform()
+                },
+                model::Transform {
+                    paths: "*.json".into(),
+                    minify_json: true,
+                    ..model::stubs::transform()
+                },
+                model::Transform {
+                    paths: "/a/*".into(),
+                    trim_trailing_whitespace: true,
+                    ..model::stubs::transform()
+                },
+            ],
+            &[],
+            &model::PatternSyntax::default(),
+            vec![
+                model::Path {
+                    relative: vec!["a".into(), "b.txt".into()],
+                    absolute: "/a/b.txt".into(),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    layer: None,
+                },
+                model::Path {
+                    relative: vec!["c.md".into()],
+                    absolute: "/c.md".into(),
+                    contents: None,
+                    size: None,
+                    hash: None,
+                    fingerprinted_path: None,
+                    layer: None,
+                },
+            ],
+            &|path| {
+                Ok((if path == "/a/b.txt" {
+                    "x \r\ny"
+                } else {
+                    unreachable!()
+                })
+                .into())
+            },
+            &|_, _, _| unreachable!(),
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap();
+        let expected = vec![
+            model::Path {
+                relative: vec!["a".into(), "b.txt".into()],
+                absolute: "/a/b.txt".into(),
+                contents: Some("x\ny".into()),
+                size: None,
+                hash: None,
+                fingerprinted_path: None,
+                layer: None,
+            },
+            model::Path {
+                relative: vec!["c.md".into()],
+                absolute: "/c.md".into(),
+                contents: None,
+                size: None,
+                hash: None,
+                fingerprinted_path: None,
+                layer: None,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_unreadable_file_it_errs() {
+        let actual = transform_paths(
+            &[model::stubs::transform()],
+            &[],
+            &model::PatternSyntax::default(),
+            vec![model::Path {
+                absolute: "/a".into(),
+                ..model::stubs::path()
+            }],
+            &|_| Err(io::Error::new(io::ErrorKind::NotFound, "not found")),
+            &|_, _, _| unreachable!(),
+            &|_| unreachable!(),
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::FileRead {
+            path: "/a".into(),
+            source: model::IoError(io::Error::new(io::ErrorKind::NotFound, "not found")),
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,51 @@
+pub fn main(contents: &[u8]) -> Result<Vec<u8>, String> {
+    serde_json::from_slice::<serde::de::IgnoredAny>(contents).map_err(|error| error.to_string())?;
+
+    let mut is_string = false;
+    let mut is_escaped = false;
+
+    Ok(contents
+        .iter()
+        .filter(|&&byte| {
+            if is_string {
+                if is_escaped {
+                    is_escaped = false;
+                } else if byte == b'\\' {
+                    is_escaped = true;
+                } else if byte == b'"' {
+                    is_string = false;
+                }
+                true
+            } else if byte == b'"' {
+                is_string = true;
+                true
+            } else {
+                !matches!(byte, b' ' | b'\t' | b'\n' | b'\r')
+            }
+        })
+        .copied()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_whitespace_outside_strings() {
+        let actual = main(b"{\n  \"a b\": [1, 7e-3],\r\n\t\"c\" : \"d\\\" e\"\n}\n");
+
+        let actual = actual.unwrap();
+        let expected = b"{\"a b\":[1,7e-3],\"c\":\"d\\\" e\"}";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_invalid_json_it_errs() {
+        let actual = main(b"[1,]");
+
+        let actual = actual.unwrap_err();
+        let expected = "expected value at line 1 column 4";
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,15 @@
+mod apply_transform;
+mod cache_command;
+mod get_variable;
+mod main;
+mod minify_json;
+mod normalize_line_endings;
+mod run_command;
+mod strip_bom;
+mod substitute_variables;
+mod transcode;
+mod trim_trailing_whitespace;
+
+pub use main::main;
+pub use main::resolve_variable;
+pub use substitute_variables::main as substitute_variables;
@@ -0,0 +1,45 @@
+use crate::model;
+
+pub fn main(contents: &[u8], line_endings: model::LineEndings) -> Vec<u8> {
+    let newline: &[u8] = match line_endings {
+        model::LineEndings::Crlf => b"\r\n",
+        model::LineEndings::Lf => b"\n",
+    };
+
+    let mut normalized = Vec::with_capacity(contents.len());
+    let mut bytes = contents.iter().peekable();
+
+    while let Some(&byte) = bytes.next() {
+        match byte {
+            b'\r' => {
+                bytes.next_if_eq(&&b'\n');
+                normalized.extend_from_slice(newline);
+            }
+            b'\n' => normalized.extend_from_slice(newline),
+            _ => normalized.push(byte),
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_crlf() {
+        let actual = main(b"a\nb\r\nc\rd\n\r", model::LineEndings::Crlf);
+
+        let expected = b"a\r\nb\r\nc\r\nd\r\n\r\n";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_lf() {
+        let actual = main(b"a\nb\r\nc\rd\n\r", model::LineEndings::Lf);
+
+        let expected = b"a\nb\nc\nd\n\n";
+        assert_eq!(actual, expected);
+    }
+}
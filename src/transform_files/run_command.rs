@@ -0,0 +1,112 @@
+use crate::model;
+use std::io;
+use std::io::Write;
+use std::path;
+use std::process;
+use std::thread;
+
+pub fn main(
+    command: &[String],
+    working_folder: &path::Path,
+    absolute_path: &str,
+    input: &[u8],
+) -> model::Result<Vec<u8>> {
+    run(command, working_folder, input).map_err(|message| model::Error::CommandFailed {
+        command: command.to_vec(),
+        path: absolute_path.into(),
+        message,
+    })
+}
+
+fn run(command: &[String], working_folder: &path::Path, input: &[u8]) -> Result<Vec<u8>, String> {
+    let (program, arguments) = command
+        .split_first()
+        .ok_or_else(|| String::from("empty command"))?;
+
+    let mut child = process::Command::new(program)
+        .args(arguments)
+        .current_dir(working_folder)
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()
+        .map_err(|error| error.to_string())?;
+
+    let output = thread::scope(|scope| {
+        let mut stdin = child.stdin.take();
+        // Writing in parallel avoids a deadlock if the command fills its output
+        // pipes before consuming all input.
+        let writer = scope.spawn(move || -> io::Result<()> {
+            if let Some(stdin) = &mut stdin {
+                stdin.write_all(input)?;
+            }
+            Ok(())
+        });
+        let output = child.wait_with_output();
+        let written = match writer.join().unwrap_or(Ok(())) {
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            written => written,
+        };
+        output.and_then(|output| written.map(|_| output))
+    })
+    .map_err(|error| error.to_string())?;
+
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("{}: {}", output.status, stderr.trim_end()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn handles_output() {
+        let actual = main(
+            &["tr".into(), "a-z".into(), "A-Z".into()],
+            path::Path::new("/"),
+            "/a",
+            b"bc",
+        );
+
+        let actual = actual.unwrap();
+        let expected = b"BC";
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn given_failing_command_it_errs() {
+        let actual = main(
+            &["sh".into(), "-c".into(), "echo oops >&2; exit 3".into()],
+            path::Path::new("/"),
+            "/a",
+            b"",
+        );
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::CommandFailed {
+            command: vec!["sh".into(), "-c".into(), "echo oops >&2; exit 3".into()],
+            path: "/a".into(),
+            message: "exit status: 3: oops".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_empty_command_it_errs() {
+        let actual = main(&[], path::Path::new("/"), "/a", b"");
+
+        let actual = actual.unwrap_err();
+        let expected = model::Error::CommandFailed {
+            command: vec![],
+            path: "/a".into(),
+            message: "empty command".into(),
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,29 @@
+pub fn main(contents: Vec<u8>) -> Vec<u8> {
+    match contents.strip_prefix(UTF_8_BOM) {
+        None => contents,
+        Some(contents) => contents.to_vec(),
+    }
+}
+
+const UTF_8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_bom() {
+        let actual = main(b"\xef\xbb\xbfa\xef\xbb\xbf".to_vec());
+
+        let expected = b"a\xef\xbb\xbf";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_no_bom() {
+        let actual = main(b"a".to_vec());
+
+        let expected = b"a";
+        assert_eq!(actual, expected);
+    }
+}
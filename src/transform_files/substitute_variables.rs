@@ -0,0 +1,88 @@
+pub fn main(
+    contents: &[u8],
+    get_variable: &dyn Fn(&str) -> Option<String>,
+) -> Result<Vec<u8>, String> {
+    let mut substituted = Vec::with_capacity(contents.len());
+    let mut rest = contents;
+
+    while let Some(start) = find(rest, b"$") {
+        let (before, after) = rest.split_at(start);
+        substituted.extend_from_slice(before);
+
+        if let Some(after) = after.strip_prefix(ESCAPED_START) {
+            substituted.extend_from_slice(START);
+            rest = after;
+        } else if let Some((name, after)) = after
+            .strip_prefix(START)
+            .and_then(|after| find(after, END).map(|end| (&after[..end], &after[end + 1..])))
+        {
+            let name = String::from_utf8_lossy(name);
+            let value = get_variable(&name).ok_or_else(|| name.into_owned())?;
+            substituted.extend_from_slice(value.as_bytes());
+            rest = after;
+        } else {
+            substituted.push(b'$');
+            rest = &after[1..];
+        }
+    }
+
+    substituted.extend_from_slice(rest);
+    Ok(substituted)
+}
+
+const START: &[u8] = b"${";
+
+const ESCAPED_START: &[u8] = b"$${";
+
+const END: &[u8] = b"}";
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_variables() {
+        let actual = main(b"a ${x} b ${y:z}${x}", &|name| match name {
+            "x" => Some("1".into()),
+            "y:z" => Some("${x}".into()),
+            _ => unreachable!(),
+        });
+
+        let actual = actual.unwrap();
+        let expected = b"a 1 b ${x}1";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_escaped_variables() {
+        let actual = main(b"$${x} $${x}${x}", &|_| Some("1".into()));
+
+        let actual = actual.unwrap();
+        let expected = b"${x} ${x}1";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_other_dollar_signs() {
+        let actual = main(b"$ $x ${x $", &|_| unreachable!());
+
+        let actual = actual.unwrap();
+        let expected = b"$ $x ${x $";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_undefined_variable_it_errs() {
+        let actual = main(b"${x}", &|_| None);
+
+        let actual = actual.unwrap_err();
+        let expected = "x";
+        assert_eq!(actual, expected);
+    }
+}
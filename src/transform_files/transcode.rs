@@ -0,0 +1,143 @@
+use crate::model;
+use std::str;
+
+pub fn main(contents: Vec<u8>, encoding: model::Encoding) -> Option<Vec<u8>> {
+    match encoding {
+        model::Encoding::Auto => match contents.as_slice() {
+            [0xff, 0xfe, ..] => decode_utf_16(&contents, u16::from_le_bytes),
+            [0xfe, 0xff, ..] => decode_utf_16(&contents, u16::from_be_bytes),
+            _ => Some(if str::from_utf8(&contents).is_ok() {
+                contents
+            } else {
+                decode_latin_1(&contents)
+            }),
+        },
+        model::Encoding::Latin1 => Some(decode_latin_1(&contents)),
+        model::Encoding::Utf8 => String::from_utf8(contents).ok().map(String::into_bytes),
+        model::Encoding::Utf16Be => decode_utf_16(&contents, u16::from_be_bytes),
+        model::Encoding::Utf16Le => decode_utf_16(&contents, u16::from_le_bytes),
+    }
+}
+
+fn decode_latin_1(contents: &[u8]) -> Vec<u8> {
+    contents
+        .iter()
+        .map(|&byte| char::from(byte))
+        .collect::<String>()
+        .into_bytes()
+}
+
+fn decode_utf_16(contents: &[u8], get_unit: fn([u8; 2]) -> u16) -> Option<Vec<u8>> {
+    let units = contents.chunks_exact(2);
+    if !units.remainder().is_empty() {
+        return None;
+    }
+
+    char::decode_utf16(units.map(|unit| get_unit([unit[0], unit[1]])))
+        .collect::<Result<String, _>>()
+        .ok()
+        .map(String::into_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(test)]
+    mod handles_auto {
+        use super::*;
+
+        #[test]
+        fn given_utf_16le_bom_it_decodes_utf_16le() {
+            let actual = main(b"\xff\xfea\x00".to_vec(), model::Encoding::Auto);
+
+            let actual = actual.unwrap();
+            let expected = "\u{feff}a".as_bytes();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_utf_16be_bom_it_decodes_utf_16be() {
+            let actual = main(b"\xfe\xff\x00a".to_vec(), model::Encoding::Auto);
+
+            let actual = actual.unwrap();
+            let expected = "\u{feff}a".as_bytes();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_valid_utf_8_it_keeps_contents() {
+            let actual = main("\u{feff}ä".as_bytes().to_vec(), model::Encoding::Auto);
+
+            let actual = actual.unwrap();
+            let expected = "\u{feff}ä".as_bytes();
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_invalid_utf_8_it_decodes_latin_1() {
+            let actual = main(b"\xe4".to_vec(), model::Encoding::Auto);
+
+            let actual = actual.unwrap();
+            let expected = "ä".as_bytes();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn handles_latin_1() {
+        let actual = main(b"a\xe4\xff".to_vec(), model::Encoding::Latin1);
+
+        let actual = actual.unwrap();
+        let expected = "aäÿ".as_bytes();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_utf_8() {
+        let actual = main("ä".as_bytes().to_vec(), model::Encoding::Utf8);
+
+        let actual = actual.unwrap();
+        let expected = "ä".as_bytes();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_utf_16be() {
+        let actual = main(b"\x00a\xd8\x3d\xdc\x7d".to_vec(), model::Encoding::Utf16Be);
+
+        let actual = actual.unwrap();
+        let expected = "a👽".as_bytes();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_utf_16le() {
+        let actual = main(b"a\x00\x3d\xd8\x7d\xdc".to_vec(), model::Encoding::Utf16Le);
+
+        let actual = actual.unwrap();
+        let expected = "a👽".as_bytes();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn given_invalid_utf_8_it_errs() {
+        let actual = main(b"\xe4".to_vec(), model::Encoding::Utf8);
+
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn given_odd_length_utf_16_it_errs() {
+        let actual = main(b"a\x00b".to_vec(), model::Encoding::Utf16Le);
+
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn given_unpaired_surrogate_it_errs() {
+        let actual = main(b"\x3d\xd8".to_vec(), model::Encoding::Utf16Le);
+
+        assert!(actual.is_none());
+    }
+}
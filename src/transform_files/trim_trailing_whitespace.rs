@@ -0,0 +1,44 @@
+pub fn main(contents: &[u8]) -> Vec<u8> {
+    contents
+        .split_inclusive(|&byte| byte == b'\n')
+        .flat_map(|line| {
+            let (line, newline) = split_newline(line);
+            [line.trim_ascii_end(), newline]
+        })
+        .flatten()
+        .copied()
+        .collect()
+}
+
+fn split_newline(line: &[u8]) -> (&[u8], &[u8]) {
+    let newline_length = if line.ends_with(b"\r\n") {
+        2
+    } else if line.ends_with(b"\n") {
+        1
+    } else {
+        0
+    };
+
+    line.split_at(line.len() - newline_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_empty_contents() {
+        let actual = main(b"");
+
+        let expected = b"";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn handles_lines() {
+        let actual = main(b" a \t\n\nb  \r\n c\x0c\r\n d ");
+
+        let expected = b" a\n\nb\r\n c\r\n d";
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,28 @@
+#[iftree::include_file_tree(
+    r"
+paths = '/*.sql'
+base_folder = 'tests/captured_files'
+captures = '^V(?<version>[0-9]+)__(?<name>\w+)\.sql$'
+"
+)]
+pub struct Migration {
+    version: u32,
+    name: &'static str,
+    contents_str: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        ASSETS
+            .iter()
+            .map(|migration| (migration.version, migration.name))
+            .collect::<Vec<_>>(),
+        [(1, "create_users"), (2, "add_email")],
+    );
+
+    assert_eq!(
+        base::V2__ADD_EMAIL_SQL.contents_str,
+        "ALTER TABLE users ADD COLUMN email TEXT;\n",
+    );
+}
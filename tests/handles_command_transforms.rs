@@ -0,0 +1,23 @@
+#![cfg(unix)]
+
+#[iftree::include_file_tree(
+    "
+paths = '/crlf.txt'
+base_folder = 'tests/transformed_files'
+
+[[transform]]
+paths = '*.txt'
+command = ['tr', 'a-z', 'A-Z']
+strip_bom = true
+line_endings = 'lf'
+"
+)]
+pub struct Asset {
+    contents_str: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(ASSETS.len(), 1);
+    assert_eq!(base::CRLF_TXT.contents_str, "HELLO \t\nWORLD\n");
+}
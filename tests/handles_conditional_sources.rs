@@ -0,0 +1,57 @@
+#[iftree::include_file_tree(
+    "
+paths = '/assets/*.md'
+base_folder = 'examples'
+
+[[source]]
+paths = '**'
+base_folder = 'tests/duplicate_files'
+mount = 'debug/'
+cfg = 'debug_assertions'
+
+[[source]]
+paths = '/b/**'
+base_folder = 'tests/duplicate_files'
+mount = 'release/'
+cfg = 'not(debug_assertions)'
+
+# Colliding paths only fail compilation where the predicate holds.
+[[source]]
+paths = '/assets/credits.md'
+base_folder = 'examples'
+cfg = 'any()'
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+}
+
+#[test]
+fn main() {
+    let relative_paths = ASSETS
+        .iter()
+        .map(|asset| asset.relative_path)
+        .collect::<Vec<_>>();
+
+    #[cfg(debug_assertions)]
+    {
+        assert_eq!(
+            relative_paths,
+            [
+                "assets/credits.md",
+                "debug/a.txt",
+                "debug/b/c.txt",
+                "debug/d.txt",
+            ],
+        );
+        assert_eq!(base::debug::D_TXT.relative_path, "debug/d.txt");
+    }
+
+    #[cfg(not(debug_assertions))]
+    {
+        assert_eq!(relative_paths, ["assets/credits.md", "release/b/c.txt"]);
+        assert_eq!(base::release::b::C_TXT.relative_path, "release/b/c.txt");
+    }
+
+    assert_eq!(base::assets::CREDITS_MD.relative_path, "assets/credits.md");
+}
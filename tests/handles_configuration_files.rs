@@ -0,0 +1,18 @@
+#[iftree::include_file_tree("configuration_file = 'tests/configuration_files/iftree.toml'")]
+pub struct Asset {
+    relative_path: &'static str,
+    contents_str: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        ASSETS
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        ["iftree/a.txt"],
+    );
+
+    assert_eq!(base::iftree::A_TXT.contents_str, "a\n");
+}
@@ -0,0 +1,34 @@
+#[iftree::include_file_tree(
+    "
+paths = '**'
+base_folder = 'tests/duplicate_files'
+deduplicate = true
+debug = true
+"
+)]
+pub struct Asset {
+    contents_bytes: &'static [u8],
+    contents_str: &'static str,
+    get_str: fn() -> std::borrow::Cow<'static, str>,
+}
+
+#[test]
+fn main() {
+    assert_eq!(ASSETS.len(), 3);
+
+    assert_eq!(base::A_TXT.contents_str, "same\n");
+    assert_eq!(base::b::C_TXT.contents_str, "same\n");
+    assert_eq!(base::D_TXT.contents_str, "other\n");
+    assert_eq!((base::b::C_TXT.get_str)(), "same\n");
+
+    assert_eq!(
+        base::A_TXT.contents_bytes.as_ptr(),
+        base::b::C_TXT.contents_bytes.as_ptr(),
+    );
+    assert_ne!(
+        base::A_TXT.contents_bytes.as_ptr(),
+        base::D_TXT.contents_bytes.as_ptr(),
+    );
+
+    assert!(DEBUG.ends_with("// Deduplication: 2 files share 1 contents, saving 5 bytes."));
+}
@@ -0,0 +1,51 @@
+#[iftree::include_file_tree(
+    "
+paths = '**'
+base_folder = 'tests/fingerprinted_files'
+
+[fingerprint]
+rewrite_references = '''
+*.css
+*.html
+'''
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+    fingerprinted_path: &'static str,
+    contents_str: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(ASSETS.len(), 3);
+
+    let logo = &base::images::LOGO_SVG;
+    assert_eq!(logo.fingerprinted_path.len(), "images/logo..svg".len() + 8);
+    assert!(logo.fingerprinted_path.starts_with("images/logo."));
+    assert!(logo.fingerprinted_path.ends_with(".svg"));
+
+    let style = &base::STYLE_CSS;
+    assert_eq!(
+        style.contents_str,
+        format!("body {{ background: url({}); }}\n", logo.fingerprinted_path),
+    );
+    assert_eq!(
+        base::INDEX_HTML.contents_str,
+        format!(
+            "<link rel=\"stylesheet\" href=\"/{}\">\n<img src=\"{}\">\n",
+            style.fingerprinted_path, logo.fingerprinted_path,
+        ),
+    );
+
+    for asset in &ASSETS {
+        let found = get_by_fingerprinted_path(asset.fingerprinted_path).unwrap();
+        assert_eq!(found.relative_path, asset.relative_path);
+    }
+    assert!(get_by_fingerprinted_path("style.css").is_none());
+
+    assert_eq!(BUNDLE_HASH.len(), 64);
+    assert_eq!(get_folder_hash(""), Some(BUNDLE_HASH));
+    assert_ne!(get_folder_hash("images"), Some(BUNDLE_HASH));
+    assert_eq!(get_folder_hash("unknown"), None);
+}
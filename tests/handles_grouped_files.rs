@@ -0,0 +1,41 @@
+#[iftree::include_file_tree(
+    r"
+paths = '/**'
+base_folder = 'examples/assets'
+
+[groups]
+json = '*.json'
+world = '/world/**'
+empty = '*.png'
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        groups::JSON
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        [
+            "configuration/menu.json",
+            "world/levels/tutorial.json",
+            "world/physical_constants.json",
+        ],
+    );
+    assert_eq!(
+        groups::WORLD
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        [
+            "world/levels/tutorial.json",
+            "world/physical_constants.json"
+        ],
+    );
+    assert!(groups::EMPTY.is_empty());
+    assert!(std::ptr::eq(groups::JSON[0], &ASSETS[1]));
+}
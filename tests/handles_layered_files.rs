@@ -0,0 +1,48 @@
+#[iftree::include_file_tree(
+    "
+paths = '**'
+base_folder = 'tests/layered_files/default'
+runtime_layers = true
+
+[[source]]
+paths = '**'
+base_folder = 'tests/layered_files/dark'
+layer = 'dark'
+
+[[source]]
+paths = '**'
+base_folder = 'tests/layered_files/contrast'
+layer = 'contrast'
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+    contents_str: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        ASSETS
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        ["a.txt", "b.txt", "c.txt", "a.txt", "b.txt"],
+    );
+
+    assert_eq!(base::A_TXT.contents_str, "dark a\n");
+    assert_eq!(base::B_TXT.contents_str, "contrast b\n");
+    assert_eq!(base::C_TXT.contents_str, "dark c\n");
+
+    let dark = select_layer("dark").unwrap();
+    assert_eq!(dark("a.txt").unwrap().contents_str, "dark a\n");
+    assert_eq!(dark("b.txt").unwrap().contents_str, "default b\n");
+    assert_eq!(dark("c.txt").unwrap().contents_str, "dark c\n");
+    assert!(dark("d.txt").is_none());
+
+    let contrast = select_layer("contrast").unwrap();
+    assert_eq!(contrast("a.txt").unwrap().contents_str, "dark a\n");
+    assert_eq!(contrast("b.txt").unwrap().contents_str, "contrast b\n");
+
+    assert!(select_layer("light").is_none());
+}
@@ -0,0 +1,40 @@
+#[iftree::include_file_tree(
+    "
+paths = '/assets/*.md'
+base_folder = 'examples'
+
+[[source]]
+paths = '**'
+base_folder = 'tests/duplicate_files'
+mount = 'duplicates/'
+
+[[source]]
+paths = '/b/**'
+base_folder = 'tests/duplicate_files'
+mount = 'more/duplicates'
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+    contents_str: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        ASSETS
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        [
+            "assets/credits.md",
+            "duplicates/a.txt",
+            "duplicates/b/c.txt",
+            "duplicates/d.txt",
+            "more/duplicates/b/c.txt",
+        ],
+    );
+
+    assert_eq!(base::duplicates::D_TXT.contents_str, "other\n");
+    assert_eq!(base::more::duplicates::b::C_TXT.contents_str, "same\n");
+}
@@ -0,0 +1,45 @@
+mod natural {
+    #[iftree::include_file_tree(
+        r"
+paths = '/**'
+base_folder = 'tests/ordered_files'
+order = 'natural'
+"
+    )]
+    pub struct Asset {
+        pub relative_path: &'static str,
+    }
+}
+
+mod locked {
+    #[iftree::include_file_tree(
+        r"
+paths = '/**'
+base_folder = 'tests/ordered_files'
+index_lock = 'tests/ordered_files.lock'
+"
+    )]
+    pub struct Asset {
+        pub relative_path: &'static str,
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        natural::ASSETS
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        ["a2.txt", "a10.txt", "b1.txt"],
+    );
+
+    assert_eq!(
+        locked::ASSETS
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        ["b1.txt", "a10.txt", "a2.txt"],
+    );
+    assert_eq!(locked::base::A2_TXT.relative_path, "a2.txt");
+}
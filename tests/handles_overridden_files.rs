@@ -0,0 +1,67 @@
+macro_rules! binary_asset {
+    ($relative_path:literal, $absolute_path:literal) => {
+        Asset {
+            relative_path: $relative_path,
+            contents_str: "(binary)",
+        }
+    };
+}
+
+#[iftree::include_file_tree(
+    r"
+paths = '/**'
+base_folder = 'tests/overridden_files'
+
+[[override]]
+paths = '*.json'
+transform.minify_json = true
+
+[[override]]
+paths = '*.png'
+initializer = 'binary_asset'
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+    contents_str: &'static str,
+}
+
+mod fields {
+    #[iftree::include_file_tree(
+        r#"
+paths = '/**'
+base_folder = 'tests/overridden_files'
+
+[[override]]
+paths = '*'
+fields = { contents_str = '""' }
+
+[[override]]
+paths = '*.txt'
+"#
+    )]
+    pub struct Asset {
+        pub relative_path: &'static str,
+        pub contents_str: &'static str,
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(base::DATA_JSON.contents_str, r#"{"a":1}"#);
+    assert_eq!(base::HELLO_TXT.contents_str, "hello\n");
+    assert_eq!(base::IMAGE_PNG.contents_str, "(binary)");
+    assert_eq!(base::IMAGE_PNG.relative_path, "image.png");
+
+    assert_eq!(
+        fields::ASSETS
+            .iter()
+            .map(|asset| (asset.relative_path, asset.contents_str))
+            .collect::<Vec<_>>(),
+        [
+            ("data.json", ""),
+            ("hello.txt", "hello\n"),
+            ("image.png", ""),
+        ],
+    );
+}
@@ -0,0 +1,25 @@
+#[iftree::include_file_tree(
+    "
+paths = '''
+**/*.JSON
+!world/levels/**
+'''
+base_folder = 'examples/assets'
+pattern_syntax = 'glob'
+case_insensitive = true
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        ASSETS
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        ["configuration/menu.json", "world/physical_constants.json"],
+    );
+}
@@ -0,0 +1,46 @@
+#[iftree::include_file_tree(
+    "
+paths = '''
+/configuration/**
+/credits.md
+'''
+base_folder = 'examples/assets'
+aliases = { 'about' = 'static/credits' }
+
+[[rewrite]]
+strip_prefix = 'configuration/'
+extensions = { md = '' }
+
+[[rewrite]]
+paths = '*.json'
+rename = { regex = '^menu', replacement = 'navigation' }
+
+[[rewrite]]
+add_prefix = 'static/'
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+    contents_str: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        ASSETS
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        [
+            "static/credits",
+            "static/navigation.json",
+            "static/translations.csv",
+        ],
+    );
+
+    assert!(std::ptr::eq(base::ABOUT, base::r#static::CREDITS));
+    assert_eq!(
+        base::r#static::NAVIGATION_JSON.contents_str,
+        include_str!("../examples/assets/configuration/menu.json"),
+    );
+}
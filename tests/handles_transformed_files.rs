@@ -0,0 +1,44 @@
+#[iftree::include_file_tree(
+    "
+paths = '**'
+base_folder = 'tests/transformed_files'
+
+[[transform]]
+paths = '*.txt'
+encoding = 'auto'
+strip_bom = true
+line_endings = 'lf'
+trim_trailing_whitespace = true
+
+[[transform]]
+paths = '*.json'
+minify_json = true
+
+[[transform]]
+paths = '*.html'
+substitute_variables = true
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+    contents_bytes: &'static [u8],
+}
+
+#[test]
+fn main() {
+    assert_eq!(ASSETS.len(), 6);
+    assert_eq!(ASSETS[0].relative_path, "binary.bin");
+
+    assert_eq!(base::BINARY_BIN.contents_bytes, b"\x00\x01\r\n");
+    assert_eq!(base::CRLF_TXT.contents_bytes, b"Hello\nWorld\n");
+    assert_eq!(
+        base::DATA_JSON.contents_bytes,
+        br#"{"name":"a b","values":[1,2]}"#,
+    );
+    assert_eq!(base::LATIN_1_TXT.contents_bytes, "Grüße\n".as_bytes());
+    assert_eq!(
+        base::TEMPLATE_HTML.contents_bytes,
+        format!("<p>iftree {} ${{x}}</p>\n", env!("CARGO_PKG_VERSION")).as_bytes(),
+    );
+    assert_eq!(base::UTF_16_TXT.contents_bytes, b"Hi\n");
+}
@@ -0,0 +1,42 @@
+mod app {
+    #[iftree::include_file_tree(
+        r"
+paths = '/**'
+base_folder = 'tests/variant_files/app'
+variants = { map = '.map' }
+"
+    )]
+    pub struct Asset {
+        pub relative_path: &'static str,
+        pub contents_str: &'static str,
+        pub map: Option<&'static str>,
+    }
+}
+
+mod cases {
+    #[iftree::include_file_tree(
+        r"
+paths = '/**'
+base_folder = 'tests/variant_files/cases'
+variants = { input = '.input', expected = '.expected' }
+"
+    )]
+    pub struct Case {
+        pub relative_path: &'static str,
+        pub input: Option<&'static str>,
+        pub expected: Option<&'static [u8]>,
+    }
+}
+
+#[test]
+fn main() {
+    assert_eq!(app::ASSETS.len(), 1);
+    assert_eq!(app::base::APP_JS.relative_path, "app.js");
+    assert_eq!(app::base::APP_JS.contents_str, "console.log(\"app\");\n");
+    assert_eq!(app::base::APP_JS.map, Some("{\"version\":3}\n"));
+
+    assert_eq!(cases::ASSETS.len(), 1);
+    assert_eq!(cases::base::SUM.relative_path, "sum");
+    assert_eq!(cases::base::SUM.input, Some("1 + 2\n"));
+    assert_eq!(cases::base::SUM.expected, Some(&b"3\n"[..]));
+}
@@ -0,0 +1,39 @@
+#[iftree::include_file_tree(
+    r"
+paths = '/credits.md'
+base_folder = 'examples/assets'
+
+[[file]]
+path = 'robots.txt'
+contents = '''
+User-agent: *
+Disallow:
+'''
+
+[[file]]
+path = 'meta/version.txt'
+contents = '${cargo:pkg_name} ${cargo:pkg_version}'
+substitute_variables = true
+"
+)]
+pub struct Asset {
+    relative_path: &'static str,
+    contents_str: &'static str,
+}
+
+#[test]
+fn main() {
+    assert_eq!(
+        ASSETS
+            .iter()
+            .map(|asset| asset.relative_path)
+            .collect::<Vec<_>>(),
+        ["credits.md", "meta/version.txt", "robots.txt"],
+    );
+
+    assert_eq!(base::ROBOTS_TXT.contents_str, "User-agent: *\nDisallow:\n");
+    assert_eq!(
+        base::meta::VERSION_TXT.contents_str,
+        concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")),
+    );
+}